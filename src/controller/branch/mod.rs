@@ -0,0 +1,308 @@
+// Module: controller::branch
+use crate::helpers::git::{Git, GitError};
+use crate::helpers::services::{get_service_repositories, split_service_list};
+
+/// The branch operations accepted by `tools branch`
+#[derive(Debug, PartialEq)]
+pub enum BranchAction {
+    Create(String),
+    Checkout(String),
+    Delete(String),
+    List(Option<String>),
+}
+
+/// The options accepted by `tools branch`
+#[derive(Debug, PartialEq)]
+pub struct BranchOptions {
+    pub action: BranchAction,
+    pub only: Vec<String>, // only operate on these services, empty means all services
+    pub force: bool,       // delete branches even if they are not merged
+}
+
+// ## parse_args(args: &[String]) -> Result<BranchOptions, String>
+// This function parses the arguments of `tools branch`
+//
+// ### arguments:
+// - args: &[String] - the arguments after `branch`
+//
+// ### returns:
+// - Result<BranchOptions, String> - the parsed options or a message describing the error
+pub fn parse_args(args: &[String]) -> Result<BranchOptions, String> {
+    let mut only = Vec::new();
+    let mut force = false;
+    let mut positional = Vec::new();
+
+    let mut index = 0;
+    while index < args.len() {
+        match args[index].as_str() {
+            "--only" => {
+                index += 1;
+                match args.get(index) {
+                    Some(services) => only.extend(split_service_list(services)),
+                    None => return Err("--only expects a list of services".to_string()),
+                }
+            }
+            "-f" | "--force" => force = true,
+            unknown if unknown.starts_with('-') => {
+                return Err(format!("Unknown option: {}", unknown))
+            }
+            value => positional.push(value.to_string()),
+        }
+        index += 1;
+    }
+
+    let mut positional = positional.into_iter();
+    let action = match (positional.next().as_deref(), positional.next()) {
+        (Some("create"), Some(name)) => BranchAction::Create(name),
+        (Some("checkout"), Some(name)) => BranchAction::Checkout(name),
+        (Some("delete"), Some(name)) => BranchAction::Delete(name),
+        (Some("list"), name) => BranchAction::List(name),
+        (Some(action @ ("create" | "checkout" | "delete")), None) => {
+            return Err(format!("`tools branch {}` expects a branch name", action))
+        }
+        (Some(action), _) => return Err(format!("Unknown branch action: {}", action)),
+        (None, _) => return Err("No branch action given".to_string()),
+    };
+
+    if let Some(extra) = positional.next() {
+        return Err(format!("Unexpected argument: {}", extra));
+    }
+
+    Ok(BranchOptions {
+        action,
+        only,
+        force,
+    })
+}
+
+// ## branch(options: BranchOptions)
+// This function runs a branch operation over every service repository
+//
+// ### arguments:
+// - options: BranchOptions - the parsed options of the command
+//
+// ### returns:
+// - nothing
+pub fn branch(options: BranchOptions) {
+    let repositories = get_service_repositories(&options.only);
+
+    if repositories.is_empty() {
+        eprintln!("No service repositories matched");
+        std::process::exit(1);
+    }
+
+    let success = match &options.action {
+        BranchAction::Create(name) => create(&repositories, name),
+        BranchAction::Checkout(name) => checkout(&repositories, name),
+        BranchAction::Delete(name) => delete(&repositories, name, options.force),
+        BranchAction::List(name) => list(&repositories, name.as_deref()),
+    };
+
+    if !success {
+        std::process::exit(1);
+    }
+}
+
+// ## ensure_clean(repositories: &[(String, Git)]) -> bool
+// This function checks that no service has uncommitted changes to tracked
+// files, printing the services that do. Untracked files, like the ones
+// `tools init` bootstraps from templates, are carried over to the branch
+fn ensure_clean(repositories: &[(String, Git)]) -> bool {
+    let mut clean = true;
+
+    for (service, repository) in repositories {
+        match repository.status() {
+            Ok(status) if !status.has_tracked_changes() => {}
+            Ok(_) => {
+                eprintln!("{} has uncommitted changes", service);
+                clean = false;
+            }
+            Err(error) => {
+                eprintln!("{}: {}", service, error);
+                clean = false;
+            }
+        }
+    }
+
+    if !clean {
+        eprintln!("Refusing to switch branches, commit or stash the changes first");
+    }
+
+    clean
+}
+
+// ## has_branch(repository: &Git, name: &str) -> Result<bool, GitError>
+// This function checks if a repository has a local branch
+fn has_branch(repository: &Git, name: &str) -> Result<bool, GitError> {
+    Ok(repository
+        .branches()?
+        .iter()
+        .any(|branch| branch.name == name))
+}
+
+// ## create(repositories: &[(String, Git)], name: &str) -> bool
+// This function creates and checks out a branch in every service that
+// doesn't have it yet
+fn create(repositories: &[(String, Git)], name: &str) -> bool {
+    if !ensure_clean(repositories) {
+        return false;
+    }
+
+    let mut success = true;
+    for (service, repository) in repositories {
+        let result = has_branch(repository, name).and_then(|exists| {
+            if exists {
+                return Ok("already has the branch");
+            }
+            repository.create_branch(name, None)?;
+            repository.checkout(name, false)?;
+            Ok("created")
+        });
+
+        match result {
+            Ok(message) => println!("{} - {}", service, message),
+            Err(error) => {
+                eprintln!("{} - {}", service, error);
+                success = false;
+            }
+        }
+    }
+
+    success
+}
+
+// ## checkout(repositories: &[(String, Git)], name: &str) -> bool
+// This function checks out a branch in every service that has it
+fn checkout(repositories: &[(String, Git)], name: &str) -> bool {
+    if !ensure_clean(repositories) {
+        return false;
+    }
+
+    let mut success = true;
+    for (service, repository) in repositories {
+        let result = has_branch(repository, name).and_then(|exists| {
+            if !exists {
+                return Ok("doesn't have the branch");
+            }
+            repository.checkout(name, false)?;
+            Ok("checked out")
+        });
+
+        match result {
+            Ok(message) => println!("{} - {}", service, message),
+            Err(error) => {
+                eprintln!("{} - {}", service, error);
+                success = false;
+            }
+        }
+    }
+
+    success
+}
+
+// ## delete(repositories: &[(String, Git)], name: &str, force: bool) -> bool
+// This function deletes a branch in every service that has it, unless it
+// is the branch that is checked out
+fn delete(repositories: &[(String, Git)], name: &str, force: bool) -> bool {
+    let mut success = true;
+
+    for (service, repository) in repositories {
+        let result = repository.branches().and_then(|branches| {
+            match branches.iter().find(|branch| branch.name == name) {
+                None => Ok("doesn't have the branch"),
+                Some(branch) if branch.is_head => Ok("has the branch checked out, not deleted"),
+                Some(_) => {
+                    repository.delete_branch(name, force)?;
+                    Ok("deleted")
+                }
+            }
+        });
+
+        match result {
+            Ok(message) => println!("{} - {}", service, message),
+            Err(error) => {
+                eprintln!("{} - {}", service, error);
+                success = false;
+            }
+        }
+    }
+
+    success
+}
+
+// ## list(repositories: &[(String, Git)], name: Option<&str>) -> bool
+// This function prints a matrix of which services have which branches,
+// `*` marks the branch that is checked out and `+` a branch that exists
+fn list(repositories: &[(String, Git)], name: Option<&str>) -> bool {
+    let mut success = true;
+    let mut branch_names: Vec<String> = Vec::new();
+    let mut service_branches = Vec::new();
+
+    for (service, repository) in repositories {
+        match repository.branches() {
+            Ok(branches) => {
+                for branch in &branches {
+                    if !branch_names.contains(&branch.name)
+                        && name.is_none_or(|name| name == branch.name)
+                    {
+                        branch_names.push(branch.name.clone());
+                    }
+                }
+                service_branches.push((service, branches));
+            }
+            Err(error) => {
+                eprintln!("{} - {}", service, error);
+                success = false;
+            }
+        }
+    }
+    branch_names.sort();
+
+    let width = branch_names
+        .iter()
+        .map(|branch| branch.len())
+        .max()
+        .unwrap_or(0)
+        .max("Branch".len());
+
+    print!("{:width$}", "Branch", width = width);
+    for (service, _) in &service_branches {
+        print!("  {}", service);
+    }
+    println!();
+
+    for branch_name in &branch_names {
+        print!("{:width$}", branch_name, width = width);
+        for (service, branches) in &service_branches {
+            let mark = match branches.iter().find(|branch| &branch.name == branch_name) {
+                Some(branch) if branch.is_head => "*",
+                Some(_) => "+",
+                None => "-",
+            };
+            print!("  {:^width$}", mark, width = service.len());
+        }
+        println!();
+    }
+
+    success
+}
+
+#[test]
+fn test_branch_parse_args() {
+    let args = ["create", "feature/login", "--only", "backend,frontend"].map(String::from);
+
+    assert_eq!(
+        parse_args(&args),
+        Ok(BranchOptions {
+            action: BranchAction::Create("feature/login".to_string()),
+            only: vec!["backend".to_string(), "frontend".to_string()],
+            force: false,
+        })
+    );
+    assert_eq!(
+        parse_args(&["list".to_string()]).unwrap().action,
+        BranchAction::List(None)
+    );
+    assert!(parse_args(&["checkout".to_string()]).is_err());
+    assert!(parse_args(&["rename".to_string(), "x".to_string()]).is_err());
+}
@@ -0,0 +1,387 @@
+// Module: controller::changelog
+use crate::helpers::conventional_commits::{ConventionalCommit, COMMIT_TYPES};
+use crate::helpers::git::log::Commit;
+use crate::helpers::git::{Git, GitError};
+use crate::CONFIG;
+
+/// The changes of the project or of a single service between two refs
+#[derive(Debug, PartialEq)]
+pub struct ServiceChanges {
+    pub name: String,         // the project name or the path of the service
+    pub from: Option<String>, // the commit at the start of the range, None for added services
+    pub to: Option<String>,   // the commit at the end of the range, None for removed services
+    pub commits: Vec<Commit>,
+    pub error: Option<String>, // set if the commits of the service couldn't be read
+}
+
+// ## parse_range(range: &str) -> Result<(String, String), String>
+// This function splits a `<from>..<to>` range, `<from>..` ends at HEAD
+//
+// ### arguments:
+// - range: &str - the revision range
+//
+// ### returns:
+// - Result<(String, String), String> - (from, to) or a message describing the error
+pub fn parse_range(range: &str) -> Result<(String, String), String> {
+    match range.split_once("..") {
+        Some(("", _)) => Err(format!("The range {} has no start", range)),
+        Some((from, to)) if !to.starts_with('.') => Ok((
+            from.to_string(),
+            if to.is_empty() { "HEAD" } else { to }.to_string(),
+        )),
+        _ => Err(format!(
+            "Expected a range like v0.1.0..v0.2.0, got {}",
+            range
+        )),
+    }
+}
+
+/// The options accepted by `tools changelog`
+#[derive(Debug, PartialEq)]
+pub struct ChangelogOptions {
+    pub from: String,           // the ref at the start of the range
+    pub to: String,             // the ref at the end of the range, HEAD if not given
+    pub output: Option<String>, // the file to write, stdout if None
+}
+
+// ## parse_args(args: &[String]) -> Result<ChangelogOptions, String>
+// This function parses the arguments of `tools changelog`
+//
+// ### arguments:
+// - args: &[String] - the arguments after `changelog`
+//
+// ### returns:
+// - Result<ChangelogOptions, String> - the parsed options or a message describing the error
+pub fn parse_args(args: &[String]) -> Result<ChangelogOptions, String> {
+    let mut range = None;
+    let mut output = None;
+
+    let mut index = 0;
+    while index < args.len() {
+        match args[index].as_str() {
+            option @ ("-o" | "--output") => {
+                index += 1;
+                let Some(value) = args.get(index).cloned() else {
+                    return Err(format!("{} expects a value", option));
+                };
+                output = Some(value);
+            }
+            unknown if unknown.starts_with('-') => {
+                return Err(format!("Unknown option: {}", unknown))
+            }
+            value if range.is_some() => return Err(format!("Unexpected argument: {}", value)),
+            value => range = Some(value.to_string()),
+        }
+        index += 1;
+    }
+
+    let (from, to) = parse_range(&range.ok_or("No range given".to_string())?)?;
+    Ok(ChangelogOptions { from, to, output })
+}
+
+// ## changelog(options: ChangelogOptions)
+// This function prints, or writes with `--output <file>`, the Markdown
+// changelog of the project and every service between two refs
+//
+// ### arguments:
+// - options: ChangelogOptions - the parsed options of the command
+//
+// ### returns:
+// - nothing
+pub fn changelog(options: ChangelogOptions) {
+    let ChangelogOptions { from, to, output } = options;
+
+    let changes = match collect_changes(&from, &to) {
+        Ok(changes) => changes,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    };
+
+    let markdown = render_markdown(&format!("{}..{}", from, to), &changes);
+
+    match output {
+        Some(output) => match std::fs::write(&output, markdown) {
+            Ok(_) => println!("Wrote changelog to {}", output),
+            Err(error) => {
+                eprintln!("Couldn't write {}: {}", output, error);
+                std::process::exit(1);
+            }
+        },
+        None => print!("{}", markdown),
+    }
+}
+
+// ## collect_changes(from: &str, to: &str) -> Result<Vec<ServiceChanges>, GitError>
+// This function collects the commits of the superproject between two refs
+// and, for every submodule whose pointer moved in that range, the commits
+// of the service between the two pointers
+//
+// ### arguments:
+// - from: &str - the ref at the start of the range
+// - to: &str - the ref at the end of the range
+//
+// ### returns:
+// - Result<Vec<ServiceChanges>, GitError> - the project first, then every changed service
+pub fn collect_changes(from: &str, to: &str) -> Result<Vec<ServiceChanges>, GitError> {
+    let project = Git::project();
+
+    let mut changes = vec![ServiceChanges {
+        name: CONFIG.project_name.clone(),
+        from: Some(project.short_hash(from)?),
+        to: Some(project.short_hash(to)?),
+        commits: project.log(&format!("{}..{}", from, to))?,
+        error: None,
+    }];
+
+    let from_links = project.gitlinks(from)?;
+    let to_links = project.gitlinks(to)?;
+
+    // services that exist at the end of the range, then the removed ones
+    let mut paths = to_links
+        .iter()
+        .map(|gitlink| gitlink.path.clone())
+        .collect::<Vec<String>>();
+    for gitlink in &from_links {
+        if !paths.contains(&gitlink.path) {
+            paths.push(gitlink.path.clone());
+        }
+    }
+
+    for path in paths {
+        let from_commit = from_links
+            .iter()
+            .find(|gitlink| gitlink.path == path)
+            .map(|gitlink| gitlink.commit.clone());
+        let to_commit = to_links
+            .iter()
+            .find(|gitlink| gitlink.path == path)
+            .map(|gitlink| gitlink.commit.clone());
+
+        if from_commit == to_commit {
+            continue;
+        }
+
+        let mut service = ServiceChanges {
+            name: path.clone(),
+            from: from_commit.clone(),
+            to: to_commit.clone(),
+            commits: Vec::new(),
+            error: None,
+        };
+
+        // only a moved pointer has a range of commits
+        if let (Some(from_commit), Some(to_commit)) = (from_commit, to_commit) {
+            let repository = Git::new(format!("{}/{}", CONFIG.project_path, path));
+            let range = format!("{}..{}", from_commit, to_commit);
+
+            // the commits may not have been fetched in the service yet
+            match repository
+                .log(&range)
+                .or_else(|_| repository.fetch(None).and_then(|_| repository.log(&range)))
+            {
+                Ok(commits) => service.commits = commits,
+                Err(error) => service.error = Some(error.to_string()),
+            }
+        }
+
+        changes.push(service);
+    }
+
+    Ok(changes)
+}
+
+// ## render_markdown(title: &str, changes: &[ServiceChanges]) -> String
+// This function renders the changes as Markdown, grouping the commits of
+// every service by their conventional commit type
+//
+// ### arguments:
+// - title: &str - the title of the changelog, e.g. the version or range
+// - changes: &[ServiceChanges] - the changes as returned by `collect_changes`
+//
+// ### returns:
+// - String - the Markdown changelog
+pub fn render_markdown(title: &str, changes: &[ServiceChanges]) -> String {
+    let mut markdown = format!("# {}\n", title);
+
+    for service in changes {
+        let short = |commit: &Option<String>| {
+            commit
+                .as_deref()
+                .map(|commit| commit.chars().take(7).collect::<String>())
+        };
+
+        markdown.push_str(&format!("\n## {}", service.name));
+        match (short(&service.from), short(&service.to)) {
+            (Some(from), Some(to)) => markdown.push_str(&format!(" ({}..{})\n", from, to)),
+            (None, Some(to)) => markdown.push_str(&format!(" (added at {})\n", to)),
+            (Some(from), None) => markdown.push_str(&format!(" (removed, was {})\n", from)),
+            (None, None) => markdown.push('\n'),
+        }
+
+        if let Some(error) = &service.error {
+            markdown.push_str(&format!("\n_Couldn't read the commits: {}_\n", error));
+            continue;
+        }
+
+        let commits = service
+            .commits
+            .iter()
+            .filter(|commit| !commit.subject.starts_with("Merge "))
+            .map(|commit| {
+                (
+                    commit,
+                    ConventionalCommit::parse(&commit.subject, &commit.body),
+                )
+            })
+            .collect::<Vec<(&Commit, Option<ConventionalCommit>)>>();
+
+        if commits.is_empty() {
+            if service.from.is_some() && service.to.is_some() {
+                markdown.push_str("\n_No changes_\n");
+            }
+            continue;
+        }
+
+        // breaking changes are listed first, whatever their type, and only there
+        let breaking = commits
+            .iter()
+            .filter(|(_, conventional)| conventional.as_ref().is_some_and(|c| c.breaking))
+            .collect::<Vec<_>>();
+        push_section(&mut markdown, "Breaking Changes", &breaking);
+
+        for (commit_type, section) in COMMIT_TYPES {
+            let entries = commits
+                .iter()
+                .filter(|(_, conventional)| {
+                    conventional
+                        .as_ref()
+                        .is_some_and(|c| c.commit_type == commit_type && !c.breaking)
+                })
+                .collect::<Vec<_>>();
+            push_section(&mut markdown, section, &entries);
+        }
+
+        let other = commits
+            .iter()
+            .filter(|(_, conventional)| {
+                conventional
+                    .as_ref()
+                    .is_none_or(|c| !c.is_known_type() && !c.breaking)
+            })
+            .collect::<Vec<_>>();
+        push_section(&mut markdown, "Other Changes", &other);
+    }
+
+    markdown
+}
+
+// ## push_section(markdown, title, entries)
+// This function appends a `###` section listing the commits, if there are any
+fn push_section(
+    markdown: &mut String,
+    title: &str,
+    entries: &[&(&Commit, Option<ConventionalCommit>)],
+) {
+    if entries.is_empty() {
+        return;
+    }
+
+    markdown.push_str(&format!("\n### {}\n\n", title));
+    for (commit, conventional) in entries {
+        let line = match conventional {
+            Some(ConventionalCommit {
+                scope: Some(scope),
+                description,
+                ..
+            }) => format!("**{}:** {}", scope, description),
+            Some(conventional) => conventional.description.clone(),
+            None => commit.subject.clone(),
+        };
+        markdown.push_str(&format!("- {} ({})\n", line, commit.short_hash));
+    }
+}
+
+#[test]
+fn test_changelog_parse_range() {
+    assert_eq!(
+        parse_range("v0.1.0..v0.2.0"),
+        Ok(("v0.1.0".to_string(), "v0.2.0".to_string()))
+    );
+    assert_eq!(
+        parse_range("v0.1.0.."),
+        Ok(("v0.1.0".to_string(), "HEAD".to_string()))
+    );
+    assert!(parse_range("v0.1.0").is_err());
+    assert!(parse_range("..v0.2.0").is_err());
+    assert!(parse_range("v0.1.0...v0.2.0").is_err());
+}
+
+#[test]
+fn test_changelog_parse_args() {
+    let args = |args: &str| {
+        args.split_whitespace()
+            .map(String::from)
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(
+        parse_args(&args("v0.1.0.. -o CHANGELOG.md")),
+        Ok(ChangelogOptions {
+            from: "v0.1.0".to_string(),
+            to: "HEAD".to_string(),
+            output: Some("CHANGELOG.md".to_string()),
+        })
+    );
+    assert!(parse_args(&args("v0.1.0..v0.2.0 -o")).is_err());
+    assert!(parse_args(&args("v0.1.0..v0.2.0 --outptu CHANGELOG.md")).is_err());
+    assert!(parse_args(&args("v0.1.0..v0.2.0 v0.2.0..v0.3.0")).is_err());
+    assert!(parse_args(&args("-o CHANGELOG.md")).is_err());
+}
+
+#[test]
+fn test_changelog_render_markdown() {
+    let commit = |short_hash: &str, subject: &str, body: &str| Commit {
+        hash: short_hash.to_string(),
+        short_hash: short_hash.to_string(),
+        author_name: "Diana".to_string(),
+        author_email: "diana@example.com".to_string(),
+        date: "2023-11-03T10:00:00+00:00".to_string(),
+        subject: subject.to_string(),
+        body: body.to_string(),
+    };
+
+    let changes = vec![ServiceChanges {
+        name: "backend".to_string(),
+        from: Some("3f2a1b4c5d".to_string()),
+        to: Some("9e8d7c6b5a".to_string()),
+        commits: vec![
+            commit("1111111", "feat(api)!: drop v1 endpoints", ""),
+            commit("2222222", "fix: handle empty config", ""),
+            commit("3333333", "Update README", ""),
+            commit("4444444", "Merge branch 'main'", ""),
+        ],
+        error: None,
+    }];
+
+    assert_eq!(
+        render_markdown("v0.1.0..v0.2.0", &changes),
+        "# v0.1.0..v0.2.0
+
+## backend (3f2a1b4..9e8d7c6)
+
+### Breaking Changes
+
+- **api:** drop v1 endpoints (1111111)
+
+### Bug Fixes
+
+- handle empty config (2222222)
+
+### Other Changes
+
+- Update README (3333333)
+"
+    );
+}
@@ -0,0 +1,835 @@
+// Module: controller::compose
+use crate::helpers::config::environment::{
+    get_environment, parse_environment_option, select_environment,
+};
+use crate::helpers::config::project::get_project;
+use crate::helpers::docker::docker_compose::file::{
+    get_base_docker_compose_file, get_path_to_docker_compose, read_docker_compose_files,
+    write_docker_compose_file,
+};
+use crate::helpers::docker::docker_compose::interpolate::{
+    apply_profiles, load_variables, resolve,
+};
+use crate::helpers::docker::docker_compose::lint::{lint, Severity};
+use crate::helpers::docker::docker_compose::merge::merge_compose_texts;
+use crate::helpers::version::get_version_info;
+use crate::models::docker_compose::service::{Build, Service};
+use crate::models::docker_compose::{Config, DockerCompose, Network, Secret, Volume};
+use crate::CONFIG;
+
+/// The top level entries of a compose file `tools compose` edits
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resource {
+    Service,
+    Network,
+    Volume,
+    Secret,
+    Config,
+}
+
+/// A change to the compose file made by `tools compose add|remove|rename`
+#[derive(Debug, PartialEq)]
+pub enum ComposeEdit {
+    AddService {
+        name: String,
+        image: Option<String>,
+        build: Option<String>,
+        ports: Vec<String>,
+        environment: Vec<String>, // KEY=VALUE, or KEY to pass the host value through
+        networks: Vec<String>,
+    },
+    AddResource {
+        resource: Resource,
+        name: String,
+        driver: Option<String>,
+        file: Option<String>,
+        environment: Option<String>,
+    },
+    Attach {
+        resource: Resource,
+        name: String,
+        services: Vec<String>,
+        target: Option<String>, // where a volume is mounted
+        read_only: bool,
+    },
+    Detach {
+        resource: Resource,
+        name: String,
+        services: Vec<String>,
+    },
+    Remove {
+        resource: Resource,
+        names: Vec<String>,
+    },
+    SetEnvironment {
+        service: String,
+        variables: Vec<String>,
+    },
+    UnsetEnvironment {
+        service: String,
+        variables: Vec<String>,
+    },
+    ExposePorts {
+        service: String,
+        ports: Vec<String>,
+    },
+    UnexposePorts {
+        service: String,
+        ports: Vec<String>,
+    },
+    RenameService {
+        name: String,
+        new_name: String,
+    },
+}
+
+/// The options of `tools compose add|remove`, checked against the entry
+/// they are given for
+#[derive(Debug, Default)]
+struct EditOptions {
+    given: Vec<String>, // the options in the order they were given
+    image: Option<String>,
+    build: Option<String>,
+    driver: Option<String>,
+    file: Option<String>,
+    environment: Option<String>,
+    target: Option<String>,
+    read_only: bool,
+    services: Vec<String>,
+    ports: Vec<String>,
+    env: Vec<String>,
+    networks: Vec<String>,
+}
+
+// ## parse_args(args: &[String]) -> Result<ComposeEdit, String>
+// This function parses the arguments of `tools compose add|remove|rename`
+//
+// ### arguments:
+// - args: &[String] - the arguments after `compose`
+//
+// ### returns:
+// - Result<ComposeEdit, String> - the parsed edit or a message describing the error
+pub fn parse_args(args: &[String]) -> Result<ComposeEdit, String> {
+    let mut options = EditOptions::default();
+    let mut positional = Vec::new();
+
+    let mut index = 0;
+    while index < args.len() {
+        let option = args[index].as_str();
+        if option == "--read-only" {
+            options.read_only = true;
+            options.given.push(option.to_string());
+        } else if option.starts_with('-') {
+            index += 1;
+            let value = match args.get(index) {
+                Some(value) => value.to_string(),
+                None => return Err(format!("{} expects a value", option)),
+            };
+            match option {
+                "--image" => options.image = Some(value),
+                "--build" => options.build = Some(value),
+                "--driver" => options.driver = Some(value),
+                "--file" => options.file = Some(value),
+                "--environment" => options.environment = Some(value),
+                "--target" => options.target = Some(value),
+                "--service" => options.services.push(value),
+                "--port" => options.ports.push(value),
+                "--env" => options.env.push(value),
+                "--network" => options.networks.push(value),
+                unknown => return Err(format!("Unknown option: {}", unknown)),
+            }
+            options.given.push(option.to_string());
+        } else {
+            positional.push(option.to_string());
+        }
+        index += 1;
+    }
+
+    let action = positional.first().cloned().unwrap_or_default();
+    let entry = positional.get(1).cloned().unwrap_or_default();
+    let names = positional.iter().skip(2).cloned().collect::<Vec<String>>();
+    let attaching = !options.services.is_empty();
+
+    let (edit, allowed): (ComposeEdit, &[&str]) = match (action.as_str(), entry.as_str()) {
+        ("rename", _) => match &positional[1..] {
+            [name, new_name] => (
+                ComposeEdit::RenameService {
+                    name: name.clone(),
+                    new_name: new_name.clone(),
+                },
+                &[],
+            ),
+            _ => {
+                return Err("`tools compose rename` expects a service and its new name".to_string())
+            }
+        },
+        ("add" | "remove", "env" | "port") => {
+            let (service, values) = match names.split_first() {
+                Some((service, values)) if !values.is_empty() => (service.clone(), values.to_vec()),
+                _ => {
+                    return Err(format!(
+                        "`tools compose {} {}` expects a service and at least one value",
+                        action, entry
+                    ))
+                }
+            };
+            let edit = match (action.as_str(), entry.as_str()) {
+                ("add", "env") => ComposeEdit::SetEnvironment {
+                    service,
+                    variables: values,
+                },
+                ("remove", "env") => ComposeEdit::UnsetEnvironment {
+                    service,
+                    variables: values,
+                },
+                ("add", _) => ComposeEdit::ExposePorts {
+                    service,
+                    ports: values,
+                },
+                _ => ComposeEdit::UnexposePorts {
+                    service,
+                    ports: values,
+                },
+            };
+            (edit, &[])
+        }
+        ("add" | "remove", entry) => {
+            let resource = match entry {
+                "service" => Resource::Service,
+                "network" => Resource::Network,
+                "volume" => Resource::Volume,
+                "secret" => Resource::Secret,
+                "config" => Resource::Config,
+                "" => {
+                    return Err(format!(
+                        "`tools compose {}` expects what to {}",
+                        action, action
+                    ))
+                }
+                unknown => return Err(format!("Unknown compose entry: {}", unknown)),
+            };
+            let name = match names.first() {
+                Some(name) => name.clone(),
+                None => {
+                    return Err(format!(
+                        "`tools compose {} {}` expects a name",
+                        action, entry
+                    ))
+                }
+            };
+            if names.len() > 1 && (action == "add" || attaching) {
+                return Err(format!("Unexpected argument: {}", names[1]));
+            }
+
+            match (action.as_str(), resource) {
+                ("add", Resource::Service) => {
+                    if options.image.is_none() && options.build.is_none() {
+                        return Err(
+                            "`tools compose add service` expects --image or --build".to_string()
+                        );
+                    }
+                    (
+                        ComposeEdit::AddService {
+                            name,
+                            image: options.image.clone(),
+                            build: options.build.clone(),
+                            ports: options.ports.clone(),
+                            environment: options.env.clone(),
+                            networks: options.networks.clone(),
+                        },
+                        &["--image", "--build", "--port", "--env", "--network"],
+                    )
+                }
+                (_, Resource::Service) if attaching => {
+                    return Err("--service doesn't apply to services".to_string())
+                }
+                ("add", resource) if attaching => {
+                    if resource == Resource::Volume && options.target.is_none() {
+                        return Err("Mounting a volume expects --target <path>".to_string());
+                    }
+                    (
+                        ComposeEdit::Attach {
+                            resource,
+                            name,
+                            services: options.services.clone(),
+                            target: options.target.clone(),
+                            read_only: options.read_only,
+                        },
+                        match resource {
+                            Resource::Volume => &["--service", "--target", "--read-only"],
+                            _ => &["--service"],
+                        },
+                    )
+                }
+                ("add", resource) => {
+                    let allowed: &[&str] = match resource {
+                        Resource::Network | Resource::Volume => &["--driver"],
+                        _ => &["--file", "--environment"],
+                    };
+                    if matches!(resource, Resource::Secret | Resource::Config)
+                        && options.file.is_none() == options.environment.is_none()
+                    {
+                        return Err(format!(
+                            "`tools compose add {}` expects --file or --environment",
+                            entry
+                        ));
+                    }
+                    (
+                        ComposeEdit::AddResource {
+                            resource,
+                            name,
+                            driver: options.driver.clone(),
+                            file: options.file.clone(),
+                            environment: options.environment.clone(),
+                        },
+                        allowed,
+                    )
+                }
+                (_, resource) if attaching => (
+                    ComposeEdit::Detach {
+                        resource,
+                        name,
+                        services: options.services.clone(),
+                    },
+                    &["--service"],
+                ),
+                (_, resource) => (ComposeEdit::Remove { resource, names }, &[]),
+            }
+        }
+        ("", _) => return Err("No compose action given".to_string()),
+        (unknown, _) => return Err(format!("Unknown compose action: {}", unknown)),
+    };
+
+    if let Some(option) = options
+        .given
+        .iter()
+        .find(|option| !allowed.contains(&option.as_str()))
+    {
+        return Err(format!(
+            "{} doesn't apply to `tools compose {} {}`",
+            option, action, entry
+        ));
+    }
+
+    Ok(edit)
+}
+
+// ## compose(edit: ComposeEdit)
+// This function applies an edit to the compose file and writes it back,
+// nothing is written if the edit is invalid
+//
+// ### arguments:
+// - edit: ComposeEdit - the parsed edit of the command
+//
+// ### returns:
+// - nothing, exits with 1 if the edit is invalid or the file couldn't be written
+pub fn compose(edit: ComposeEdit) {
+    let mut docker_compose = get_base_docker_compose_file();
+
+    let summary = match apply(&mut docker_compose, &edit) {
+        Ok(summary) => summary,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    };
+
+    if !write_docker_compose_file(&docker_compose) {
+        eprintln!("Could not write {}", CONFIG.docker_compose.base());
+        std::process::exit(1);
+    }
+    println!("{}: {}", CONFIG.docker_compose.base(), summary);
+}
+
+// ## generate(args: &[String])
+// This function creates or completes a compose service for every service
+// of project.yaml, the settings written by hand are kept
+//
+// ### arguments:
+// - args: &[String] - the arguments after `compose generate`, `--dry-run` prints the changes without writing them
+//
+// ### returns:
+// - nothing, exits with 1 if there is no project.yaml or it declares an unknown dependency
+pub fn generate(args: &[String]) {
+    let dry_run = match args {
+        [] => false,
+        [option] if option == "--dry-run" => true,
+        [unknown, ..] => {
+            eprintln!("Unexpected argument: {}", unknown);
+            std::process::exit(1);
+        }
+    };
+
+    let Some(project) = get_project() else {
+        eprintln!("No project.yaml in {}", CONFIG.project_path);
+        std::process::exit(1);
+    };
+
+    // a project without a compose file yet starts from an empty one
+    let mut docker_compose = match get_path_to_docker_compose().exists() {
+        true => get_base_docker_compose_file(),
+        false => DockerCompose::default(),
+    };
+    let changes = match docker_compose.generate_services(&project) {
+        Ok(changes) => changes,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    };
+
+    if changes.is_empty() {
+        println!(
+            "{}: every service of project.yaml is up to date",
+            CONFIG.docker_compose.base()
+        );
+        return;
+    }
+    if !dry_run && !write_docker_compose_file(&docker_compose) {
+        eprintln!("Could not write {}", CONFIG.docker_compose.base());
+        std::process::exit(1);
+    }
+    for change in changes {
+        println!("{}: {}", CONFIG.docker_compose.base(), change);
+    }
+}
+
+// ## apply(docker_compose: &mut DockerCompose, edit: &ComposeEdit) -> Result<String, String>
+// This function applies an edit to a compose file
+//
+// ### arguments:
+// - docker_compose: &mut DockerCompose - the compose file to edit
+// - edit: &ComposeEdit - the edit to apply
+//
+// ### returns:
+// - Result<String, String> - a summary of the change or a message describing the error
+pub fn apply(docker_compose: &mut DockerCompose, edit: &ComposeEdit) -> Result<String, String> {
+    match edit {
+        ComposeEdit::AddService {
+            name,
+            image,
+            build,
+            ports,
+            environment,
+            networks,
+        } => {
+            let service = Service {
+                image: image.clone(),
+                build: build.clone().map(Build::Context),
+                ..Default::default()
+            };
+            docker_compose.add_service(name, service)?;
+            for port in ports {
+                docker_compose.expose_port(name, port)?;
+            }
+            for variable in environment {
+                let (key, value) = split_variable(variable);
+                docker_compose.set_environment(name, key, value)?;
+            }
+            for network in networks {
+                docker_compose.attach_network(name, network)?;
+            }
+            Ok(format!("added service {}", name))
+        }
+        ComposeEdit::AddResource {
+            resource,
+            name,
+            driver,
+            file,
+            environment,
+        } => {
+            match resource {
+                Resource::Network => docker_compose.add_network(
+                    name,
+                    driver.clone().map(|driver| Network {
+                        driver: Some(driver),
+                        ..Default::default()
+                    }),
+                )?,
+                Resource::Volume => docker_compose.add_volume(
+                    name,
+                    driver.clone().map(|driver| Volume {
+                        driver: Some(driver),
+                        ..Default::default()
+                    }),
+                )?,
+                Resource::Secret => docker_compose.add_secret(
+                    name,
+                    Secret {
+                        file: file.clone(),
+                        environment: environment.clone(),
+                        ..Default::default()
+                    },
+                )?,
+                Resource::Config => docker_compose.add_config(
+                    name,
+                    Config {
+                        file: file.clone(),
+                        environment: environment.clone(),
+                        ..Default::default()
+                    },
+                )?,
+                Resource::Service => unreachable!("services are added with AddService"),
+            }
+            Ok(format!("added {} {}", resource_name(*resource), name))
+        }
+        ComposeEdit::Attach {
+            resource,
+            name,
+            services,
+            target,
+            read_only,
+        } => {
+            for service in services {
+                match resource {
+                    Resource::Network => docker_compose.attach_network(service, name)?,
+                    Resource::Volume => docker_compose.attach_volume(
+                        service,
+                        name,
+                        target.as_deref().unwrap_or_default(),
+                        *read_only,
+                    )?,
+                    Resource::Secret => docker_compose.attach_secret(service, name)?,
+                    Resource::Config => docker_compose.attach_config(service, name)?,
+                    Resource::Service => unreachable!("services can't be attached"),
+                }
+            }
+            Ok(format!(
+                "added {} {} to {}",
+                resource_name(*resource),
+                name,
+                services.join(", ")
+            ))
+        }
+        ComposeEdit::Detach {
+            resource,
+            name,
+            services,
+        } => {
+            for service in services {
+                match resource {
+                    Resource::Network => docker_compose.detach_network(service, name)?,
+                    Resource::Volume => docker_compose.detach_volume(service, name)?,
+                    Resource::Secret => docker_compose.detach_secret(service, name)?,
+                    Resource::Config => docker_compose.detach_config(service, name)?,
+                    Resource::Service => unreachable!("services can't be detached"),
+                }
+            }
+            Ok(format!(
+                "removed {} {} from {}",
+                resource_name(*resource),
+                name,
+                services.join(", ")
+            ))
+        }
+        ComposeEdit::Remove { resource, names } => {
+            for name in names {
+                match resource {
+                    Resource::Service => docker_compose.remove_service(name)?,
+                    Resource::Network => docker_compose.remove_network(name)?,
+                    Resource::Volume => docker_compose.remove_volume(name)?,
+                    Resource::Secret => docker_compose.remove_secret(name)?,
+                    Resource::Config => docker_compose.remove_config(name)?,
+                }
+            }
+            Ok(format!(
+                "removed {} {}",
+                resource_name(*resource),
+                names.join(", ")
+            ))
+        }
+        ComposeEdit::SetEnvironment { service, variables } => {
+            for variable in variables {
+                let (key, value) = split_variable(variable);
+                docker_compose.set_environment(service, key, value)?;
+            }
+            Ok(format!("set the environment of {}", service))
+        }
+        ComposeEdit::UnsetEnvironment { service, variables } => {
+            for variable in variables {
+                docker_compose.unset_environment(service, variable)?;
+            }
+            Ok(format!(
+                "removed {} from the environment of {}",
+                variables.join(", "),
+                service
+            ))
+        }
+        ComposeEdit::ExposePorts { service, ports } => {
+            for port in ports {
+                docker_compose.expose_port(service, port)?;
+            }
+            Ok(format!("{} publishes {}", service, ports.join(", ")))
+        }
+        ComposeEdit::UnexposePorts { service, ports } => {
+            for port in ports {
+                docker_compose.unexpose_port(service, port)?;
+            }
+            Ok(format!(
+                "{} no longer publishes {}",
+                service,
+                ports.join(", ")
+            ))
+        }
+        ComposeEdit::RenameService { name, new_name } => {
+            docker_compose.rename_service(name, new_name)?;
+            Ok(format!("renamed service {} to {}", name, new_name))
+        }
+    }
+}
+
+// ## lint_compose_file(args: &[String])
+// This function checks the compose file and prints every problem with its
+// line
+//
+// ### arguments:
+// - args: &[String] - the arguments after `compose lint`, none are accepted
+//
+// ### returns:
+// - nothing, exits with 1 if the file has errors
+pub fn lint_compose_file(args: &[String]) {
+    if let Some(unknown) = args.first() {
+        eprintln!("Unexpected argument: {}", unknown);
+        std::process::exit(1);
+    }
+
+    let files = match read_docker_compose_files() {
+        Ok(files) => files
+            .into_iter()
+            .map(|(path, text)| (display_name(&path), text))
+            .collect::<Vec<(String, String)>>(),
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    };
+    for (name, text) in &files {
+        if let Err(error) = DockerCompose::from_yaml(text) {
+            eprintln!("{}: error: {}", name, error);
+            std::process::exit(1);
+        }
+    }
+    let texts = files
+        .iter()
+        .map(|(_, text)| text.clone())
+        .collect::<Vec<String>>();
+    let docker_compose = match merge_compose_texts(&texts) {
+        Ok(docker_compose) => docker_compose,
+        Err(error) => {
+            eprintln!("{}: error: {}", CONFIG.docker_compose, error);
+            std::process::exit(1);
+        }
+    };
+
+    let path = get_path_to_docker_compose();
+    let issues = lint(&docker_compose, &files, path.parent().unwrap_or(&path));
+    for issue in &issues {
+        let severity = match issue.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        let file = issue
+            .file
+            .as_deref()
+            .unwrap_or(CONFIG.docker_compose.base());
+        match issue.line {
+            Some(line) => println!("{}:{}: {}: {}", file, line, severity, issue.message),
+            None => println!("{}: {}: {}", file, severity, issue.message),
+        }
+    }
+
+    let errors = issues
+        .iter()
+        .filter(|issue| issue.severity == Severity::Error)
+        .count();
+    let warnings = issues.len() - errors;
+    if issues.is_empty() {
+        println!("{}: no problems found", CONFIG.docker_compose);
+    } else {
+        println!("{} errors, {} warnings", errors, warnings);
+    }
+    if errors > 0 {
+        std::process::exit(1);
+    }
+}
+
+// ## config(args: &[String])
+// This function prints the compose file the way compose sees it, with the
+// variables interpolated, the env files merged into the environment and
+// the services of disabled profiles left out
+//
+// ### arguments:
+// - args: &[String] - the arguments after `compose config`, `--env <name>` selects an environment and `--env-file <path>` replaces `.env`
+//
+// ### returns:
+// - nothing, exits with 1 if a required variable is missing or the file is invalid
+pub fn config(args: &[String]) {
+    let (env, args) = match parse_environment_option(args) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    };
+    let mut env_files = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next()) {
+            ("--env-file", Some(path)) => env_files.push(std::path::PathBuf::from(path)),
+            ("--env-file", None) => {
+                eprintln!("--env-file expects a path");
+                std::process::exit(1);
+            }
+            (unknown, _) => {
+                eprintln!("Unexpected argument: {}", unknown);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    select_environment(env.as_deref());
+    let environment = get_environment()
+        .map(|(_, environment)| environment)
+        .unwrap_or_default();
+
+    let path = get_path_to_docker_compose();
+    let project_path = path.parent().unwrap_or(&path);
+    if env_files.is_empty() {
+        env_files = environment
+            .env_files
+            .iter()
+            .flatten()
+            .map(|env_file| project_path.join(env_file))
+            .collect();
+    }
+    let texts = match read_docker_compose_files() {
+        Ok(files) => files
+            .into_iter()
+            .map(|(_, text)| text)
+            .collect::<Vec<String>>(),
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    };
+
+    // the variables of the environment win over the shell, and the project version over both
+    let resolved = load_variables(project_path, &env_files).and_then(|mut variables| {
+        variables.extend(environment.variables.clone().unwrap_or_default());
+        variables.extend(get_version_info().to_env());
+        let mut profiles = environment.profiles.clone().unwrap_or_default();
+        if let Some(enabled) = variables.get("COMPOSE_PROFILES") {
+            profiles.extend(enabled.split(',').map(|profile| profile.trim().to_string()));
+        }
+        let (mut docker_compose, warnings) = resolve(&texts, project_path, &variables)?;
+        apply_profiles(&mut docker_compose, &profiles);
+        Ok((docker_compose, warnings))
+    });
+    match resolved {
+        Ok((docker_compose, warnings)) => {
+            for warning in warnings {
+                eprintln!("warning: {}", warning);
+            }
+            print!("{}", docker_compose.to_yaml().unwrap());
+        }
+        Err(error) => {
+            eprintln!("{}: {}", CONFIG.docker_compose, error);
+            std::process::exit(1);
+        }
+    }
+}
+
+// ## display_name(path: &Path) -> String
+// This function returns the path of a compose file relative to the project
+fn display_name(path: &std::path::Path) -> String {
+    path.strip_prefix(&CONFIG.project_path)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+// ## resource_name(resource: Resource) -> &'static str
+// This function returns the name of a kind of entry for the messages
+fn resource_name(resource: Resource) -> &'static str {
+    match resource {
+        Resource::Service => "service",
+        Resource::Network => "network",
+        Resource::Volume => "volume",
+        Resource::Secret => "secret",
+        Resource::Config => "config",
+    }
+}
+
+// ## split_variable(variable: &str) -> (&str, Option<&str>)
+// This function splits a `KEY=VALUE` argument, a `KEY` has no value
+fn split_variable(variable: &str) -> (&str, Option<&str>) {
+    match variable.split_once('=') {
+        Some((key, value)) => (key, Some(value)),
+        None => (variable, None),
+    }
+}
+
+#[test]
+fn test_compose_parse_args() {
+    let args = |args: &str| {
+        args.split_whitespace()
+            .map(String::from)
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(
+        parse_args(&args("add service cache --image redis:7 --port 6379:6379")),
+        Ok(ComposeEdit::AddService {
+            name: "cache".to_string(),
+            image: Some("redis:7".to_string()),
+            build: None,
+            ports: vec!["6379:6379".to_string()],
+            environment: vec![],
+            networks: vec![],
+        })
+    );
+    assert_eq!(
+        parse_args(&args(
+            "add volume data --service db --target /data --read-only"
+        )),
+        Ok(ComposeEdit::Attach {
+            resource: Resource::Volume,
+            name: "data".to_string(),
+            services: vec!["db".to_string()],
+            target: Some("/data".to_string()),
+            read_only: true,
+        })
+    );
+    assert_eq!(
+        parse_args(&args("remove network backend frontend")),
+        Ok(ComposeEdit::Remove {
+            resource: Resource::Network,
+            names: vec!["backend".to_string(), "frontend".to_string()],
+        })
+    );
+    assert!(parse_args(&args("add service cache")).is_err());
+    assert!(parse_args(&args("add network backend --image nginx")).is_err());
+    assert!(parse_args(&args("add secret token")).is_err());
+    assert!(parse_args(&args("add env api")).is_err());
+
+    let mut docker_compose =
+        DockerCompose::from_yaml("services:\n  api:\n    image: api\n").unwrap();
+    assert!(apply(
+        &mut docker_compose,
+        &parse_args(&args("add network backend --service api")).unwrap()
+    )
+    .is_err());
+    apply(
+        &mut docker_compose,
+        &parse_args(&args("add env api DEBUG=1 TOKEN")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        docker_compose.services["api"]
+            .environment
+            .as_ref()
+            .unwrap()
+            .to_map()["TOKEN"],
+        None
+    );
+}
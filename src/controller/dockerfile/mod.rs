@@ -0,0 +1,284 @@
+// Module: controller::dockerfile
+use crate::helpers::config::project::get_project;
+use crate::helpers::container_engine::compose_project_name;
+use crate::helpers::docker::docker_compose::file::{
+    get_base_docker_compose_file, get_path_to_docker_compose, write_docker_compose_file,
+};
+use crate::helpers::docker::docker_compose::generate::generated_services;
+use crate::helpers::dockerfile::generate_dockerfile;
+use crate::helpers::services::normalize_path;
+use crate::helpers::templates::get_project_variables;
+use crate::models::docker_compose::service::{Build, Service};
+use crate::models::docker_compose::DockerCompose;
+use crate::CONFIG;
+use std::path::{Path, PathBuf};
+
+/// The options accepted by `tools dockerfile generate`
+#[derive(Debug, PartialEq)]
+pub struct GenerateOptions {
+    pub service: String, // the name or path of the service in project.yaml
+    pub force: bool,     // overwrite an existing Dockerfile and .dockerignore
+}
+
+// ## parse_args(args: &[String]) -> Result<GenerateOptions, String>
+// This function parses the arguments of `tools dockerfile generate`
+//
+// ### arguments:
+// - args: &[String] - the arguments after `dockerfile generate`
+//
+// ### returns:
+// - Result<GenerateOptions, String> - the parsed options or a message describing the error
+pub fn parse_args(args: &[String]) -> Result<GenerateOptions, String> {
+    let mut service = None;
+    let mut force = false;
+
+    for arg in args {
+        match arg.as_str() {
+            "-f" | "--force" => force = true,
+            unknown if unknown.starts_with('-') => {
+                return Err(format!("Unknown option: {}", unknown))
+            }
+            name => match service {
+                None => service = Some(name.to_string()),
+                Some(_) => return Err(format!("Unexpected argument: {}", name)),
+            },
+        }
+    }
+
+    match service {
+        Some(service) => Ok(GenerateOptions { service, force }),
+        None => Err("generate expects the name of a service".to_string()),
+    }
+}
+
+// ## generate(options: GenerateOptions)
+// This function writes the Dockerfile and .dockerignore of a service from
+// the template of its languages and frameworks, and builds the compose
+// service of the service from them
+//
+// ### arguments:
+// - options: GenerateOptions - the parsed options of the command
+//
+// ### returns:
+// - nothing, exits with 1 if the service is unknown, has no template or a file can't be written
+pub fn generate(options: GenerateOptions) {
+    let Some(project) = get_project() else {
+        eprintln!("No project.yaml in {}", CONFIG.project_path);
+        std::process::exit(1);
+    };
+
+    let services = project.get_all_services();
+    let Some(service) = services.iter().find(|service| {
+        service.name == options.service
+            || compose_project_name(&service.name) == options.service
+            || service.service_path() == options.service
+    }) else {
+        let names = services
+            .iter()
+            .map(|service| service.name.as_str())
+            .collect::<Vec<&str>>();
+        eprintln!(
+            "Unknown service {}, project.yaml has {}",
+            options.service,
+            names.join(", ")
+        );
+        std::process::exit(1);
+    };
+
+    // only the services without services of their own are built
+    let name = compose_project_name(&service.name);
+    let generated = match generated_services(&project) {
+        Ok(generated) => generated
+            .into_iter()
+            .find(|generated| generated.name == name),
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    };
+    let Some(generated) = generated else {
+        eprintln!(
+            "Service {} has services of its own, generate the Dockerfile of one of them",
+            service.name
+        );
+        std::process::exit(1);
+    };
+
+    let project_path = PathBuf::from(&CONFIG.project_path);
+    let service_path = service.service_path();
+    let service_dir = project_path.join(&service_path);
+    if !service_dir.is_dir() {
+        eprintln!(
+            "{} doesn't exist, run `tools init` to create the service first",
+            service_path
+        );
+        std::process::exit(1);
+    }
+
+    let dockerfile = match generate_dockerfile(
+        service,
+        &service_dir,
+        &project_path,
+        &get_project_variables(),
+    ) {
+        Ok(dockerfile) => dockerfile,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    };
+
+    let files = [
+        ("Dockerfile", Some(dockerfile.dockerfile)),
+        (".dockerignore", dockerfile.dockerignore),
+    ];
+    for (file, contents) in files {
+        let Some(contents) = contents else {
+            continue;
+        };
+        let display = Path::new(&service_path).join(file);
+        if service_dir.join(file).exists() && !options.force {
+            println!(
+                "{}: already exists, kept it (use --force to overwrite)",
+                display.display()
+            );
+            continue;
+        }
+        if let Err(error) = std::fs::write(service_dir.join(file), contents) {
+            eprintln!("Could not write {}: {}", display.display(), error);
+            std::process::exit(1);
+        }
+        println!(
+            "{}: generated from the {} template",
+            display.display(),
+            dockerfile.template
+        );
+    }
+
+    // a project without a compose file yet starts from an empty one
+    let mut docker_compose = match get_path_to_docker_compose().exists() {
+        true => get_base_docker_compose_file(),
+        false => DockerCompose::default(),
+    };
+    let change = match reference_dockerfile(&mut docker_compose, &name, &generated.context) {
+        Ok(change) => change,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    };
+
+    if let Some(change) = change {
+        if !write_docker_compose_file(&docker_compose) {
+            eprintln!("Could not write {}", CONFIG.docker_compose.base());
+            std::process::exit(1);
+        }
+        println!("{}: {}", CONFIG.docker_compose.base(), change);
+    }
+}
+
+// ## reference_dockerfile(docker_compose: &mut DockerCompose, name: &str, context: &str) -> Result<Option<String>, String>
+// This function makes a compose service build from the Dockerfile of its
+// context. A service that builds from another context or Dockerfile is
+// changed to build from it, one that already does is kept as it is
+//
+// ### arguments:
+// - docker_compose: &mut DockerCompose - the compose file to edit
+// - name: &str - the name of the compose service
+// - context: &str - the path of the service, with the Dockerfile
+//
+// ### returns:
+// - Result<Option<String>, String> - a summary of the change, None if nothing changed, or a message describing the error
+fn reference_dockerfile(
+    docker_compose: &mut DockerCompose,
+    name: &str,
+    context: &str,
+) -> Result<Option<String>, String> {
+    if !docker_compose.services.contains_key(name) {
+        let service = Service {
+            build: Some(Build::Context(context.to_string())),
+            ..Service::default()
+        };
+        docker_compose.add_service(name, service)?;
+        return Ok(Some(format!(
+            "added service {} built from {}/Dockerfile",
+            name, context
+        )));
+    }
+
+    let same_context = |other: &str| normalize_path(other) == normalize_path(context);
+    let service = docker_compose.service_mut(name)?;
+    let previous = match &mut service.build {
+        None => {
+            service.build = Some(Build::Context(context.to_string()));
+            None
+        }
+        Some(Build::Context(current)) if same_context(current) => return Ok(None),
+        Some(Build::Context(current)) => Some(std::mem::replace(current, context.to_string())),
+        Some(Build::Config(config)) => {
+            // Compose builds from the directory of the compose file without a context
+            let current = config.context.clone().unwrap_or(".".to_string());
+            let default_dockerfile = config.dockerfile_inline.is_none()
+                && config.dockerfile.as_deref().unwrap_or("Dockerfile") == "Dockerfile";
+            if same_context(&current) && default_dockerfile {
+                return Ok(None);
+            }
+            if !default_dockerfile {
+                config.dockerfile = Some("Dockerfile".to_string());
+                config.dockerfile_inline = None;
+            }
+            match same_context(&current) {
+                true => None,
+                false => {
+                    config.context = Some(context.to_string());
+                    Some(current)
+                }
+            }
+        }
+    };
+
+    Ok(Some(match previous {
+        Some(previous) => format!(
+            "service {} is now built from {}/Dockerfile instead of {}",
+            name, context, previous
+        ),
+        None => format!("service {} is now built from {}/Dockerfile", name, context),
+    }))
+}
+
+#[test]
+fn test_reference_dockerfile() {
+    let mut docker_compose = DockerCompose::from_yaml(
+        "services:\n  api:\n    build: ./api\n  web:\n    build: ./other\n  worker:\n    build:\n      context: ./other\n      dockerfile: worker.Dockerfile\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        reference_dockerfile(&mut docker_compose, "api", "api"),
+        Ok(None)
+    );
+    assert_eq!(
+        reference_dockerfile(&mut docker_compose, "web", "./web"),
+        Ok(Some(
+            "service web is now built from ./web/Dockerfile instead of ./other".to_string()
+        ))
+    );
+    assert_eq!(
+        docker_compose.service("web").unwrap().build,
+        Some(Build::Context("./web".to_string()))
+    );
+    assert!(
+        reference_dockerfile(&mut docker_compose, "worker", "./worker")
+            .unwrap()
+            .is_some()
+    );
+    let Some(Build::Config(config)) = &docker_compose.service("worker").unwrap().build else {
+        panic!("worker lost its build config");
+    };
+    assert_eq!(config.context.as_deref(), Some("./worker"));
+    assert_eq!(config.dockerfile.as_deref(), Some("Dockerfile"));
+    assert_eq!(
+        reference_dockerfile(&mut docker_compose, "worker", "worker"),
+        Ok(None)
+    );
+}
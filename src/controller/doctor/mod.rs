@@ -0,0 +1,321 @@
+// Module: controller::doctor
+use crate::helpers::container_engine::Engine;
+use crate::helpers::git::Git;
+use crate::helpers::program_checker::{check_program, Program, ProgramStatus, PROGRAMS};
+use crate::models::app_config::{AppConfigFile, ContainerEngine};
+use crate::models::docker_compose::DockerCompose;
+use crate::models::project::Project;
+use crossterm::style::Stylize;
+use semver::Version;
+use std::path::Path;
+
+/// The result of a single check of `tools doctor`
+#[derive(Debug, PartialEq)]
+pub enum CheckResult {
+    Pass(String),         // what was found
+    Warn(String, String), // what is wrong and how to fix it, some features won't work
+    Fail(String, String), // what is wrong and how to fix it, the tools won't work
+}
+
+// ## doctor()
+// This function checks the programs the tools depend on, the container
+// engine and the project files, and prints how to fix every problem
+//
+// ### arguments:
+// - nothing
+//
+// ### returns:
+// - nothing, exits with 1 if any check failed
+pub fn doctor() {
+    let mut checks = Vec::new();
+
+    // the project files are read directly, the global config exits on errors
+    let config = read_config();
+    let preferred = config
+        .as_ref()
+        .map(|config| config.get_container_engine())
+        .unwrap_or_default();
+
+    for program in &PROGRAMS {
+        checks.push((program.name.to_string(), program_result(program)));
+    }
+    checks.append(&mut check_container_engine(preferred));
+
+    match config {
+        Ok(config) => {
+            checks.push(("config.yaml".to_string(), check_config(&config)));
+            checks.push(("project.yaml".to_string(), check_project(&config)));
+            for file in config.docker_compose.files() {
+                checks.push((file.clone(), check_compose(&config, &file)));
+            }
+            checks.push((".gitmodules".to_string(), check_submodules(&config)));
+        }
+        Err(result) => checks.push(("config.yaml".to_string(), result)),
+    }
+
+    let width = checks.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    let mut failed = false;
+
+    for (name, result) in &checks {
+        let (label, message, fix) = match result {
+            CheckResult::Pass(message) => ("ok  ".green(), message, None),
+            CheckResult::Warn(message, fix) => ("warn".yellow(), message, Some(fix)),
+            CheckResult::Fail(message, fix) => {
+                failed = true;
+                ("FAIL".red(), message, Some(fix))
+            }
+        };
+
+        println!("{}  {:width$}  {}", label, name, message, width = width);
+        if let Some(fix) = fix {
+            println!("      {:width$}  fix: {}", "", fix, width = width);
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+// ## program_result(program) -> CheckResult
+// This function turns the status of a program into a check result, missing
+// optional programs are only warnings
+fn program_result(program: &Program) -> CheckResult {
+    let status = check_program(program);
+    if let ProgramStatus::Found(version) = &status {
+        return CheckResult::Pass(version.to_string());
+    }
+    let problem = status.problem(program).unwrap_or_default();
+
+    if program.required {
+        CheckResult::Fail(problem, program.install.to_string())
+    } else {
+        CheckResult::Warn(problem, program.install.to_string())
+    }
+}
+
+// ## check_container_engine(preferred: ContainerEngine) -> Vec<(String, CheckResult)>
+// This function checks the programs of the configured container engine,
+// and that its server is running and the user is allowed to talk to it
+fn check_container_engine(preferred: ContainerEngine) -> Vec<(String, CheckResult)> {
+    let engine = match Engine::detect(preferred) {
+        Ok(engine) => engine,
+        // show why the configured engine doesn't work
+        Err(error) if preferred != ContainerEngine::Auto => {
+            let mut checks = vec![(
+                "container engine".to_string(),
+                CheckResult::Fail(error, "see the checks below".to_string()),
+            )];
+            for program in (Engine { kind: preferred }).programs() {
+                checks.push((program.name.to_string(), program_result(program)));
+            }
+            return checks;
+        }
+        Err(error) => {
+            return vec![(
+                "container engine".to_string(),
+                CheckResult::Fail(
+                    error,
+                    "install Docker with the Compose plugin, or podman with podman-compose"
+                        .to_string(),
+                ),
+            )]
+        }
+    };
+
+    let mut checks = vec![(
+        "container engine".to_string(),
+        CheckResult::Pass(engine.name()),
+    )];
+    for program in engine.programs() {
+        checks.push((program.name.to_string(), program_result(program)));
+    }
+
+    let server = match engine.server_version() {
+        Ok(version) => CheckResult::Pass(format!("reachable, server {}", version)),
+        Err(error) if error.to_lowercase().contains("permission denied") => CheckResult::Fail(
+            format!("the current user can't access the {} socket", engine.cli()),
+            "add the user to the docker group with `sudo usermod -aG docker $USER` and log in again"
+                .to_string(),
+        ),
+        Err(error) => {
+            let fix = match engine.cli() {
+                "podman" => "start the podman machine with `podman machine start`, or check `podman info`",
+                "nerdctl" => "start containerd, e.g. `sudo systemctl start containerd`",
+                _ => "start docker, e.g. `sudo systemctl start docker` or start Docker Desktop",
+            };
+            CheckResult::Fail(error, fix.to_string())
+        }
+    };
+    checks.push((format!("{} server", engine.cli()), server));
+
+    checks
+}
+
+// ## read_config() -> Result<AppConfigFile, CheckResult>
+// This function reads config.yaml from the current directory without
+// creating it
+fn read_config() -> Result<AppConfigFile, CheckResult> {
+    let raw_config = std::fs::read_to_string("config.yaml").map_err(|_| {
+        CheckResult::Fail(
+            "there is no config.yaml in the current directory".to_string(),
+            "run the tools from the project root, or run `tools init` to create one".to_string(),
+        )
+    })?;
+
+    AppConfigFile::from_yaml(&raw_config).map_err(|error| {
+        CheckResult::Fail(
+            format!("couldn't parse: {}", error),
+            "fix the YAML, see the Configuration section of the README".to_string(),
+        )
+    })
+}
+
+// ## check_config(config: &AppConfigFile) -> CheckResult
+// This function checks the values of config.yaml
+fn check_config(config: &AppConfigFile) -> CheckResult {
+    if Version::parse(&config.project_version).is_err() {
+        return CheckResult::Fail(
+            format!(
+                "project_version {} is not a semantic version",
+                config.project_version
+            ),
+            "set project_version to a version like 0.1.0, e.g. with `tools version set 0.1.0`"
+                .to_string(),
+        );
+    }
+
+    if !Path::new(&config.project_path).is_dir() {
+        return CheckResult::Fail(
+            format!("project_path {} doesn't exist", config.project_path),
+            "set project_path to the directory of the project".to_string(),
+        );
+    }
+
+    CheckResult::Pass(format!(
+        "{} {}",
+        config.project_name, config.project_version
+    ))
+}
+
+// ## check_project(config: &AppConfigFile) -> CheckResult
+// This function checks that project.yaml parses if the project has one
+fn check_project(config: &AppConfigFile) -> CheckResult {
+    let path = Path::new(&config.project_path).join("project.yaml");
+    let Ok(raw_project) = std::fs::read_to_string(&path) else {
+        return CheckResult::Pass("not used".to_string());
+    };
+
+    match Project::from_yaml(&raw_project) {
+        Ok(project) => CheckResult::Pass(format!(
+            "{} with {} services",
+            project.name,
+            project.get_all_services().len()
+        )),
+        Err(error) => CheckResult::Fail(
+            format!("couldn't parse: {}", error),
+            format!("fix the YAML of {}", path.display()),
+        ),
+    }
+}
+
+// ## check_compose(config: &AppConfigFile, file: &str) -> CheckResult
+// This function checks that a compose file exists and parses
+fn check_compose(config: &AppConfigFile, file: &str) -> CheckResult {
+    let path = Path::new(&config.project_path).join(file);
+    let Ok(raw_compose) = std::fs::read_to_string(&path) else {
+        return CheckResult::Warn(
+            format!("{} doesn't exist", path.display()),
+            "create the compose file, or set docker_compose in config.yaml".to_string(),
+        );
+    };
+
+    match DockerCompose::from_yaml(&raw_compose) {
+        Ok(compose) => CheckResult::Pass(format!("{} services", compose.services.len())),
+        Err(error) => CheckResult::Fail(
+            format!("couldn't parse: {}", error),
+            format!(
+                "fix {}, `docker compose config` shows the errors",
+                path.display()
+            ),
+        ),
+    }
+}
+
+// ## check_submodules(config: &AppConfigFile) -> CheckResult
+// This function checks that .gitmodules parses and that every submodule
+// has a path, a url and is initialized
+fn check_submodules(config: &AppConfigFile) -> CheckResult {
+    let project = Git::new(&config.project_path);
+    if !Path::new(&config.project_path).join(".gitmodules").exists() {
+        return CheckResult::Pass("not used".to_string());
+    }
+
+    let entries = match project.run(&["config", "--file", ".gitmodules", "--list"]) {
+        Ok(entries) => entries,
+        Err(error) => {
+            return CheckResult::Fail(
+                format!("couldn't parse: {}", error),
+                "fix the syntax of .gitmodules".to_string(),
+            )
+        }
+    };
+
+    // submodule.<name>.<key>=<value>, names can contain dots
+    let mut submodules: Vec<(String, Option<String>, Option<String>)> = Vec::new();
+    for entry in entries.lines() {
+        let Some((key, value)) = entry.split_once('=') else {
+            continue;
+        };
+        let Some(key) = key.strip_prefix("submodule.") else {
+            continue;
+        };
+        let Some((name, key)) = key.rsplit_once('.') else {
+            continue;
+        };
+
+        if !submodules.iter().any(|submodule| submodule.0 == name) {
+            submodules.push((name.to_string(), None, None));
+        }
+        let submodule = submodules
+            .iter_mut()
+            .find(|submodule| submodule.0 == name)
+            .expect("the submodule was just added");
+        match key {
+            "path" => submodule.1 = Some(value.to_string()),
+            "url" => submodule.2 = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    if let Some(submodule) = submodules
+        .iter()
+        .find(|submodule| submodule.1.is_none() || submodule.2.is_none())
+    {
+        return CheckResult::Fail(
+            format!("submodule {} has no path or url", submodule.0),
+            "add the missing path and url to .gitmodules".to_string(),
+        );
+    }
+
+    let uninitialized = submodules
+        .iter()
+        .filter_map(|submodule| submodule.1.as_ref())
+        .filter(|path| {
+            !Path::new(&config.project_path)
+                .join(path)
+                .join(".git")
+                .exists()
+        })
+        .cloned()
+        .collect::<Vec<String>>();
+
+    if uninitialized.is_empty() {
+        CheckResult::Pass(format!("{} submodules", submodules.len()))
+    } else {
+        CheckResult::Warn(
+            format!("not initialized: {}", uninitialized.join(", ")),
+            "run `tools init`".to_string(),
+        )
+    }
+}
@@ -0,0 +1,51 @@
+// Module: controller::down
+use crate::helpers::config::environment::{parse_environment_option, select_environment};
+use crate::helpers::container_engine::get_engine;
+
+/// The options accepted by `tools down`
+#[derive(Debug, PartialEq)]
+pub struct DownOptions {
+    pub volumes: bool,       // also remove the volumes of the project
+    pub env: Option<String>, // the environment to stop, TOOLS_ENV if None
+}
+
+// ## parse_args(args: &[String]) -> Result<DownOptions, String>
+// This function parses the arguments of `tools down`
+//
+// ### arguments:
+// - args: &[String] - the arguments after `down`
+//
+// ### returns:
+// - Result<DownOptions, String> - the parsed options or a message describing the error
+pub fn parse_args(args: &[String]) -> Result<DownOptions, String> {
+    let (env, args) = parse_environment_option(args)?;
+    let mut volumes = false;
+
+    for arg in &args {
+        match arg.as_str() {
+            "-v" | "--volumes" => volumes = true,
+            unknown => return Err(format!("Unknown option: {}", unknown)),
+        }
+    }
+
+    Ok(DownOptions { volumes, env })
+}
+
+// ## down(options: DownOptions)
+// This function stops and removes the containers of the project with the
+// configured container engine
+//
+// ### arguments:
+// - options: DownOptions - the parsed options of the command
+//
+// ### returns:
+// - nothing, exits with 1 if compose failed
+pub fn down(options: DownOptions) {
+    select_environment(options.env.as_deref());
+    let engine = get_engine();
+
+    if let Err(error) = engine.down(options.volumes) {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    }
+}
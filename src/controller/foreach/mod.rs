@@ -0,0 +1,327 @@
+// Module: controller::foreach
+use crate::helpers::services::{filter_service_paths, get_service_paths, split_service_list};
+use crate::helpers::version::get_version_info;
+use crossterm::style::{Color, Stylize};
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Colors used to prefix the output of each service, assigned in order
+const SERVICE_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Magenta,
+    Color::Yellow,
+    Color::Blue,
+    Color::DarkCyan,
+    Color::DarkMagenta,
+];
+
+/// The options accepted by `tools foreach`
+#[derive(Debug, PartialEq)]
+pub struct ForeachOptions {
+    pub parallel: usize,      // number of services to run the command in at once
+    pub only: Vec<String>,    // only run in these services, empty means all services
+    pub fail_fast: bool,      // stop starting new services after the first failure
+    pub command: Vec<String>, // the command and its arguments
+}
+
+/// The outcome of running the command in a single service
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServiceOutcome {
+    Passed,
+    Failed(Option<i32>),
+    Skipped(String),
+}
+
+/// ## parse_args(args: &[String]) -> Result<ForeachOptions, String>
+/// This function parses the arguments of `tools foreach` into `ForeachOptions`
+///
+/// ### Arguments
+/// - args: &[String] - The arguments passed after `foreach`
+///
+/// ### Returns
+/// - Result<ForeachOptions, String> - The parsed options or a message describing the error
+pub fn parse_args(args: &[String]) -> Result<ForeachOptions, String> {
+    let mut options = ForeachOptions {
+        parallel: 1,
+        only: Vec::new(),
+        fail_fast: false,
+        command: Vec::new(),
+    };
+
+    let mut index = 0;
+    while index < args.len() {
+        match args[index].as_str() {
+            "--" => {
+                options.command = args[index + 1..].to_vec();
+                break;
+            }
+            "-p" | "--parallel" => {
+                index += 1;
+                options.parallel = match args.get(index).map(|value| value.parse::<usize>()) {
+                    Some(Ok(parallel)) if parallel > 0 => parallel,
+                    _ => return Err("--parallel expects a number greater than 0".to_string()),
+                };
+            }
+            "--only" => {
+                index += 1;
+                match args.get(index) {
+                    Some(services) => options.only.extend(split_service_list(services)),
+                    None => return Err("--only expects a list of services".to_string()),
+                }
+            }
+            "--fail-fast" => options.fail_fast = true,
+            "--keep-going" => options.fail_fast = false,
+            unknown => return Err(format!("Unknown option: {}", unknown)),
+        }
+        index += 1;
+    }
+
+    if options.command.is_empty() {
+        return Err("No command given, pass the command after `--`".to_string());
+    }
+
+    Ok(options)
+}
+
+/// ## foreach(options: ForeachOptions)
+/// This function runs a command in every service of the project, prints
+/// the output of each service prefixed with its name and finishes with
+/// a pass/fail matrix. The process exits with a non-zero code if the
+/// command failed in any service.
+///
+/// ### Arguments
+/// - options: ForeachOptions - The parsed options of the command
+///
+/// ### Returns
+/// - nothing
+pub fn foreach(options: ForeachOptions) {
+    // get the services (absolute path, relative path, initialized) and apply the --only filter
+    let services = filter_service_paths(get_service_paths(), &options.only);
+
+    if services.is_empty() {
+        eprintln!("No services matched, nothing to run");
+        std::process::exit(1);
+    }
+
+    // the project version is exported to every command
+    let env = get_version_info().to_env();
+
+    let width = services
+        .iter()
+        .map(|service| service.1.len())
+        .max()
+        .unwrap_or(0);
+    let next_service = Mutex::new(0usize);
+    let stop = AtomicBool::new(false);
+    let results: Mutex<Vec<Option<(ServiceOutcome, Duration)>>> =
+        Mutex::new(vec![None; services.len()]);
+
+    std::thread::scope(|scope| {
+        for _ in 0..options.parallel.min(services.len()) {
+            scope.spawn(|| loop {
+                // take the next service off the queue
+                let index = {
+                    let mut next = next_service.lock().unwrap();
+                    let index = *next;
+                    *next += 1;
+                    index
+                };
+                let Some(service) = services.get(index) else {
+                    break;
+                };
+
+                let started = Instant::now();
+                let outcome = if stop.load(Ordering::SeqCst) {
+                    ServiceOutcome::Skipped("fail fast".to_string())
+                } else if !service.2 {
+                    ServiceOutcome::Skipped("missing".to_string())
+                } else {
+                    let color = SERVICE_COLORS[index % SERVICE_COLORS.len()];
+                    let prefix = format!("{:width$} |", service.1, width = width)
+                        .with(color)
+                        .to_string();
+                    run_in_service(&options.command, &service.0, &env, &prefix)
+                };
+
+                if options.fail_fast && matches!(outcome, ServiceOutcome::Failed(_)) {
+                    stop.store(true, Ordering::SeqCst);
+                }
+
+                results.lock().unwrap()[index] = Some((outcome, started.elapsed()));
+            });
+        }
+    });
+
+    let results = results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| {
+            result.unwrap_or((
+                ServiceOutcome::Skipped("not run".to_string()),
+                Duration::ZERO,
+            ))
+        })
+        .collect::<Vec<(ServiceOutcome, Duration)>>();
+
+    print_matrix(&services, &results, width);
+
+    // exit with a failing code if the command failed in any service
+    if results
+        .iter()
+        .any(|result| matches!(result.0, ServiceOutcome::Failed(_)))
+    {
+        std::process::exit(1);
+    }
+}
+
+/// ## run_in_service(command: &[String], dir: &str, env: &[(String, String)], prefix: &str) -> ServiceOutcome
+/// This function runs the command in the directory of a service and
+/// streams the stdout and stderr of the command prefixed with the service name
+///
+/// ### Arguments
+/// - command: &[String] - The command and its arguments
+/// - dir: &str - The absolute path to the service
+/// - env: &[(String, String)] - The environment variables set for the command
+/// - prefix: &str - The colored prefix printed in front of every line
+///
+/// ### Returns
+/// - ServiceOutcome - Passed if the command exited successfully, Failed otherwise
+fn run_in_service(
+    command: &[String],
+    dir: &str,
+    env: &[(String, String)],
+    prefix: &str,
+) -> ServiceOutcome {
+    let child = Command::new(&command[0])
+        .args(&command[1..])
+        .current_dir(dir)
+        .envs(env.iter().map(|(key, value)| (key, value)))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+
+    let mut child = match child {
+        Ok(child) => child,
+        Err(error) => {
+            eprintln!("{} failed to run {}: {}", prefix, command[0], error);
+            return ServiceOutcome::Failed(None);
+        }
+    };
+
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+
+    // print both streams line by line as they arrive
+    std::thread::scope(|scope| {
+        if let Some(stdout) = stdout {
+            scope.spawn(|| print_prefixed(stdout, prefix, false));
+        }
+        if let Some(stderr) = stderr {
+            scope.spawn(|| print_prefixed(stderr, prefix, true));
+        }
+    });
+
+    match child.wait() {
+        Ok(status) if status.success() => ServiceOutcome::Passed,
+        Ok(status) => ServiceOutcome::Failed(status.code()),
+        Err(error) => {
+            eprintln!("{} failed to wait for {}: {}", prefix, command[0], error);
+            ServiceOutcome::Failed(None)
+        }
+    }
+}
+
+/// ## print_prefixed(stream: impl Read, prefix: &str, is_stderr: bool)
+/// This function prints every line of a stream prefixed with the service name
+fn print_prefixed(stream: impl Read, prefix: &str, is_stderr: bool) {
+    for line in BufReader::new(stream).lines().map_while(Result::ok) {
+        if is_stderr {
+            eprintln!("{} {}", prefix, line);
+        } else {
+            println!("{} {}", prefix, line);
+        }
+    }
+}
+
+/// ## print_matrix(services, results, width)
+/// This function prints the pass/fail matrix of every service
+fn print_matrix(
+    services: &[(String, String, bool)],
+    results: &[(ServiceOutcome, Duration)],
+    width: usize,
+) {
+    println!();
+    println!(
+        "{:width$}  {:6}  {:>4}  {:>8}",
+        "Service",
+        "Result",
+        "Exit",
+        "Time",
+        width = width
+    );
+    for (service, (outcome, duration)) in services.iter().zip(results) {
+        let (result, exit_code, note) = match outcome {
+            ServiceOutcome::Passed => ("pass  ".green(), "0".to_string(), String::new()),
+            ServiceOutcome::Failed(code) => (
+                "FAIL  ".red(),
+                code.map(|code| code.to_string()).unwrap_or("-".to_string()),
+                String::new(),
+            ),
+            ServiceOutcome::Skipped(reason) => (
+                "skip  ".dark_grey(),
+                "-".to_string(),
+                format!("  ({})", reason),
+            ),
+        };
+        println!(
+            "{:width$}  {}  {:>4}  {:>7.1}s{}",
+            service.1,
+            result,
+            exit_code,
+            duration.as_secs_f64(),
+            note,
+            width = width
+        );
+    }
+}
+
+#[test]
+fn test_foreach_parse_args() {
+    let args = [
+        "--parallel",
+        "4",
+        "--only",
+        "backend,frontend",
+        "--fail-fast",
+        "--",
+        "cargo",
+        "test",
+    ]
+    .map(String::from);
+
+    let options = parse_args(&args).unwrap();
+
+    assert_eq!(
+        options,
+        ForeachOptions {
+            parallel: 4,
+            only: vec!["backend".to_string(), "frontend".to_string()],
+            fail_fast: true,
+            command: vec!["cargo".to_string(), "test".to_string()],
+        }
+    );
+}
+
+#[test]
+fn test_foreach_parse_args_requires_command() {
+    let args = ["--parallel", "2"].map(String::from);
+    assert!(parse_args(&args).is_err());
+
+    let args = ["--parallel", "0", "--", "ls"].map(String::from);
+    assert!(parse_args(&args).is_err());
+}
@@ -0,0 +1,207 @@
+// Module: controller::graph
+use crate::helpers::config::project::get_project;
+use crate::helpers::docker::docker_compose::file::get_docker_compose_file;
+use crate::helpers::graph::{EdgeKind, ServiceGraph};
+use crate::CONFIG;
+
+/// The formats `tools graph` prints the graph in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphFormat {
+    Text,
+    Dot,
+    Mermaid,
+}
+
+/// What `tools graph` prints
+#[derive(Debug, PartialEq)]
+pub enum GraphAction {
+    Show(GraphFormat),
+    Order { stop: bool },
+    Dependencies { service: String, reverse: bool },
+}
+
+/// The options accepted by `tools graph`
+#[derive(Debug, PartialEq)]
+pub struct GraphOptions {
+    pub action: GraphAction,
+    pub project: bool, // add the service hierarchy of project.yaml
+}
+
+// ## parse_args(args: &[String]) -> Result<GraphOptions, String>
+// This function parses the arguments of `tools graph`
+//
+// ### arguments:
+// - args: &[String] - the arguments after `graph`
+//
+// ### returns:
+// - Result<GraphOptions, String> - the parsed options or a message describing the error
+pub fn parse_args(args: &[String]) -> Result<GraphOptions, String> {
+    let mut format = None;
+    let mut project = false;
+    let mut stop = false;
+    let mut reverse = false;
+    let mut positional = Vec::new();
+
+    let mut index = 0;
+    while index < args.len() {
+        match args[index].as_str() {
+            "--format" => {
+                index += 1;
+                format = match args.get(index).map(|format| format.as_str()) {
+                    Some("text") => Some(GraphFormat::Text),
+                    Some("dot") => Some(GraphFormat::Dot),
+                    Some("mermaid") => Some(GraphFormat::Mermaid),
+                    Some(unknown) => return Err(format!("Unknown graph format: {}", unknown)),
+                    None => return Err("--format expects text, dot or mermaid".to_string()),
+                }
+            }
+            "--project" => project = true,
+            "--stop" => stop = true,
+            "--reverse" => reverse = true,
+            unknown if unknown.starts_with('-') => {
+                return Err(format!("Unknown option: {}", unknown))
+            }
+            value => positional.push(value.to_string()),
+        }
+        index += 1;
+    }
+
+    let action = match positional.first().map(|action| action.as_str()) {
+        None => GraphAction::Show(format.unwrap_or(GraphFormat::Text)),
+        Some("order") if positional.len() == 1 => GraphAction::Order { stop },
+        Some("deps") => match &positional[1..] {
+            [service] => GraphAction::Dependencies {
+                service: service.clone(),
+                reverse,
+            },
+            _ => return Err("`tools graph deps` expects one service".to_string()),
+        },
+        Some("order") => return Err(format!("Unexpected argument: {}", positional[1])),
+        Some(unknown) => return Err(format!("Unknown graph action: {}", unknown)),
+    };
+
+    // the projects of project.yaml aren't services, they are never started
+    let misplaced = match action {
+        GraphAction::Show(_) => vec![(stop, "--stop"), (reverse, "--reverse")],
+        GraphAction::Order { .. } => vec![
+            (format.is_some(), "--format"),
+            (reverse, "--reverse"),
+            (project, "--project"),
+        ],
+        GraphAction::Dependencies { .. } => vec![
+            (format.is_some(), "--format"),
+            (stop, "--stop"),
+            (project, "--project"),
+        ],
+    };
+    if let Some((_, option)) = misplaced.iter().find(|(given, _)| *given) {
+        return Err(format!("{} doesn't apply here", option));
+    }
+
+    Ok(GraphOptions { action, project })
+}
+
+// ## graph(options: GraphOptions)
+// This function prints the dependency graph of the services, their start
+// or stop order, or the dependencies of a service
+//
+// ### arguments:
+// - options: GraphOptions - the parsed options of the command
+//
+// ### returns:
+// - nothing, exits with 1 if the dependencies have a cycle or the service doesn't exist
+pub fn graph(options: GraphOptions) {
+    let mut graph = ServiceGraph::from_compose(&get_docker_compose_file());
+    if options.project {
+        match get_project() {
+            Some(project) => graph.add_project(&project),
+            None => {
+                eprintln!("No project.yaml in {}", CONFIG.project_path);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    match options.action {
+        GraphAction::Show(GraphFormat::Dot) => print!("{}", graph.to_dot(&CONFIG.project_name)),
+        GraphAction::Show(GraphFormat::Mermaid) => print!("{}", graph.to_mermaid()),
+        GraphAction::Show(GraphFormat::Text) => {
+            for node in &graph.nodes {
+                match graph.dependencies(node)[..] {
+                    [] => println!("{}", node),
+                    ref dependencies => println!("{} -> {}", node, dependencies.join(", ")),
+                }
+                let children = graph
+                    .edges
+                    .iter()
+                    .filter(|edge| edge.kind == EdgeKind::Contains && edge.from == *node)
+                    .map(|edge| edge.to.as_str())
+                    .collect::<Vec<&str>>();
+                if !children.is_empty() {
+                    println!("{} contains {}", node, children.join(", "));
+                }
+            }
+        }
+        GraphAction::Order { stop } => {
+            let order = match stop {
+                true => graph.stop_order(),
+                false => graph.start_order(),
+            };
+            match order {
+                Ok(order) => order
+                    .iter()
+                    .enumerate()
+                    .for_each(|(index, node)| println!("{:>3}. {}", index + 1, node)),
+                Err(cycle) => {
+                    eprintln!(
+                        "The services can't be ordered, dependency cycle: {}",
+                        cycle.join(" -> ")
+                    );
+                    std::process::exit(1);
+                }
+            }
+        }
+        GraphAction::Dependencies { service, reverse } => {
+            if !graph.nodes.contains(&service) {
+                eprintln!("Service {} doesn't exist", service);
+                std::process::exit(1);
+            }
+            let services = match reverse {
+                true => graph.transitive_dependents(&service),
+                false => graph.transitive_dependencies(&service),
+            };
+            services.iter().for_each(|service| println!("{}", service));
+        }
+    }
+}
+
+#[test]
+fn test_graph_parse_args() {
+    let args = |args: &str| {
+        args.split_whitespace()
+            .map(String::from)
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(
+        parse_args(&args("--format mermaid --project")),
+        Ok(GraphOptions {
+            action: GraphAction::Show(GraphFormat::Mermaid),
+            project: true,
+        })
+    );
+    assert_eq!(
+        parse_args(&args("deps db --reverse")),
+        Ok(GraphOptions {
+            action: GraphAction::Dependencies {
+                service: "db".to_string(),
+                reverse: true,
+            },
+            project: false,
+        })
+    );
+    assert!(parse_args(&args("order --format dot")).is_err());
+    assert!(parse_args(&args("order --project")).is_err());
+    assert!(parse_args(&args("--format svg")).is_err());
+    assert!(parse_args(&args("deps")).is_err());
+}
@@ -0,0 +1,321 @@
+// Module: controller::hooks
+mod scripts;
+
+use crate::helpers::git::Git;
+use crate::helpers::services::{get_service_repositories, split_service_list};
+use crate::CONFIG;
+use scripts::{expected_hooks, HOOK_MARKER, HOOK_NAMES};
+use std::path::{Path, PathBuf};
+
+/// The hook operations accepted by `tools hooks`
+#[derive(Debug, PartialEq)]
+pub enum HooksAction {
+    Install,
+    Status,
+}
+
+/// The options accepted by `tools hooks`
+#[derive(Debug, PartialEq)]
+pub struct HooksOptions {
+    pub action: HooksAction,
+    pub only: Vec<String>, // only operate on these services, empty means the project and all services
+    pub force: bool,       // overwrite hooks that were not installed by tools
+}
+
+/// The name, state and configured script of every managed hook of a repository
+type HookStates = Vec<(&'static str, HookState, Option<String>)>;
+
+/// The state of an installed hook compared to the configured hooks
+#[derive(Debug, PartialEq)]
+pub enum HookState {
+    UpToDate,   // installed and matches the configuration
+    Missing,    // configured but not installed
+    Outdated,   // installed by tools but the configuration changed since
+    Foreign,    // a hook that was not installed by tools is in the way
+    Stale,      // installed by tools but no longer configured
+    NotManaged, // neither configured nor installed by tools
+}
+
+impl HookState {
+    /// This function returns the label of the state in the status matrix.
+    fn label(&self) -> &'static str {
+        match self {
+            HookState::UpToDate => "ok",
+            HookState::Missing => "missing",
+            HookState::Outdated => "outdated",
+            HookState::Foreign => "foreign",
+            HookState::Stale => "stale",
+            HookState::NotManaged => "-",
+        }
+    }
+}
+
+// ## parse_args(args: &[String]) -> Result<HooksOptions, String>
+// This function parses the arguments of `tools hooks`
+//
+// ### arguments:
+// - args: &[String] - the arguments after `hooks`
+//
+// ### returns:
+// - Result<HooksOptions, String> - the parsed options or a message describing the error
+pub fn parse_args(args: &[String]) -> Result<HooksOptions, String> {
+    let mut action = None;
+    let mut only = Vec::new();
+    let mut force = false;
+
+    let mut index = 0;
+    while index < args.len() {
+        match args[index].as_str() {
+            "--only" => {
+                index += 1;
+                match args.get(index) {
+                    Some(services) => only.extend(split_service_list(services)),
+                    None => return Err("--only expects a list of services".to_string()),
+                }
+            }
+            "-f" | "--force" => force = true,
+            unknown if unknown.starts_with('-') => {
+                return Err(format!("Unknown option: {}", unknown))
+            }
+            value if action.is_some() => return Err(format!("Unexpected argument: {}", value)),
+            "install" => action = Some(HooksAction::Install),
+            "status" => action = Some(HooksAction::Status),
+            value => return Err(format!("Unknown hooks action: {}", value)),
+        }
+        index += 1;
+    }
+
+    Ok(HooksOptions {
+        action: action.ok_or("No hooks action given".to_string())?,
+        only,
+        force,
+    })
+}
+
+// ## hook_state(expected: Option<&str>, installed: Option<&str>) -> HookState
+// This function compares the configured script of a hook with the one
+// that is installed
+//
+// ### arguments:
+// - expected: Option<&str> - the configured script, None if the hook is not configured
+// - installed: Option<&str> - the installed script, None if there is none
+//
+// ### returns:
+// - HookState - the state of the hook
+pub fn hook_state(expected: Option<&str>, installed: Option<&str>) -> HookState {
+    let managed = installed.is_some_and(|installed| installed.contains(HOOK_MARKER));
+
+    match (expected, installed) {
+        (Some(expected), Some(installed)) if expected == installed => HookState::UpToDate,
+        (Some(_), Some(_)) if managed => HookState::Outdated,
+        (Some(_), Some(_)) => HookState::Foreign,
+        (Some(_), None) => HookState::Missing,
+        (None, Some(_)) if managed => HookState::Stale,
+        (None, _) => HookState::NotManaged,
+    }
+}
+
+// ## hooks(options: HooksOptions)
+// This function installs the configured hooks, or shows their state, in
+// the project and every service repository
+//
+// ### arguments:
+// - options: HooksOptions - the parsed options of the command
+//
+// ### returns:
+// - nothing
+pub fn hooks(options: HooksOptions) {
+    // the project itself is only included when no services are selected
+    let mut repositories = Vec::new();
+    if options.only.is_empty() {
+        repositories.push((CONFIG.project_name.clone(), Git::project()));
+    }
+    repositories.append(&mut get_service_repositories(&options.only));
+
+    if repositories.is_empty() {
+        eprintln!("No service repositories matched");
+        std::process::exit(1);
+    }
+
+    let success = match options.action {
+        HooksAction::Install => install(&repositories, options.force),
+        HooksAction::Status => status(&repositories),
+    };
+
+    if !success {
+        std::process::exit(1);
+    }
+}
+
+// ## repository_states(repository: &Git) -> Result<(PathBuf, HookStates), String>
+// This function returns the hooks directory of a repository, and the state
+// and configured script of every managed hook
+fn repository_states(repository: &Git) -> Result<(PathBuf, HookStates), String> {
+    // submodules keep their hooks in modules/<name> of the superproject
+    let hooks_dir = repository
+        .git_path("hooks")
+        .map_err(|error| error.to_string())?;
+    let expected = expected_hooks(&CONFIG.get_hooks());
+
+    let states = HOOK_NAMES
+        .iter()
+        .map(|name| {
+            let script = expected
+                .iter()
+                .find(|(hook, _)| hook == name)
+                .map(|(_, script)| script.clone());
+            let installed = std::fs::read_to_string(hooks_dir.join(name)).ok();
+            let state = hook_state(script.as_deref(), installed.as_deref());
+            (*name, state, script)
+        })
+        .collect();
+
+    Ok((hooks_dir, states))
+}
+
+// ## install(repositories: &[(String, Git)], force: bool) -> bool
+// This function installs the configured hooks and removes the hooks tools
+// installed that are no longer configured
+fn install(repositories: &[(String, Git)], force: bool) -> bool {
+    let mut success = true;
+
+    for (name, repository) in repositories {
+        let (hooks_dir, states) = match repository_states(repository) {
+            Ok(states) => states,
+            Err(error) => {
+                eprintln!("{} - {}", name, error);
+                success = false;
+                continue;
+            }
+        };
+
+        for (hook, state, script) in states {
+            let path = hooks_dir.join(hook);
+            let result = match (state, script) {
+                (HookState::UpToDate, _) => Ok("up to date"),
+                (HookState::NotManaged, _) => continue,
+                (HookState::Foreign, _) if !force => {
+                    eprintln!(
+                        "{} - {} was not installed by tools, use --force to replace it",
+                        name, hook
+                    );
+                    success = false;
+                    continue;
+                }
+                (HookState::Stale, _) => std::fs::remove_file(&path).map(|_| "removed"),
+                (_, Some(script)) => write_hook(&path, &script).map(|_| "installed"),
+                (_, None) => continue,
+            };
+
+            match result {
+                Ok(message) => println!("{} - {} {}", name, hook, message),
+                Err(error) => {
+                    eprintln!("{} - {}: {}", name, hook, error);
+                    success = false;
+                }
+            }
+        }
+    }
+
+    success
+}
+
+// ## write_hook(path: &Path, script: &str) -> std::io::Result<()>
+// This function writes an executable hook script
+fn write_hook(path: &Path, script: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, script)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    Ok(())
+}
+
+// ## status(repositories: &[(String, Git)]) -> bool
+// This function prints a matrix of the state of every hook in every
+// repository
+//
+// ### returns:
+// - bool - true if every hook is up to date, false if any drifted
+fn status(repositories: &[(String, Git)]) -> bool {
+    let width = repositories
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0)
+        .max("Repository".len());
+
+    print!("{:width$}", "Repository", width = width);
+    for hook in HOOK_NAMES {
+        print!("  {:10}", hook);
+    }
+    println!();
+
+    let mut success = true;
+    for (name, repository) in repositories {
+        match repository_states(repository) {
+            Ok((_, states)) => {
+                print!("{:width$}", name, width = width);
+                for (_, state, _) in states {
+                    if !matches!(state, HookState::UpToDate | HookState::NotManaged) {
+                        success = false;
+                    }
+                    print!("  {:10}", state.label());
+                }
+                println!();
+            }
+            Err(error) => {
+                eprintln!("{} - {}", name, error);
+                success = false;
+            }
+        }
+    }
+
+    if !success {
+        println!("Run `tools hooks install` to install the configured hooks");
+    }
+
+    success
+}
+
+#[test]
+fn test_hooks_hook_state() {
+    let script = format!("#!/bin/sh\n{}\nexit 0\n", HOOK_MARKER);
+    let changed = format!("#!/bin/sh\n{}\nexit 1\n", HOOK_MARKER);
+
+    assert_eq!(
+        hook_state(Some(&script), Some(&script)),
+        HookState::UpToDate
+    );
+    assert_eq!(
+        hook_state(Some(&script), Some(&changed)),
+        HookState::Outdated
+    );
+    assert_eq!(
+        hook_state(Some(&script), Some("#!/bin/sh\nlint\n")),
+        HookState::Foreign
+    );
+    assert_eq!(hook_state(Some(&script), None), HookState::Missing);
+    assert_eq!(hook_state(None, Some(&script)), HookState::Stale);
+    assert_eq!(
+        hook_state(None, Some("#!/bin/sh\nlint\n")),
+        HookState::NotManaged
+    );
+    assert_eq!(hook_state(None, None), HookState::NotManaged);
+
+    assert_eq!(
+        parse_args(&["install".to_string(), "--force".to_string()]),
+        Ok(HooksOptions {
+            action: HooksAction::Install,
+            only: Vec::new(),
+            force: true,
+        })
+    );
+    assert!(parse_args(&["remove".to_string()]).is_err());
+}
@@ -0,0 +1,136 @@
+// Module: controller::hooks::scripts
+use crate::helpers::conventional_commits::COMMIT_TYPES;
+use crate::models::app_config::HooksConfig;
+
+/// The line that marks a hook as installed by `tools hooks install`,
+/// hooks without it are never overwritten unless forced
+pub const HOOK_MARKER: &str = "# Installed by `tools hooks install`, changes are overwritten";
+
+/// The hooks managed by `tools hooks`
+pub const HOOK_NAMES: [&str; 2] = ["commit-msg", "pre-commit"];
+
+/// Extended regular expressions of secrets that should never be committed
+const SECRET_PATTERNS: [&str; 5] = [
+    "AKIA[0-9A-Z]{16}",                                  // AWS access key ids
+    "-----BEGIN ([A-Z]+ )?PRIVATE KEY-----",            // private keys
+    "gh[pousr]_[A-Za-z0-9]{36}",                        // GitHub tokens
+    "xox[abprs]-[A-Za-z0-9-]{10,}",                     // Slack tokens
+    "(api|secret|access)[_-]?(key|token)[\"']?[[:space:]]*[:=][[:space:]]*[\"'][A-Za-z0-9/+=_-]{16,}[\"']", // assigned keys
+];
+
+// ## expected_hooks(config: &HooksConfig) -> Vec<(&'static str, String)>
+// This function returns the name and script of every configured hook
+//
+// ### arguments:
+// - config: &HooksConfig - the hooks of config.yaml
+//
+// ### returns:
+// - Vec<(&'static str, String)> - the configured hooks, unconfigured hooks are left out
+pub fn expected_hooks(config: &HooksConfig) -> Vec<(&'static str, String)> {
+    let mut hooks = Vec::new();
+
+    if config.commit_msg.unwrap_or(true) {
+        hooks.push(("commit-msg", commit_msg_hook()));
+    }
+
+    let secret_scan = config.secret_scan.unwrap_or(true);
+    let tasks = config.pre_commit.clone().unwrap_or_default();
+    if secret_scan || !tasks.is_empty() {
+        hooks.push(("pre-commit", pre_commit_hook(secret_scan, &tasks)));
+    }
+
+    hooks
+}
+
+// ## commit_msg_hook() -> String
+// This function returns a commit-msg hook that rejects messages that are
+// not conventional commits, merges, reverts and fixups are allowed
+fn commit_msg_hook() -> String {
+    let types = COMMIT_TYPES
+        .iter()
+        .map(|(commit_type, _)| *commit_type)
+        .collect::<Vec<&str>>()
+        .join("|");
+
+    format!(
+        r#"#!/bin/sh
+{marker}
+# Checks that the commit message follows the Conventional Commits specification
+subject=$(head -n 1 "$1")
+case "$subject" in
+    "Merge "*|"Revert "*|"fixup! "*|"squash! "*|"amend! "*) exit 0 ;;
+esac
+if ! printf '%s\n' "$subject" | grep -Eq '^({types})(\([^()]+\))?!?: .+'; then
+    echo "commit-msg: \"$subject\" is not a conventional commit" >&2
+    echo "commit-msg: expected <type>[(scope)][!]: <description>, e.g. feat(api): add login" >&2
+    exit 1
+fi
+"#,
+        marker = HOOK_MARKER,
+        types = types
+    )
+}
+
+// ## pre_commit_hook(secret_scan: bool, tasks: &[String]) -> String
+// This function returns a pre-commit hook that scans the staged changes
+// for secrets and runs the configured tasks
+fn pre_commit_hook(secret_scan: bool, tasks: &[String]) -> String {
+    let mut script = format!("#!/bin/sh\n{}\n", HOOK_MARKER);
+
+    if secret_scan {
+        script.push_str(&format!(
+            r#"# Refuses staged changes that look like they add a secret
+patterns={patterns}
+added=$(git diff --cached --no-color --unified=0 --diff-filter=ACMR | grep -E '^\+[^+]')
+if printf '%s\n' "$added" | grep -Eiq "$patterns"; then
+    echo "pre-commit: the staged changes look like they contain a secret:" >&2
+    printf '%s\n' "$added" | grep -Ei "$patterns" >&2
+    echo "pre-commit: remove it, or commit with --no-verify if it is a false positive" >&2
+    exit 1
+fi
+"#,
+            patterns = shell_quote(&SECRET_PATTERNS.join("|"))
+        ));
+    }
+
+    for task in tasks {
+        script.push_str(&format!(
+            "echo {}\n{} || {{ echo {} >&2; exit 1; }}\n",
+            shell_quote(&format!("pre-commit: {}", task)),
+            task,
+            shell_quote(&format!("pre-commit: `{}` failed", task))
+        ));
+    }
+
+    script
+}
+
+// ## shell_quote(value: &str) -> String
+// This function quotes a value for a POSIX shell
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+#[test]
+fn test_hooks_expected_hooks() {
+    let hooks = expected_hooks(&HooksConfig::default());
+    assert_eq!(
+        hooks.iter().map(|(name, _)| *name).collect::<Vec<&str>>(),
+        HOOK_NAMES
+    );
+    assert!(hooks[0].1.contains("^(feat|fix|perf|"));
+    assert!(hooks[1].1.contains(HOOK_MARKER));
+
+    let hooks = expected_hooks(&HooksConfig {
+        commit_msg: Some(false),
+        secret_scan: Some(false),
+        pre_commit: Some(vec!["cargo fmt --check".to_string()]),
+    });
+    assert_eq!(hooks.len(), 1);
+    assert!(!hooks[0].1.contains("patterns="));
+    assert!(hooks[0].1.contains(
+        "cargo fmt --check || { echo 'pre-commit: `cargo fmt --check` failed' >&2; exit 1; }"
+    ));
+
+    assert_eq!(shell_quote("it's"), "'it'\\''s'");
+}
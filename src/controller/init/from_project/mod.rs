@@ -0,0 +1,191 @@
+// Module: controller::init::from_project
+use crate::helpers::config::project::{read_project, PROJECT_FILE};
+use crate::helpers::filesystem::dir::{check_if_directory_exists, get_absolute_path};
+use crate::helpers::git::{self, Git};
+use crate::helpers::services::{get_service_paths, normalize_path};
+use crate::models::project::Project;
+use std::path::PathBuf;
+
+/// The options accepted by `tools init --from-project`
+#[derive(Debug, PartialEq)]
+pub struct FromProjectOptions {
+    pub project_file: String, // the project definition file to reconcile against
+    pub submodule: bool,      // register the cloned repositories as git submodules
+}
+
+/// The state of a service declared in the project definition file
+#[derive(Debug, PartialEq)]
+pub enum ServiceState {
+    Present,
+    Cloned,
+    CloneFailed(String),
+    NoRepository,
+}
+
+// ## parse_args(args: &[String]) -> Result<FromProjectOptions, String>
+// This function parses the arguments following `tools init --from-project`
+//
+// ### arguments:
+// - args: &[String] - the arguments after `--from-project`
+//
+// ### returns:
+// - Result<FromProjectOptions, String> - the parsed options or a message describing the error
+pub fn parse_args(args: &[String]) -> Result<FromProjectOptions, String> {
+    let mut options = FromProjectOptions {
+        project_file: PROJECT_FILE.to_string(),
+        submodule: false,
+    };
+
+    for arg in args {
+        match arg.as_str() {
+            "--submodule" => options.submodule = true,
+            unknown if unknown.starts_with('-') => {
+                return Err(format!("Unknown option: {}", unknown))
+            }
+            project_file => options.project_file = project_file.to_string(),
+        }
+    }
+
+    Ok(options)
+}
+
+// ## from_project(options: FromProjectOptions)
+// This function walks the service tree of a project definition file,
+// clones every service repository whose directory is missing and reports
+// the differences between the project definition and the disk
+//
+// ### arguments:
+// - options: FromProjectOptions - the parsed options of the command
+//
+// ### returns:
+// - nothing
+pub fn from_project(options: FromProjectOptions) {
+    let project_file = get_absolute_path(&options.project_file);
+
+    let project = match read_project(&PathBuf::from(&project_file)) {
+        Some(project) => project,
+        None => {
+            eprintln!("Couldn't read project definition file {}", project_file);
+            std::process::exit(1);
+        }
+    };
+
+    let declared = get_declared_services(&project);
+    let mut states = Vec::new();
+
+    for (name, path, git_url) in &declared {
+        let state = if check_if_directory_exists(path) {
+            ServiceState::Present
+        } else {
+            match git_url {
+                Some(git_url) => clone_service(git_url, path, options.submodule),
+                None => ServiceState::NoRepository,
+            }
+        };
+        states.push((name.clone(), path.clone(), state));
+    }
+
+    // submodules and service directories on disk that the project
+    // definition doesn't know about
+    let undeclared = get_service_paths()
+        .into_iter()
+        .filter(|service| {
+            !declared
+                .iter()
+                .any(|(_, path, _)| normalize_path(path) == normalize_path(&service.1))
+        })
+        .map(|service| service.1)
+        .collect::<Vec<String>>();
+
+    print_report(&options.project_file, &states, &undeclared);
+
+    // exit with a failing code if a service couldn't be cloned
+    if states
+        .iter()
+        .any(|(_, _, state)| matches!(state, ServiceState::CloneFailed(_)))
+    {
+        std::process::exit(1);
+    }
+}
+
+// ## get_declared_services(project: &Project) -> Vec<(String, String, Option<String>)>
+// This function returns every service of the project that lives in its
+// own directory, which is the declared `path` or the service name when the
+// service only declares a repository
+//
+// ### returns:
+// - Vec<(String, String, Option<String>)> - (service name, service path, git url)
+fn get_declared_services(project: &Project) -> Vec<(String, String, Option<String>)> {
+    project
+        .get_all_services()
+        .into_iter()
+        .filter(|service| service.has_directory())
+        .map(|service| {
+            (
+                service.name.clone(),
+                service.service_path(),
+                service.get_git_url().map(|git_url| git_url.to_string()),
+            )
+        })
+        .collect()
+}
+
+// ## clone_service(git_url: &str, path: &str, submodule: bool) -> ServiceState
+// This function clones a service repository into its path, either as a
+// plain clone or registered as a git submodule of the project
+fn clone_service(git_url: &str, path: &str, submodule: bool) -> ServiceState {
+    println!("Cloning {} into {}", git_url, path);
+
+    let result = if submodule {
+        Git::project().submodule_add(git_url, path)
+    } else {
+        git::clone(git_url, &PathBuf::from(get_absolute_path(path))).map(|_| ())
+    };
+
+    match result {
+        Ok(_) => ServiceState::Cloned,
+        Err(error) => ServiceState::CloneFailed(error.to_string()),
+    }
+}
+
+// ## print_report(project_file, states, undeclared)
+// This function prints the state of every declared service and the
+// services on disk that are not declared in the project definition file
+fn print_report(
+    project_file: &str,
+    states: &[(String, String, ServiceState)],
+    undeclared: &[String],
+) {
+    println!("Services declared in {}:", project_file);
+    for (name, path, state) in states {
+        let state = match state {
+            ServiceState::Present => "present".to_string(),
+            ServiceState::Cloned => "cloned".to_string(),
+            ServiceState::CloneFailed(error) => format!("missing (clone failed: {})", error),
+            ServiceState::NoRepository => "missing (no git_url to clone from)".to_string(),
+        };
+        println!("  {} ({}) - {}", name, path, state);
+    }
+
+    if !undeclared.is_empty() {
+        println!("Services not declared in {}:", project_file);
+        for path in undeclared {
+            println!("  {}", path);
+        }
+    }
+}
+
+#[test]
+fn test_from_project_parse_args() {
+    let args = ["projects/project.yaml", "--submodule"].map(String::from);
+
+    assert_eq!(
+        parse_args(&args),
+        Ok(FromProjectOptions {
+            project_file: "projects/project.yaml".to_string(),
+            submodule: true,
+        })
+    );
+    assert_eq!(parse_args(&[]).unwrap().project_file, PROJECT_FILE);
+    assert!(parse_args(&["--bogus".to_string()]).is_err());
+}
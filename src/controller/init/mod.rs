@@ -1,6 +1,9 @@
 // Module: controller::init
-mod submodules;
+mod from_project;
+pub mod submodules;
+mod templates;
 use self::submodules::delete_all_submodules;
+use crate::helpers::services::get_service_paths;
 use crate::helpers::user_input;
 use submodules::{check_if_submodule_init_run_needed, get_submodule_paths};
 
@@ -11,7 +14,7 @@ use submodules::{check_if_submodule_init_run_needed, get_submodule_paths};
 ///
 /// ### returns: ()
 pub fn rebuild_full(args: &[String]) {
-    if args.get(0).map(|s| s.as_str()) == Some("-y") {
+    if args.first().map(|s| s.as_str()) == Some("-y") {
         clean_logic();
     } else {
         println!("Cleaning the project... Are you sure you want to do this?");
@@ -49,7 +52,7 @@ fn clean_logic() {
 pub fn clean(args: &[String]) {
     // check if the -y flag was passed to the command
     // if it was not, ask the user for confirmation
-    if args.get(0).map(|s| s.as_str()) == Some("-y") {
+    if args.first().map(|s| s.as_str()) == Some("-y") {
         clean_logic();
     } else {
         println!("Cleaning the project... Are you sure you want to do this?");
@@ -66,12 +69,71 @@ pub fn clean(args: &[String]) {
     }
 }
 
+/// ## from_project()
+/// This function initializes the project from a project definition file,
+/// cloning the service repositories that are missing on disk.
+///
+/// ### returns: ()
+pub fn from_project(args: &[String]) {
+    match from_project::parse_args(args) {
+        Ok(options) => from_project::from_project(options),
+        Err(error) => {
+            eprintln!("{}", error);
+            crate::helpers::help::init_help::print_help();
+            std::process::exit(1);
+        }
+    }
+}
+
+/// ## locked()
+/// This function initializes the project and then checks out the commit
+/// of every service pinned in the lock file for the current project
+/// version, or the version passed as the first argument.
+///
+/// ### returns: ()
+pub fn locked(args: &[String]) {
+    let project_version = args
+        .first()
+        .cloned()
+        .unwrap_or(crate::CONFIG.project_version.clone());
+
+    // fail before touching the project if the version was never locked
+    if crate::helpers::config::lockfile::get_lock_file()
+        .get_version(&project_version)
+        .is_none()
+    {
+        eprintln!(
+            "Project version {} is not locked, run `tools lock` first",
+            project_version
+        );
+        std::process::exit(1);
+    }
+
+    init();
+
+    if !crate::controller::lock::checkout_locked(&project_version) {
+        std::process::exit(1);
+    }
+}
+
 /// ## init()
 /// This function initializes the project and creates the necessary
-/// files and directories for the project.
+/// files and directories for the project. It also bootstraps the
+/// project configs and secrets from the template files.
 ///
 /// ### returns: ()
 pub fn init() {
     // check if all submodules have been initialized
     check_if_submodule_init_run_needed(get_submodule_paths());
+
+    // report the services that are still missing, e.g. directories declared
+    // in project.yaml that don't exist or submodules that failed to initialize
+    let service_paths = get_service_paths();
+    for service in service_paths.iter().filter(|service| !service.2) {
+        eprintln!("Service {} is missing at {}", service.1, service.0);
+    }
+
+    // render the template files of the project and every service that was just initialized
+    let reports = templates::bootstrap_templates(&service_paths);
+    templates::print_report(&reports);
 }
@@ -1,4 +1,5 @@
 use crate::helpers::filesystem::dir::map_dirs;
+use crate::helpers::git::Git;
 use crate::CONFIG;
 
 // ## get_submodule_paths() -> Vec<(String, String, bool)>
@@ -13,19 +14,19 @@ use crate::CONFIG;
 pub fn get_submodule_paths() -> Vec<(String, String, bool)> {
     // read the config file and get the .gitmodules file path and root path
     let base_path = CONFIG.project_path.clone();
-    // try to get the path to .gitmodule file in the current directory
-    let submodule_path = std::fs::canonicalize(format!("{}/{}", base_path, ".gitmodules"))
-        .expect("Couldn't get path to .gitmodules file")
-        .to_str()
-        .unwrap()
-        .to_string();
+    // try to get the path to .gitmodule file in the current directory,
+    // projects without submodules (e.g. monorepos) don't have one
+    let submodule_path = match std::fs::canonicalize(format!("{}/{}", base_path, ".gitmodules")) {
+        Ok(submodule_path) => submodule_path.to_str().unwrap().to_string(),
+        Err(_) => return Vec::new(),
+    };
 
     // read the .gitmodules file and get the paths of the submodules
     // example .gitmodules file:
     // [submodule "backend"]
     // path = backend
     // url = git@github.com:xN4P4LM-org/{project}
-    let sub_module_paths = std::fs::read_to_string(format!("{}/{}", base_path, submodule_path))
+    let sub_module_paths = std::fs::read_to_string(submodule_path)
         .expect("Couldn't read .gitmodules file")
         .lines()
         .filter(|line| line.contains("path = "))
@@ -54,30 +55,11 @@ pub fn check_if_submodule_init_run_needed(sub_module_paths: Vec<(String, String,
 
         // if the directory doesn't exist, call `git submodule update --init --recursive`
         if !dir.2 {
-            // run the command in a child process in the directory of the project
-            let output = std::process::Command::new("git")
-                .arg("submodule")
-                .arg("update")
-                .arg("--init")
-                .arg("--recursive")
-                .current_dir(CONFIG.project_path.clone())
-                .output();
-
-            // print the output
-            match output {
-                Ok(output) => {
-                    if output.status.success() {
-                        missing_submodule = true
-                    } else {
-                        eprintln!("Command executed with failing error code");
-                        missing_submodule = true
-                    }
-                }
-                Err(_) => {
-                    eprintln!("Failed to run git submodule update --init --recursive");
-                    missing_submodule = true
-                }
+            // run the command in the directory of the project
+            if let Err(error) = Git::project().submodule_update() {
+                eprintln!("{}", error);
             }
+            missing_submodule = true
         }
     }
 
@@ -0,0 +1,296 @@
+// Module: controller::init::templates
+use crate::helpers::templates::{get_project_variables, render_template};
+use crate::helpers::user_input::get_user_input;
+use crate::CONFIG;
+use std::collections::HashMap;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+/// File suffixes that mark a file as a template for a real file
+const TEMPLATE_SUFFIXES: [&str; 2] = [".example", ".template"];
+
+/// Directories that are never searched for templates
+const IGNORED_DIRS: [&str; 4] = [".git", "node_modules", "target", "vendor"];
+
+/// The result of bootstrapping a single template file
+#[derive(Debug, PartialEq)]
+pub struct TemplateReport {
+    pub template: PathBuf,         // the template file
+    pub target: PathBuf,           // the file rendered from the template
+    pub written: bool,             // false if the target already existed
+    pub missing_keys: Vec<String>, // keys in the target that still need a value
+}
+
+// ## bootstrap_templates(service_paths: &[(String, String, bool)]) -> Vec<TemplateReport>
+// This function finds the template files in the project and in every
+// service, renders them with the project variables and writes the real
+// files next to them when they do not exist yet
+//
+// ### arguments:
+// - service_paths: &[(String, String, bool)] - vector of tuples containing information about the services
+//   - (String, String, bool) - (service absolute path, service path, service exists)
+//
+// ### returns:
+// - Vec<TemplateReport> - one report per template that was found
+pub fn bootstrap_templates(service_paths: &[(String, String, bool)]) -> Vec<TemplateReport> {
+    let project_variables = get_project_variables();
+    let interactive = std::io::stdin().is_terminal();
+
+    // values prompted from the user are shared between all templates
+    let mut prompted: HashMap<String, String> = HashMap::new();
+    let mut reports = Vec::new();
+
+    // the project itself, without descending into the services
+    let service_dirs = service_paths
+        .iter()
+        .map(|service| PathBuf::from(&service.0))
+        .collect::<Vec<PathBuf>>();
+    let mut templates = vec![(PathBuf::from(&CONFIG.project_path), None)];
+
+    // every initialized service
+    for service in service_paths.iter().filter(|service| service.2) {
+        templates.push((PathBuf::from(&service.0), Some(service.1.clone())));
+    }
+
+    for (root, service) in templates {
+        let mut variables = project_variables.clone();
+        if let Some(service) = &service {
+            variables.insert("service_path".to_string(), service.clone());
+            variables.insert(
+                "service_name".to_string(),
+                service.rsplit('/').next().unwrap_or(service).to_string(),
+            );
+        }
+
+        for template in find_templates(&root, &service_dirs) {
+            reports.push(bootstrap_template(
+                &template,
+                &variables,
+                &mut prompted,
+                interactive,
+            ));
+        }
+    }
+
+    reports
+}
+
+// ## print_report(reports: &[TemplateReport])
+// This function prints which files were created from templates and which
+// keys still need a value
+pub fn print_report(reports: &[TemplateReport]) {
+    if reports.is_empty() {
+        return;
+    }
+
+    println!("Templates:");
+    for report in reports {
+        let status = if report.written { "created" } else { "exists " };
+        println!("  {} {}", status, report.target.display());
+    }
+
+    let incomplete = reports
+        .iter()
+        .filter(|report| !report.missing_keys.is_empty())
+        .collect::<Vec<&TemplateReport>>();
+
+    if !incomplete.is_empty() {
+        println!("The following keys still need values:");
+        for report in incomplete {
+            println!(
+                "  {}: {}",
+                report.target.display(),
+                report.missing_keys.join(", ")
+            );
+        }
+    }
+}
+
+// ## find_templates(root: &Path, skip: &[PathBuf]) -> Vec<PathBuf>
+// This function recursively finds all template files below root, without
+// descending into ignored directories or the directories in skip
+fn find_templates(root: &Path, skip: &[PathBuf]) -> Vec<PathBuf> {
+    let mut templates = Vec::new();
+
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return templates;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if path.is_dir() {
+            if !IGNORED_DIRS.contains(&name.as_str()) && !skip.contains(&path) {
+                templates.append(&mut find_templates(&path, skip));
+            }
+        } else if get_target_path(&path).is_some() {
+            templates.push(path);
+        }
+    }
+
+    templates.sort();
+    templates
+}
+
+// ## get_target_path(template: &Path) -> Option<PathBuf>
+// This function returns the path of the real file for a template,
+// e.g. `.env.example` -> `.env`, or None if the file is not a template
+fn get_target_path(template: &Path) -> Option<PathBuf> {
+    let name = template.file_name()?.to_str()?;
+
+    TEMPLATE_SUFFIXES.iter().find_map(|suffix| {
+        name.strip_suffix(suffix)
+            .filter(|target| !target.is_empty())
+            .map(|target| template.with_file_name(target))
+    })
+}
+
+// ## bootstrap_template(...) -> TemplateReport
+// This function renders a single template and writes the real file if
+// it does not exist yet
+fn bootstrap_template(
+    template: &Path,
+    variables: &HashMap<String, String>,
+    prompted: &mut HashMap<String, String>,
+    interactive: bool,
+) -> TemplateReport {
+    let target = get_target_path(template).expect("template without a target path");
+
+    // never overwrite an existing file, only report what it is missing
+    if target.exists() {
+        let existing = std::fs::read_to_string(&target).unwrap_or_default();
+        return TemplateReport {
+            template: template.to_path_buf(),
+            missing_keys: find_missing_keys(&existing),
+            target,
+            written: false,
+        };
+    }
+
+    let raw_template = match std::fs::read_to_string(template) {
+        Ok(raw_template) => raw_template,
+        Err(error) => {
+            eprintln!("Couldn't read template {}: {}", template.display(), error);
+            return TemplateReport {
+                template: template.to_path_buf(),
+                target,
+                written: false,
+                missing_keys: Vec::new(),
+            };
+        }
+    };
+
+    // prompt for the placeholders that are not known yet
+    if interactive {
+        for placeholder in find_placeholders(&raw_template) {
+            if variables.contains_key(&placeholder) || prompted.contains_key(&placeholder) {
+                continue;
+            }
+            let value = get_user_input(
+                &format!(
+                    "Value for {} in {} (leave empty to skip)",
+                    placeholder,
+                    target.display()
+                ),
+                None,
+            );
+            if !value.is_empty() {
+                prompted.insert(placeholder, value);
+            }
+        }
+    }
+
+    let mut all_variables = variables.clone();
+    all_variables.extend(prompted.clone());
+    let rendered = render_template(&raw_template, &all_variables);
+
+    let written = match std::fs::write(&target, &rendered) {
+        Ok(_) => true,
+        Err(error) => {
+            eprintln!("Couldn't write {}: {}", target.display(), error);
+            false
+        }
+    };
+
+    TemplateReport {
+        template: template.to_path_buf(),
+        target,
+        written,
+        missing_keys: find_missing_keys(&rendered),
+    }
+}
+
+// ## find_placeholders(template: &str) -> Vec<String>
+// This function returns the names of all `{{ name }}` placeholders in
+// the order they first appear
+fn find_placeholders(template: &str) -> Vec<String> {
+    let mut placeholders = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + end].trim().to_string();
+        if !name.is_empty() && !placeholders.contains(&name) {
+            placeholders.push(name);
+        }
+        rest = &rest[start + end + 2..];
+    }
+
+    placeholders
+}
+
+// ## find_missing_keys(contents: &str) -> Vec<String>
+// This function returns the keys of a rendered file that still need a
+// value: unresolved placeholders and `KEY=` lines without a value
+fn find_missing_keys(contents: &str) -> Vec<String> {
+    let mut missing = find_placeholders(contents);
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        if let Some(key) = line.strip_suffix('=') {
+            let key = key.trim_start_matches("export ").trim();
+            if !key.is_empty() && !key.contains(' ') && !missing.contains(&key.to_string()) {
+                missing.push(key.to_string());
+            }
+        }
+    }
+
+    missing
+}
+
+#[test]
+fn test_render_template() {
+    let variables = HashMap::from([
+        ("project_name".to_string(), "tools".to_string()),
+        ("service_name".to_string(), "backend".to_string()),
+    ]);
+
+    let template = "NAME={{ project_name }}-{{service_name}}\nSECRET={{ secret }}\nTOKEN=\n";
+    let rendered = render_template(template, &variables);
+
+    assert_eq!(
+        rendered,
+        "NAME=tools-backend\nSECRET={{ secret }}\nTOKEN=\n"
+    );
+    assert_eq!(find_missing_keys(&rendered), vec!["secret", "TOKEN"]);
+}
+
+#[test]
+fn test_template_target_path() {
+    assert_eq!(
+        get_target_path(Path::new("backend/.env.example")),
+        Some(PathBuf::from("backend/.env"))
+    );
+    assert_eq!(
+        get_target_path(Path::new("config.yaml.template")),
+        Some(PathBuf::from("config.yaml"))
+    );
+    assert_eq!(get_target_path(Path::new("config.yaml")), None);
+    assert_eq!(get_target_path(Path::new(".example")), None);
+}
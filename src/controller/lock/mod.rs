@@ -0,0 +1,153 @@
+// Module: controller::lock
+use crate::helpers::config::lockfile::{get_lock_file, get_path_to_lock_file, write_lock_file};
+use crate::helpers::container_engine::get_engine;
+use crate::helpers::docker::docker_compose::file::get_docker_compose_file;
+use crate::helpers::git::Git;
+use crate::helpers::services::{get_service_paths, get_service_repositories};
+use crate::models::lockfile::{LockedImage, LockedService, LockedVersion};
+use crate::CONFIG;
+
+// ## lock(args: &[String])
+// This function records the current commit of every service repository,
+// submodules and nested clones, and the image digests of the compose file
+// when `--images` is passed, for the current project version in the lock
+// file
+//
+// ### arguments:
+// - args: &[String] - the arguments after `lock`
+//
+// ### returns:
+// - nothing
+pub fn lock(args: &[String]) {
+    let with_images = args.iter().any(|arg| arg == "--images");
+
+    // every service must exist before its commit can be pinned
+    if let Some(missing) = get_service_paths().into_iter().find(|service| !service.2) {
+        eprintln!("Service {} is missing, run `tools init` first", missing.1);
+        std::process::exit(1);
+    }
+
+    // plain directories of a monorepo are pinned by the commit of the project
+    let mut services = Vec::new();
+    for (path, repository) in get_service_repositories(&[]) {
+        match repository.rev_parse("HEAD") {
+            Ok(commit) => services.push(LockedService { path, commit }),
+            Err(error) => {
+                eprintln!("Couldn't get the commit of service {}: {}", path, error);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let images = if with_images {
+        Some(get_image_digests())
+    } else {
+        None
+    };
+
+    let mut lock_file = get_lock_file();
+    lock_file.set_version(LockedVersion {
+        project_version: CONFIG.project_version.clone(),
+        services,
+        images,
+    });
+    write_lock_file(&lock_file);
+
+    println!(
+        "Locked project version {} in {}",
+        CONFIG.project_version,
+        get_path_to_lock_file().display()
+    );
+}
+
+// ## checkout_locked(project_version: &str) -> bool
+// This function checks out the commit every service is pinned to for
+// a project version in the lock file
+//
+// ### arguments:
+// - project_version: &str - the project version to check out
+//
+// ### returns:
+// - bool - true if every service is at its locked commit, false if not
+pub fn checkout_locked(project_version: &str) -> bool {
+    let lock_file = get_lock_file();
+
+    let Some(locked_version) = lock_file.get_version(project_version) else {
+        eprintln!(
+            "Project version {} is not in {}, run `tools lock` first",
+            project_version,
+            get_path_to_lock_file().display()
+        );
+        return false;
+    };
+
+    let mut success = true;
+    for service in &locked_version.services {
+        let service_path = format!("{}/{}", CONFIG.project_path, service.path);
+
+        if checkout_commit(&service_path, &service.commit) {
+            println!("{} - {}", service.path, service.commit);
+        } else {
+            eprintln!("Failed to check out {} at {}", service.path, service.commit);
+            success = false;
+        }
+    }
+
+    success
+}
+
+// ## checkout_commit(path: &str, commit: &str) -> bool
+// This function checks out a commit in a repository, fetching it first
+// if it is not available locally
+fn checkout_commit(path: &str, commit: &str) -> bool {
+    let repository = Git::new(path);
+
+    if repository.checkout(commit, true).is_ok() {
+        return true;
+    }
+
+    // the commit may not have been fetched yet
+    match repository
+        .fetch(None)
+        .and_then(|_| repository.checkout(commit, true))
+    {
+        Ok(_) => true,
+        Err(error) => {
+            eprintln!("{}", error);
+            false
+        }
+    }
+}
+
+// ## get_image_digests() -> Vec<LockedImage>
+// This function resolves the repository digest of every image in the
+// compose file, skipping images that are not available locally
+fn get_image_digests() -> Vec<LockedImage> {
+    let docker_compose = get_docker_compose_file();
+
+    let mut services = docker_compose
+        .services
+        .iter()
+        .filter_map(|(name, service)| service.image.as_ref().map(|image| (name, image)))
+        .collect::<Vec<(&String, &String)>>();
+    services.sort();
+
+    let engine = get_engine();
+
+    let mut images = Vec::new();
+    for (service, image) in services {
+        match engine.image_digest(image) {
+            Some(digest) => images.push(LockedImage {
+                service: service.clone(),
+                image: image.clone(),
+                digest,
+            }),
+            None => eprintln!(
+                "Couldn't resolve the digest of {} ({}), pull it first",
+                image, service
+            ),
+        }
+    }
+
+    images
+}
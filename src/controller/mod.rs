@@ -1,10 +1,22 @@
 use crate::helpers;
-use crate::CONFIG;
+use crate::helpers::program_checker::Requirement;
+mod branch;
+mod changelog;
+mod compose;
+mod dockerfile;
+mod doctor;
 mod down;
-mod init;
+mod foreach;
+mod graph;
+mod hooks;
+pub mod init;
+mod lock;
 mod new;
+mod release;
 mod setup;
+mod status;
 mod up;
+mod version;
 mod watch;
 
 /// ## run()
@@ -19,16 +31,38 @@ mod watch;
 /// ### Returns
 /// This function does not return anything
 pub fn run(command: String, args: &[String]) {
+    // check the programs the command needs before it has any side effects
+    if !matches!(
+        args.first().map(|arg0| arg0.as_str()),
+        Some("help" | "-h" | "--help")
+    ) {
+        helpers::program_checker::require(&command, requirements(&command, args));
+    }
+
     match command.as_str() {
+        "branch" => branch(args),
+        "changelog" => changelog(args),
+        "compose" => compose(args),
+        "dockerfile" => dockerfile(args),
+        "doctor" => doctor(args),
         "down" => down(args),
+        "foreach" => foreach(args),
+        "graph" => graph(args),
+        "hooks" => hooks(args),
         "init" => init(args),
+        "lock" => lock(args),
+        "release" => release(args),
         "up" => up(args),
+        "version" => version(args),
         "watch" => watch(args),
         "status" => status(args),
         "-v" | "--version" => {
             // print the version number of the program
             println!("Tools Version: {}", env!("CARGO_PKG_VERSION"));
-            println!("Project Version: {}", CONFIG.get_version());
+            println!(
+                "Project Version: {}",
+                helpers::version::get_version_info().version
+            );
         }
         _ => {
             helpers::help::main_help::print_help();
@@ -36,11 +70,169 @@ pub fn run(command: String, args: &[String]) {
     }
 }
 
+/// ## requirements()
+/// This function returns the external programs a command needs, they are
+/// checked before the command runs.
+///
+/// ### Arguments
+/// - command: &str - The command to run
+/// - args: &[String] - The arguments of the command, options like `--images` need more
+///
+/// ### Returns
+/// - &'static [Requirement] - The requirements of the command, empty if it needs nothing
+pub fn requirements(command: &str, args: &[String]) -> &'static [Requirement] {
+    match command {
+        // the image digests are read from the container engine
+        "lock" if args.iter().any(|arg| arg == "--images") => {
+            &[Requirement::Git, Requirement::ContainerEngine]
+        }
+        "branch" | "changelog" | "hooks" | "init" | "lock" | "release" => &[Requirement::Git],
+        "down" | "status" | "up" | "watch" => &[Requirement::ContainerEngine],
+        _ => &[],
+    }
+}
+
 // Controller actions
 
+/// ## branch()
+/// This function creates, checks out, deletes or lists a branch in
+/// every service repository of the project.
+///
+/// ### Arguments
+/// - args: &[String] - A list of arguments to pass to the command
+///
+/// ### Returns
+/// This function does not return anything
+pub fn branch(args: &[String]) {
+    match args.first().map(|arg0| arg0.as_str()) {
+        // print the help message for the branch command
+        Some("help" | "-h" | "--help") | None => helpers::help::branch_help::print_help(),
+        // otherwise parse the options and run the branch action
+        _ => match branch::parse_args(args) {
+            Ok(options) => branch::branch(options),
+            Err(error) => {
+                eprintln!("{}", error);
+                helpers::help::branch_help::print_help();
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+/// ## changelog()
+/// This function renders the Markdown changelog of the project and every
+/// service from the conventional commits between two refs.
+///
+/// ### Arguments
+/// - args: &[String] - A list of arguments to pass to the command
+///
+/// ### Returns
+/// This function does not return anything
+pub fn changelog(args: &[String]) {
+    match args.first().map(|arg0| arg0.as_str()) {
+        // print the help message for the changelog command
+        Some("help" | "-h" | "--help") | None => helpers::help::changelog_help::print_help(),
+        // otherwise parse the options and render the changelog of the range
+        _ => match changelog::parse_args(args) {
+            Ok(options) => changelog::changelog(options),
+            Err(error) => {
+                eprintln!("{}", error);
+                helpers::help::changelog_help::print_help();
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+/// ## compose()
+/// This function edits the compose file, adding or removing services,
+/// networks, volumes, secrets, configs, environment variables and ports,
+/// checks it for problems, prints it with the variables resolved, or
+/// generates the services of project.yaml.
+///
+/// ### Arguments
+/// - args: &[String] - A list of arguments to pass to the command
+///
+/// ### Returns
+/// This function does not return anything
+pub fn compose(args: &[String]) {
+    match args.first().map(|arg0| arg0.as_str()) {
+        // print the help message for the compose command
+        Some("help" | "-h" | "--help") | None => helpers::help::compose_help::print_help(),
+        // check the compose file
+        Some("lint") => compose::lint_compose_file(&args[1..]),
+        // print the compose file with the variables resolved
+        Some("config") => compose::config(&args[1..]),
+        // create the services of project.yaml
+        Some("generate") => compose::generate(&args[1..]),
+        // otherwise parse the edit and write it to the compose file
+        _ => match compose::parse_args(args) {
+            Ok(edit) => compose::compose(edit),
+            Err(error) => {
+                eprintln!("{}", error);
+                helpers::help::compose_help::print_help();
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+/// ## dockerfile()
+/// This function generates the Dockerfile and .dockerignore of a service
+/// from the templates of its languages and frameworks.
+///
+/// ### Arguments
+/// - args: &[String] - A list of arguments to pass to the command
+///
+/// ### Returns
+/// This function does not return anything
+pub fn dockerfile(args: &[String]) {
+    match args.first().map(|arg0| arg0.as_str()) {
+        // print the help message for the dockerfile command
+        Some("help" | "-h" | "--help") | None => helpers::help::dockerfile_help::print_help(),
+        // generate the Dockerfile of a service
+        Some("generate") => match dockerfile::parse_args(&args[1..]) {
+            Ok(options) => dockerfile::generate(options),
+            Err(error) => {
+                eprintln!("{}", error);
+                helpers::help::dockerfile_help::print_help();
+                std::process::exit(1);
+            }
+        },
+        Some(unknown) => {
+            eprintln!("Unknown subcommand: {}", unknown);
+            helpers::help::dockerfile_help::print_help();
+            std::process::exit(1);
+        }
+    }
+}
+
+/// ## doctor()
+/// This function checks the programs, the docker daemon and the project
+/// files the tools depend on, and prints how to fix every problem.
+///
+/// ### Arguments
+/// - args: &[String] - A list of arguments to pass to the command
+///
+/// ### Returns
+/// This function does not return anything
+pub fn doctor(args: &[String]) {
+    match args.first().map(|arg0| arg0.as_str()) {
+        // print the help message for the doctor command
+        Some("help" | "-h" | "--help") => helpers::help::doctor_help::print_help(),
+        // doctor takes no other arguments
+        Some(unknown) => {
+            eprintln!("Unexpected argument: {}", unknown);
+            helpers::help::doctor_help::print_help();
+            std::process::exit(1);
+        }
+        None => doctor::doctor(),
+    }
+}
+
 /// ## down()
-/// This function stops the project using `docker compose down`
-/// to stop the project.
+/// This function stops the project using `compose down` of the
+/// configured container engine.
 ///
 /// ### Arguments
 /// - args: &[String] - A list of arguments to pass to the command
@@ -48,18 +240,93 @@ pub fn run(command: String, args: &[String]) {
 /// ### Returns
 /// This function does not return anything
 pub fn down(args: &[String]) {
-    println!("Not implemented up yet");
-    match args.get(0) {
-        Some(arg0) => match arg0.as_str() {
-            // print the help message for the new command
-            "help" | "-h" | "--help" => helpers::help::help_not_implemented_yet(),
-            // if the argument is not recognized, print the help message for the new command
-            _ => helpers::help::help_not_implemented_yet(),
+    match args.first().map(|arg0| arg0.as_str()) {
+        // print the help message for the down command
+        Some("help" | "-h" | "--help") => helpers::help::down_help::print_help(),
+        // otherwise parse the options and stop the project
+        _ => match down::parse_args(args) {
+            Ok(options) => down::down(options),
+            Err(error) => {
+                eprintln!("{}", error);
+                helpers::help::down_help::print_help();
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+/// ## foreach()
+/// This function runs a command in every service of the project,
+/// optionally in parallel, and prints a pass/fail matrix at the end.
+///
+/// ### Arguments
+/// - args: &[String] - A list of arguments to pass to the command
+///
+/// ### Returns
+/// This function does not return anything
+pub fn foreach(args: &[String]) {
+    match args.first().map(|arg0| arg0.as_str()) {
+        // print the help message for the foreach command
+        Some("help" | "-h" | "--help") | None => helpers::help::foreach_help::print_help(),
+        // otherwise parse the options and run the command in every service
+        _ => match foreach::parse_args(args) {
+            Ok(options) => foreach::foreach(options),
+            Err(error) => {
+                eprintln!("{}", error);
+                helpers::help::foreach_help::print_help();
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+/// ## graph()
+/// This function prints the dependency graph of the services of the
+/// compose file, as text, DOT or Mermaid, or their start and stop order.
+///
+/// ### Arguments
+/// - args: &[String] - A list of arguments to pass to the command
+///
+/// ### Returns
+/// This function does not return anything
+pub fn graph(args: &[String]) {
+    match args.first().map(|arg0| arg0.as_str()) {
+        // print the help message for the graph command
+        Some("help" | "-h" | "--help") => helpers::help::graph_help::print_help(),
+        // otherwise parse the options and print the graph
+        _ => match graph::parse_args(args) {
+            Ok(options) => graph::graph(options),
+            Err(error) => {
+                eprintln!("{}", error);
+                helpers::help::graph_help::print_help();
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+/// ## hooks()
+/// This function installs the configured git hooks, or shows their state,
+/// in the project and every service repository.
+///
+/// ### Arguments
+/// - args: &[String] - A list of arguments to pass to the command
+///
+/// ### Returns
+/// This function does not return anything
+pub fn hooks(args: &[String]) {
+    match args.first().map(|arg0| arg0.as_str()) {
+        // print the help message for the hooks command
+        Some("help" | "-h" | "--help") | None => helpers::help::hooks_help::print_help(),
+        // otherwise parse the options and run the hooks action
+        _ => match hooks::parse_args(args) {
+            Ok(options) => hooks::hooks(options),
+            Err(error) => {
+                eprintln!("{}", error);
+                helpers::help::hooks_help::print_help();
+                std::process::exit(1);
+            }
         },
-        None => {
-            // if no arguments are passed, print the help message for the new command
-            helpers::help::help_not_implemented_yet();
-        }
     }
 }
 
@@ -74,13 +341,17 @@ pub fn down(args: &[String]) {
 /// ### Returns
 /// This function does not return anything
 pub fn init(args: &[String]) {
-    match args.get(0) {
+    match args.first() {
         Some(arg0) => match arg0.as_str() {
             // rebuild_full performs a clean and then a init to rebuild the project
             // from scratch, it takes an argument to confirm the rebuild
             "rebuild" => init::rebuild_full(&args[1..]),
             // clean takes arguments to confirm the deletion
             "clean" => init::clean(&args[1..]),
+            // from-project clones the services declared in a project definition file
+            "--from-project" => init::from_project(&args[1..]),
+            // locked checks out the service commits pinned in the lock file
+            "--locked" => init::locked(&args[1..]),
             // print the help message for the init command
             "help" | "-h" | "--help" => helpers::help::init_help::print_help(),
             // if the argument is not recognized, print the help message for the init command
@@ -93,6 +364,49 @@ pub fn init(args: &[String]) {
     }
 }
 
+/// ## lock()
+/// This function records the commit of every service for the current
+/// project version in the `tools.lock` file.
+///
+/// ### Arguments
+/// - args: &[String] - A list of arguments to pass to the command
+///
+/// ### Returns
+/// This function does not return anything
+pub fn lock(args: &[String]) {
+    match args.first().map(|arg0| arg0.as_str()) {
+        // print the help message for the lock command
+        Some("help" | "-h" | "--help") => helpers::help::lock_help::print_help(),
+        // otherwise lock the current project version
+        _ => lock::lock(args),
+    }
+}
+
+/// ## release()
+/// This function bumps the project version, writes the changelog and
+/// commits and tags the release.
+///
+/// ### Arguments
+/// - args: &[String] - A list of arguments to pass to the command
+///
+/// ### Returns
+/// This function does not return anything
+pub fn release(args: &[String]) {
+    match args.first().map(|arg0| arg0.as_str()) {
+        // print the help message for the release command
+        Some("help" | "-h" | "--help") => helpers::help::release_help::print_help(),
+        // otherwise parse the options and release the project
+        _ => match release::parse_args(args) {
+            Ok(options) => release::release(options),
+            Err(error) => {
+                eprintln!("{}", error);
+                helpers::help::release_help::print_help();
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
 // ## new()
 // This function creates a new submodule for the project
 //
@@ -101,9 +415,10 @@ pub fn init(args: &[String]) {
 //
 // ### Returns
 // This function does not return anything
+#[allow(dead_code)]
 pub fn new(args: &[String]) {
     println!("Not implemented up yet");
-    match args.get(0) {
+    match args.first() {
         Some(arg0) => match arg0.as_str() {
             // print the help message for the new command
             "help" | "-h" | "--help" => helpers::help::help_not_implemented_yet(),
@@ -118,8 +433,8 @@ pub fn new(args: &[String]) {
 }
 
 /// ## up()
-/// This function starts the project using `docker compose up -d`
-/// to run the project in the background.
+/// This function starts the project using `compose up -d` of the
+/// configured container engine to run the project in the background.
 ///
 /// ### Arguments
 /// - args: &[String] - A list of arguments to pass to the command
@@ -127,25 +442,50 @@ pub fn new(args: &[String]) {
 /// ### Returns
 /// This function does not return anything
 pub fn up(args: &[String]) {
-    println!("Not implemented up yet");
-    match args.get(0) {
-        Some(arg0) => match arg0.as_str() {
-            // print the help message for the new command
-            "help" | "-h" | "--help" => helpers::help::help_not_implemented_yet(),
-            // if the argument is not recognized, print the help message for the new command
-            _ => helpers::help::help_not_implemented_yet(),
+    match args.first().map(|arg0| arg0.as_str()) {
+        // print the help message for the up command
+        Some("help" | "-h" | "--help") => helpers::help::up_help::print_help(),
+        // otherwise parse the options and start the project
+        _ => match up::parse_args(args) {
+            Ok(options) => up::up(options),
+            Err(error) => {
+                eprintln!("{}", error);
+                helpers::help::up_help::print_help();
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+/// ## version()
+/// This function prints the project version, or sets or bumps it in the
+/// project files and optionally the service manifests and image tags.
+///
+/// ### Arguments
+/// - args: &[String] - A list of arguments to pass to the command
+///
+/// ### Returns
+/// This function does not return anything
+pub fn version(args: &[String]) {
+    match args.first().map(|arg0| arg0.as_str()) {
+        // print the help message for the version command
+        Some("help" | "-h" | "--help") => helpers::help::version_help::print_help(),
+        // otherwise parse the options and run the version action
+        _ => match version::parse_args(args) {
+            Ok(options) => version::version(options),
+            Err(error) => {
+                eprintln!("{}", error);
+                helpers::help::version_help::print_help();
+                std::process::exit(1);
+            }
         },
-        None => {
-            // if no arguments are passed, print the help message for the new command
-            helpers::help::help_not_implemented_yet();
-        }
     }
 }
 
 /// ## watch()
-/// This function brings up the project and watches for changes in
-/// the application code and restarts the project when changes are
-/// detected using `docker compose up -d` and `docker compose down`.
+/// This function starts the project using `compose watch` of the
+/// configured container engine and syncs or rebuilds the services when
+/// their files change.
 ///
 /// ### Arguments
 /// - args: &[String] - A list of arguments to pass to the command
@@ -153,18 +493,18 @@ pub fn up(args: &[String]) {
 /// ### Returns
 /// This function does not return anything
 pub fn watch(args: &[String]) {
-    println!("Not implemented up yet");
-    match args.get(0) {
-        Some(arg0) => match arg0.as_str() {
-            // print the help message for the new command
-            "help" | "-h" | "--help" => helpers::help::help_not_implemented_yet(),
-            // if the argument is not recognized, print the help message for the new command
-            _ => helpers::help::help_not_implemented_yet(),
+    match args.first().map(|arg0| arg0.as_str()) {
+        // print the help message for the watch command
+        Some("help" | "-h" | "--help") => helpers::help::watch_help::print_help(),
+        // otherwise parse the options and watch the project
+        _ => match watch::parse_args(args) {
+            Ok(options) => watch::watch(options),
+            Err(error) => {
+                eprintln!("{}", error);
+                helpers::help::watch_help::print_help();
+                std::process::exit(1);
+            }
         },
-        None => {
-            // if no arguments are passed, print the help message for the new command
-            helpers::help::help_not_implemented_yet();
-        }
     }
 }
 
@@ -177,17 +517,17 @@ pub fn watch(args: &[String]) {
 // ### Returns
 // This function does not return anything
 pub fn status(args: &[String]) {
-    println!("Not implemented up yet");
-    match args.get(0) {
-        Some(arg0) => match arg0.as_str() {
-            // print the help message for the new command
-            "help" | "-h" | "--help" => helpers::help::help_not_implemented_yet(),
-            // if the argument is not recognized, print the help message for the new command
-            _ => helpers::help::help_not_implemented_yet(),
+    match args.first().map(|arg0| arg0.as_str()) {
+        // print the help message for the status command
+        Some("help" | "-h" | "--help") => helpers::help::status_help::print_help(),
+        // otherwise parse the options and print the status
+        _ => match status::parse_args(args) {
+            Ok(env) => status::status(env),
+            Err(error) => {
+                eprintln!("{}", error);
+                helpers::help::status_help::print_help();
+                std::process::exit(1);
+            }
         },
-        None => {
-            // if no arguments are passed, print the help message for the new command
-            helpers::help::help_not_implemented_yet();
-        }
     }
 }
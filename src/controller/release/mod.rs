@@ -0,0 +1,358 @@
+// Module: controller::release
+use crate::controller::changelog::{collect_changes, render_markdown, ServiceChanges};
+use crate::controller::version::{
+    print_changes, project_version_changes, write_changes, FileChange,
+};
+use crate::helpers::conventional_commits::ConventionalCommit;
+use crate::helpers::filesystem::path::append_path;
+use crate::helpers::git::Git;
+use crate::CONFIG;
+use semver::{BuildMetadata, Prerelease, Version};
+use std::path::PathBuf;
+
+/// The default changelog file in the project path
+const CHANGELOG_FILE: &str = "CHANGELOG.md";
+
+/// How the project version is bumped for a release
+#[derive(Debug, PartialEq)]
+pub enum Bump {
+    Major,
+    Minor,
+    Patch,
+    Version(Version), // an explicit version
+}
+
+/// The options accepted by `tools release`
+#[derive(Debug, PartialEq)]
+pub struct ReleaseOptions {
+    pub bump: Option<Bump>,  // None detects the bump from the conventional commits
+    pub pre: Option<String>, // the prerelease identifier of the new version, e.g. rc.1
+    pub from: Option<String>, // the ref of the previous release, defaults to its tag
+    pub dry_run: bool,       // only print what would be done
+    pub tag_services: bool,  // also tag every service that changed
+    pub changelog: String,   // the changelog file relative to the project path
+}
+
+// ## parse_args(args: &[String]) -> Result<ReleaseOptions, String>
+// This function parses the arguments of `tools release`
+//
+// ### arguments:
+// - args: &[String] - the arguments after `release`
+//
+// ### returns:
+// - Result<ReleaseOptions, String> - the parsed options or a message describing the error
+pub fn parse_args(args: &[String]) -> Result<ReleaseOptions, String> {
+    let mut options = ReleaseOptions {
+        bump: None,
+        pre: None,
+        from: None,
+        dry_run: false,
+        tag_services: false,
+        changelog: CHANGELOG_FILE.to_string(),
+    };
+
+    let mut index = 0;
+    while index < args.len() {
+        match args[index].as_str() {
+            option @ ("--pre" | "--from" | "--changelog") => {
+                index += 1;
+                let Some(value) = args.get(index).cloned() else {
+                    return Err(format!("{} expects a value", option));
+                };
+                match option {
+                    "--pre" => {
+                        Prerelease::new(&value)
+                            .map_err(|_| format!("Invalid prerelease identifier: {}", value))?;
+                        options.pre = Some(value);
+                    }
+                    "--from" => options.from = Some(value),
+                    _ => options.changelog = value,
+                }
+            }
+            "-n" | "--dry-run" => options.dry_run = true,
+            "--tag-services" => options.tag_services = true,
+            unknown if unknown.starts_with('-') => {
+                return Err(format!("Unknown option: {}", unknown))
+            }
+            value if options.bump.is_some() => {
+                return Err(format!("Unexpected argument: {}", value))
+            }
+            "major" => options.bump = Some(Bump::Major),
+            "minor" => options.bump = Some(Bump::Minor),
+            "patch" => options.bump = Some(Bump::Patch),
+            value => {
+                let version = Version::parse(value.trim_start_matches('v')).map_err(|_| {
+                    format!("Expected major, minor, patch or a version, got {}", value)
+                })?;
+                options.bump = Some(Bump::Version(version));
+            }
+        }
+        index += 1;
+    }
+
+    Ok(options)
+}
+
+// ## next_version(current: &Version, bump: &Bump, pre: Option<&str>) -> Result<Version, String>
+// This function computes the version of a release. Releasing a prerelease
+// drops its prerelease identifier if it already is at the bumped level,
+// e.g. a patch release of 0.2.0-alpha is 0.2.0
+//
+// ### arguments:
+// - current: &Version - the current project version
+// - bump: &Bump - how the version is bumped
+// - pre: Option<&str> - the prerelease identifier of the new version
+//
+// ### returns:
+// - Result<Version, String> - the new version or a message if it isn't newer
+pub fn next_version(current: &Version, bump: &Bump, pre: Option<&str>) -> Result<Version, String> {
+    let released = current.pre.is_empty();
+    let mut version = Version::new(current.major, current.minor, current.patch);
+
+    match bump {
+        Bump::Major if released || current.minor != 0 || current.patch != 0 => {
+            version = Version::new(current.major + 1, 0, 0)
+        }
+        Bump::Minor if released || current.patch != 0 => {
+            version = Version::new(current.major, current.minor + 1, 0)
+        }
+        Bump::Patch if released => version.patch += 1,
+        Bump::Version(explicit) => version = explicit.clone(),
+        _ => {}
+    }
+
+    if let Some(pre) = pre {
+        version.pre = Prerelease::new(pre).map_err(|error| error.to_string())?;
+    }
+    version.build = BuildMetadata::EMPTY;
+
+    if version <= *current {
+        return Err(format!(
+            "The new version {} is not newer than {}",
+            version, current
+        ));
+    }
+
+    Ok(version)
+}
+
+// ## detect_bump(changes: &[ServiceChanges]) -> Bump
+// This function detects the bump from the conventional commits of the
+// project and every service, major for breaking changes, minor for
+// features and patch otherwise
+pub fn detect_bump(changes: &[ServiceChanges]) -> Bump {
+    let commits = changes
+        .iter()
+        .flat_map(|service| &service.commits)
+        .filter_map(|commit| ConventionalCommit::parse(&commit.subject, &commit.body))
+        .collect::<Vec<ConventionalCommit>>();
+
+    if commits.iter().any(|commit| commit.breaking) {
+        Bump::Major
+    } else if commits.iter().any(|commit| commit.commit_type == "feat") {
+        Bump::Minor
+    } else {
+        Bump::Patch
+    }
+}
+
+// ## release(options: ReleaseOptions)
+// This function releases the project: it bumps the version in config.yaml
+// and project.yaml, prepends the changelog, commits the changes and tags
+// the superproject, and every changed service with `--tag-services`
+//
+// ### arguments:
+// - options: ReleaseOptions - the parsed options of the command
+//
+// ### returns:
+// - nothing
+pub fn release(options: ReleaseOptions) {
+    let project = Git::project();
+
+    match project.status() {
+        Ok(status) if status.is_clean() => {}
+        Ok(_) if options.dry_run => {
+            eprintln!("Warning: the project has uncommitted changes")
+        }
+        Ok(_) => exit_with("The project has uncommitted changes, commit or stash them first"),
+        Err(error) => exit_with(&error.to_string()),
+    }
+
+    let current = CONFIG.get_version();
+    let tags = project
+        .tags()
+        .unwrap_or_else(|error| exit_with(&error.to_string()));
+
+    // the previous release is the tag of the current version, or the newest tag
+    let current_tag = format!("v{}", current);
+    let from = match &options.from {
+        Some(from) => from.clone(),
+        None if tags.contains(&current_tag) => current_tag,
+        None => match tags.first() {
+            Some(tag) => tag.clone(),
+            None => exit_with("There is no previous release tag, pass --from <ref>"),
+        },
+    };
+
+    let changes =
+        collect_changes(&from, "HEAD").unwrap_or_else(|error| exit_with(&error.to_string()));
+
+    let bump = options.bump.unwrap_or_else(|| detect_bump(&changes));
+    let version = next_version(&current, &bump, options.pre.as_deref())
+        .unwrap_or_else(|error| exit_with(&error));
+
+    let tag = format!("v{}", version);
+    if tags.contains(&tag) {
+        exit_with(&format!("The tag {} already exists", tag));
+    }
+
+    let changelog = render_markdown(&tag, &changes);
+    let changelog_path = append_path(&PathBuf::from(&CONFIG.project_path), &options.changelog);
+
+    // the version files, and the changelog the new release is prepended to
+    let mut files = project_version_changes(&version).unwrap_or_else(|error| {
+        exit_with(&format!("Couldn't update the project version: {}", error))
+    });
+    let previous = std::fs::read_to_string(&changelog_path).unwrap_or_default();
+    files.push(FileChange {
+        path: changelog_path,
+        new: if previous.is_empty() {
+            changelog
+        } else {
+            format!("{}\n{}", changelog, previous)
+        },
+        old: previous,
+    });
+
+    // the services whose pointer moved and that still exist
+    let service_tag = format!("{}/{}", CONFIG.project_name, tag);
+    let services = changes
+        .iter()
+        .skip(1)
+        .filter_map(|service| service.to.as_ref().map(|to| (&service.name, to)))
+        .collect::<Vec<(&String, &String)>>();
+
+    println!("Releasing {} -> {} (from {})", current, version, from);
+
+    if options.dry_run {
+        print_changes(&files);
+        println!(
+            "Would commit \"chore(release): {}\" and tag it {}",
+            tag, tag
+        );
+        if options.tag_services {
+            for (service, commit) in &services {
+                println!(
+                    "Would tag {} at {} as {}",
+                    service,
+                    commit.chars().take(7).collect::<String>(),
+                    service_tag
+                );
+            }
+        }
+        return;
+    }
+
+    if !write_changes(&files) {
+        std::process::exit(1);
+    }
+
+    let files = files
+        .iter()
+        .map(|file| file.path.to_string_lossy().to_string())
+        .collect::<Vec<String>>();
+    let files = files
+        .iter()
+        .map(|file| file.as_str())
+        .collect::<Vec<&str>>();
+
+    let result = project
+        .add(&files)
+        .and_then(|_| project.commit(&format!("chore(release): {}", tag)))
+        .and_then(|_| project.create_tag(&tag, &format!("Release {}", tag), None));
+    match result {
+        Ok(_) => println!("Committed and tagged {}", tag),
+        Err(error) => exit_with(&error.to_string()),
+    }
+
+    let mut success = true;
+    if options.tag_services {
+        for (service, commit) in services {
+            let repository = Git::new(format!("{}/{}", CONFIG.project_path, service));
+            let message = format!("Release {} of {}", tag, CONFIG.project_name);
+
+            match repository.create_tag(&service_tag, &message, Some(commit)) {
+                Ok(_) => println!("{} - tagged {}", service, service_tag),
+                Err(error) => {
+                    eprintln!("{} - {}", service, error);
+                    success = false;
+                }
+            }
+        }
+    }
+
+    println!("Push the release with `git push --follow-tags`");
+
+    if !success {
+        std::process::exit(1);
+    }
+}
+
+// ## exit_with(message: &str) -> !
+// This function prints an error and exits
+fn exit_with(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(1);
+}
+
+#[test]
+fn test_release_parse_args() {
+    let args = ["minor", "--pre", "rc.1", "--dry-run"].map(String::from);
+
+    assert_eq!(
+        parse_args(&args),
+        Ok(ReleaseOptions {
+            bump: Some(Bump::Minor),
+            pre: Some("rc.1".to_string()),
+            from: None,
+            dry_run: true,
+            tag_services: false,
+            changelog: CHANGELOG_FILE.to_string(),
+        })
+    );
+    assert_eq!(
+        parse_args(&["v1.2.3".to_string()]).unwrap().bump,
+        Some(Bump::Version(Version::new(1, 2, 3)))
+    );
+    assert_eq!(parse_args(&[]).unwrap().bump, None);
+    assert!(parse_args(&["huge".to_string()]).is_err());
+    assert!(parse_args(&["major".to_string(), "minor".to_string()]).is_err());
+}
+
+#[test]
+fn test_release_next_version() {
+    let version = |version: &str| Version::parse(version).unwrap();
+
+    assert_eq!(
+        next_version(&version("1.2.3"), &Bump::Major, None),
+        Ok(version("2.0.0"))
+    );
+    assert_eq!(
+        next_version(&version("1.2.3"), &Bump::Minor, None),
+        Ok(version("1.3.0"))
+    );
+    assert_eq!(
+        next_version(&version("1.2.3+build.5"), &Bump::Patch, Some("rc.1")),
+        Ok(version("1.2.4-rc.1"))
+    );
+    // releasing a prerelease at its own level drops the identifier
+    assert_eq!(
+        next_version(&version("0.2.0-alpha"), &Bump::Minor, None),
+        Ok(version("0.2.0"))
+    );
+    assert_eq!(
+        next_version(&version("0.2.1-alpha"), &Bump::Minor, None),
+        Ok(version("0.3.0"))
+    );
+    assert!(next_version(&version("1.2.3"), &Bump::Version(version("1.2.3")), None).is_err());
+}
@@ -0,0 +1,80 @@
+// Module: controller::status
+use crate::helpers::config::environment::{
+    get_environment, parse_environment_option, select_environment,
+};
+use crate::helpers::container_engine::get_engine;
+use crossterm::style::Stylize;
+
+// ## parse_args(args: &[String]) -> Result<Option<String>, String>
+// This function parses the arguments of `tools status`
+//
+// ### arguments:
+// - args: &[String] - the arguments after `status`
+//
+// ### returns:
+// - Result<Option<String>, String> - the environment given with `--env`, or a message describing the error
+pub fn parse_args(args: &[String]) -> Result<Option<String>, String> {
+    match parse_environment_option(args)? {
+        (env, rest) if rest.is_empty() => Ok(env),
+        (_, rest) => Err(format!("Unexpected argument: {}", rest[0])),
+    }
+}
+
+// ## status(env: Option<String>)
+// This function prints the state of every container of the project
+//
+// ### arguments:
+// - env: Option<String> - the environment to show, TOOLS_ENV if None
+//
+// ### returns:
+// - nothing, exits with 1 if compose failed
+pub fn status(env: Option<String>) {
+    select_environment(env.as_deref());
+    let engine = get_engine();
+
+    if let Some((name, _)) = get_environment() {
+        println!("Environment: {}", name);
+    }
+
+    let containers = match engine.ps() {
+        Ok(containers) => containers,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    };
+
+    if containers.is_empty() {
+        println!("No containers, start the project with `tools up`");
+        return;
+    }
+
+    let width = containers
+        .iter()
+        .map(|container| container.service.len())
+        .max()
+        .unwrap_or(0)
+        .max("Service".len());
+
+    println!(
+        "{:width$}  {:10}  Status",
+        "Service",
+        "State",
+        width = width
+    );
+    for container in containers {
+        let state = format!("{:10}", container.state);
+        let state = if container.state == "running" {
+            state.green()
+        } else {
+            state.red()
+        };
+        println!(
+            "{:width$}  {}  {}",
+            container.service,
+            state,
+            container.status,
+            width = width
+        );
+    }
+}
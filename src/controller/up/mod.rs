@@ -0,0 +1,81 @@
+// Module: controller::up
+use crate::helpers::config::environment::{parse_environment_option, select_environment};
+use crate::helpers::container_engine::get_engine;
+
+/// The options accepted by `tools up`
+#[derive(Debug, PartialEq)]
+pub struct UpOptions {
+    pub services: Vec<String>, // only start these services, empty means all services
+    pub build: bool,           // build the images before starting the containers
+    pub env: Option<String>,   // the environment to start, TOOLS_ENV if None
+}
+
+// ## parse_args(args: &[String]) -> Result<UpOptions, String>
+// This function parses the arguments of `tools up`
+//
+// ### arguments:
+// - args: &[String] - the arguments after `up`
+//
+// ### returns:
+// - Result<UpOptions, String> - the parsed options or a message describing the error
+pub fn parse_args(args: &[String]) -> Result<UpOptions, String> {
+    let (env, args) = parse_environment_option(args)?;
+    let mut services = Vec::new();
+    let mut build = false;
+
+    for arg in &args {
+        match arg.as_str() {
+            "--build" => build = true,
+            unknown if unknown.starts_with('-') => {
+                return Err(format!("Unknown option: {}", unknown))
+            }
+            service => services.push(service.to_string()),
+        }
+    }
+
+    Ok(UpOptions {
+        services,
+        build,
+        env,
+    })
+}
+
+// ## up(options: UpOptions)
+// This function starts the project in the background with the configured
+// container engine
+//
+// ### arguments:
+// - options: UpOptions - the parsed options of the command
+//
+// ### returns:
+// - nothing, exits with 1 if compose failed
+pub fn up(options: UpOptions) {
+    select_environment(options.env.as_deref());
+    let engine = get_engine();
+
+    if let Err(error) = engine.up(&options.services, options.build) {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    }
+}
+
+#[test]
+fn test_up_parse_args() {
+    assert_eq!(
+        parse_args(&["--build".to_string(), "api".to_string()]),
+        Ok(UpOptions {
+            services: vec!["api".to_string()],
+            build: true,
+            env: None,
+        })
+    );
+    assert_eq!(
+        parse_args(&["--env".to_string(), "ci".to_string()]),
+        Ok(UpOptions {
+            services: Vec::new(),
+            build: false,
+            env: Some("ci".to_string()),
+        })
+    );
+    assert!(parse_args(&["--wait".to_string()]).is_err());
+}
@@ -0,0 +1,571 @@
+// Module: controller::version
+use crate::controller::release::{next_version, Bump};
+use crate::helpers::config::app_config::get_config;
+use crate::helpers::config::project::{get_path_to_project_file, get_project};
+use crate::helpers::diff::unified_diff;
+use crate::helpers::docker::docker_compose::file::get_path_to_docker_compose;
+use crate::helpers::filesystem::path::append_path;
+use crate::helpers::services::get_service_paths;
+use crate::helpers::version::{docker_tag, get_version_info};
+use crate::helpers::yaml_edit::patch_yaml;
+use crate::CONFIG;
+use crossterm::style::Stylize;
+use semver::Version;
+use serde_yaml::Value;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+/// The service manifests that carry a version and the TOML sections the
+/// version is read from, package.json has no sections
+const MANIFESTS: [(&str, &[&str]); 3] = [
+    ("Cargo.toml", &["package", "workspace.package"]),
+    ("pyproject.toml", &["project", "tool.poetry"]),
+    ("package.json", &[]),
+];
+
+/// The version operations accepted by `tools version`
+#[derive(Debug, PartialEq)]
+pub enum VersionAction {
+    Show,
+    Env(bool), // print the version as environment variables, prefixed with `export` if true
+    Set(Version),
+    Bump(Bump),
+}
+
+/// The options accepted by `tools version`
+#[derive(Debug, PartialEq)]
+pub struct VersionOptions {
+    pub action: VersionAction,
+    pub pre: Option<String>, // the prerelease identifier of a bumped version
+    pub manifests: bool,     // also rewrite the versions of the service manifests
+    pub images: bool, // also rewrite the image tags of the services built by the compose file
+    pub dry_run: bool, // only print the diff of every file
+}
+
+/// A file that is changed by a version update
+#[derive(Debug, PartialEq)]
+pub struct FileChange {
+    pub path: PathBuf,
+    pub old: String,
+    pub new: String,
+}
+
+// ## parse_args(args: &[String]) -> Result<VersionOptions, String>
+// This function parses the arguments of `tools version`
+//
+// ### arguments:
+// - args: &[String] - the arguments after `version`
+//
+// ### returns:
+// - Result<VersionOptions, String> - the parsed options or a message describing the error
+pub fn parse_args(args: &[String]) -> Result<VersionOptions, String> {
+    let mut pre = None;
+    let mut manifests = false;
+    let mut images = false;
+    let mut dry_run = false;
+    let mut export = false;
+    let mut positional = Vec::new();
+
+    let mut index = 0;
+    while index < args.len() {
+        match args[index].as_str() {
+            "--pre" => {
+                index += 1;
+                match args.get(index) {
+                    Some(value) => pre = Some(value.clone()),
+                    None => return Err("--pre expects a prerelease identifier".to_string()),
+                }
+            }
+            "--manifests" => manifests = true,
+            "--export" => export = true,
+            "--images" => images = true,
+            "-n" | "--dry-run" => dry_run = true,
+            unknown if unknown.starts_with('-') => {
+                return Err(format!("Unknown option: {}", unknown))
+            }
+            value => positional.push(value.to_string()),
+        }
+        index += 1;
+    }
+
+    let mut positional = positional.into_iter();
+    let action = match (positional.next().as_deref(), positional.next()) {
+        (None, _) => VersionAction::Show,
+        (Some("env"), None) => VersionAction::Env(export),
+        (Some("env"), Some(extra)) => return Err(format!("Unexpected argument: {}", extra)),
+        (Some("set"), Some(version)) => VersionAction::Set(
+            Version::parse(version.trim_start_matches('v'))
+                .map_err(|error| format!("Invalid version {}: {}", version, error))?,
+        ),
+        (Some("bump"), Some(level)) => VersionAction::Bump(match level.as_str() {
+            "major" => Bump::Major,
+            "minor" => Bump::Minor,
+            "patch" => Bump::Patch,
+            level => return Err(format!("Expected major, minor or patch, got {}", level)),
+        }),
+        (Some("set"), None) => return Err("`tools version set` expects a version".to_string()),
+        (Some("bump"), None) => {
+            return Err("`tools version bump` expects major, minor or patch".to_string())
+        }
+        (Some(action), _) => return Err(format!("Unknown version action: {}", action)),
+    };
+
+    if let Some(extra) = positional.next() {
+        return Err(format!("Unexpected argument: {}", extra));
+    }
+
+    if pre.is_some() && !matches!(action, VersionAction::Bump(_)) {
+        return Err("--pre can only be used with `tools version bump`".to_string());
+    }
+
+    Ok(VersionOptions {
+        action,
+        pre,
+        manifests,
+        images,
+        dry_run,
+    })
+}
+
+// ## version(options: VersionOptions)
+// This function prints the project version, or sets or bumps it in
+// config.yaml and project.yaml, and optionally in the service manifests
+// and the image tags of the compose file
+//
+// ### arguments:
+// - options: VersionOptions - the parsed options of the command
+//
+// ### returns:
+// - nothing
+pub fn version(options: VersionOptions) {
+    let current = CONFIG.get_version();
+
+    let version = match &options.action {
+        VersionAction::Show => {
+            let info = get_version_info();
+            if info.version == info.configured {
+                println!("{}", info.version);
+            } else {
+                println!("{} (configured {})", info.version, info.configured);
+            }
+            return;
+        }
+        VersionAction::Env(export) => {
+            for (key, value) in get_version_info().to_env() {
+                if *export {
+                    println!("export {}={}", key, value);
+                } else {
+                    println!("{}={}", key, value);
+                }
+            }
+            return;
+        }
+        VersionAction::Set(version) => version.clone(),
+        VersionAction::Bump(bump) => match next_version(&current, bump, options.pre.as_deref()) {
+            Ok(version) => version,
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            }
+        },
+    };
+
+    let mut changes = match project_version_changes(&version) {
+        Ok(changes) => changes,
+        Err(error) => {
+            eprintln!("Couldn't update the project version: {}", error);
+            std::process::exit(1);
+        }
+    };
+    if options.manifests {
+        changes.append(&mut manifest_changes(&version));
+    }
+    if options.images {
+        changes.append(&mut image_changes(&version));
+    }
+
+    print_changes(&changes);
+
+    if options.dry_run {
+        println!("Would set the project version {} -> {}", current, version);
+        return;
+    }
+
+    if !write_changes(&changes) {
+        std::process::exit(1);
+    }
+    println!(
+        "Set the project version {} -> {} in {} files",
+        current,
+        version,
+        changes.len()
+    );
+}
+
+// ## project_version_changes(version: &Version) -> Result<Vec<FileChange>, Box<dyn Error>>
+// This function sets the project version in config.yaml and, if the
+// project has one, project.yaml
+//
+// ### arguments:
+// - version: &Version - the new project version
+//
+// ### returns:
+// - Result<Vec<FileChange>, Box<dyn Error>> - the changed files, not written yet
+pub fn project_version_changes(version: &Version) -> Result<Vec<FileChange>, Box<dyn Error>> {
+    let mut changes = Vec::new();
+
+    // only the version line is rewritten, the comments of the files stay
+    let config_path = append_path(&std::env::current_dir()?, "config.yaml");
+    let mut config = get_config();
+    config.update_major_version(version.major);
+    config.update_minor_version(version.minor);
+    config.update_patch_version(version.patch);
+    config.update_prerelease_version(version.pre.as_str())?;
+    config.update_build_version(version.build.as_str())?;
+    let old = std::fs::read_to_string(&config_path)?;
+    changes.push(FileChange {
+        new: rewrite_yaml_version(&old, "project_version", &config.project_version)?,
+        old,
+        path: config_path,
+    });
+
+    if let Some(mut project) = get_project() {
+        let project_path = get_path_to_project_file();
+        project.update_major_version(version.major);
+        project.update_minor_version(version.minor);
+        project.update_patch_version(version.patch);
+        project.update_prerelease_version(version.pre.as_str())?;
+        project.update_build_version(version.build.as_str())?;
+        let old = std::fs::read_to_string(&project_path)?;
+        changes.push(FileChange {
+            new: rewrite_yaml_version(&old, "version", &project.version)?,
+            old,
+            path: project_path,
+        });
+    }
+
+    Ok(changes)
+}
+
+// ## manifest_changes(version: &Version) -> Vec<FileChange>
+// This function sets the version in the recognized manifests at the root
+// of every service
+fn manifest_changes(version: &Version) -> Vec<FileChange> {
+    let mut changes = Vec::new();
+
+    for service in get_service_paths().into_iter().filter(|service| service.2) {
+        for (manifest, sections) in MANIFESTS {
+            let path = append_path(Path::new(&service.0), manifest);
+            let Ok(old) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+
+            let new = if sections.is_empty() {
+                rewrite_json_version(&old, &version.to_string())
+            } else {
+                rewrite_toml_version(&old, sections, &version.to_string())
+            };
+
+            match new {
+                Some(new) => changes.push(FileChange { path, old, new }),
+                None => eprintln!(
+                    "Warning: {}/{} has no version to update",
+                    service.1, manifest
+                ),
+            }
+        }
+    }
+
+    changes
+}
+
+// ## image_changes(version: &Version) -> Vec<FileChange>
+// This function sets the tag of every image the compose file builds
+fn image_changes(version: &Version) -> Vec<FileChange> {
+    let path = get_path_to_docker_compose();
+    let old = match std::fs::read_to_string(&path) {
+        Ok(old) => old,
+        Err(error) => {
+            eprintln!("Couldn't read {}: {}", path.display(), error);
+            std::process::exit(1);
+        }
+    };
+
+    let compose: serde_yaml::Value = match serde_yaml::from_str(&old) {
+        Ok(compose) => compose,
+        Err(error) => {
+            eprintln!("Couldn't parse {}: {}", path.display(), error);
+            std::process::exit(1);
+        }
+    };
+
+    // only the images of services that are built, not the ones that are pulled
+    let images = compose
+        .get("services")
+        .and_then(|services| services.as_mapping())
+        .into_iter()
+        .flat_map(|services| services.values())
+        .filter(|service| service.get("build").is_some())
+        .filter_map(|service| service.get("image").and_then(|image| image.as_str()))
+        .map(|image| image.to_string())
+        .collect::<Vec<String>>();
+
+    let new = rewrite_image_tags(&old, &images, &docker_tag(version));
+    if new == old {
+        return Vec::new();
+    }
+
+    vec![FileChange { path, old, new }]
+}
+
+// ## print_changes(changes: &[FileChange])
+// This function prints the diff of every changed file
+pub fn print_changes(changes: &[FileChange]) {
+    for change in changes {
+        let path = change
+            .path
+            .strip_prefix(&CONFIG.project_path)
+            .unwrap_or(&change.path)
+            .display()
+            .to_string();
+
+        for line in unified_diff(&path, &change.old, &change.new).lines() {
+            if line.starts_with("+++") || line.starts_with("---") {
+                println!("{}", line.bold());
+            } else if line.starts_with('+') {
+                println!("{}", line.green());
+            } else if line.starts_with('-') {
+                println!("{}", line.red());
+            } else if line.starts_with("@@") {
+                println!("{}", line.cyan());
+            } else {
+                println!("{}", line);
+            }
+        }
+    }
+}
+
+// ## write_changes(changes: &[FileChange]) -> bool
+// This function writes every changed file
+//
+// ### returns:
+// - bool - true if every file was written, false if not
+pub fn write_changes(changes: &[FileChange]) -> bool {
+    let mut success = true;
+
+    for change in changes.iter().filter(|change| change.old != change.new) {
+        if let Err(error) = std::fs::write(&change.path, &change.new) {
+            eprintln!("Couldn't write {}: {}", change.path.display(), error);
+            success = false;
+        }
+    }
+
+    success
+}
+
+// ## rewrite_toml_version(content: &str, sections: &[&str], version: &str) -> Option<String>
+// This function replaces the first `version = "..."` in one of the sections
+// of a TOML file, keeping the rest of the file as it is
+pub fn rewrite_toml_version(content: &str, sections: &[&str], version: &str) -> Option<String> {
+    let mut section = String::new();
+    let mut replaced = false;
+    let mut rewritten = String::new();
+
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with('[') {
+            section = trimmed.trim_matches(['[', ']']).trim().to_string();
+        } else if !replaced
+            && sections.contains(&section.as_str())
+            && trimmed
+                .strip_prefix("version")
+                .is_some_and(|rest| rest.trim_start().starts_with('='))
+        {
+            if let Some(line) = replace_quoted_value(line, version) {
+                rewritten.push_str(&line);
+                replaced = true;
+                continue;
+            }
+        }
+
+        rewritten.push_str(line);
+    }
+
+    replaced.then_some(rewritten)
+}
+
+// ## rewrite_json_version(content: &str, version: &str) -> Option<String>
+// This function replaces the top level `"version": "..."` of a
+// package.json, which is the version of the package itself
+pub fn rewrite_json_version(content: &str, version: &str) -> Option<String> {
+    let mut replaced = false;
+    let mut rewritten = String::new();
+    let mut depth = 0;
+
+    for line in content.split_inclusive('\n') {
+        let is_version = depth == 1
+            && line
+                .trim_start()
+                .strip_prefix("\"version\"")
+                .is_some_and(|rest| rest.trim_start().starts_with(':'));
+        depth = json_depth(line, depth);
+
+        if !replaced && is_version {
+            if let Some(line) = replace_quoted_value(line, version) {
+                rewritten.push_str(&line);
+                replaced = true;
+                continue;
+            }
+        }
+
+        rewritten.push_str(line);
+    }
+
+    replaced.then_some(rewritten)
+}
+
+// ## json_depth(line: &str, depth: usize) -> usize
+// This function returns the nesting depth of JSON after a line, the
+// brackets in strings are skipped
+fn json_depth(line: &str, mut depth: usize) -> usize {
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for c in line.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '{' | '[' if !in_string => depth += 1,
+            '}' | ']' if !in_string => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    depth
+}
+
+// ## rewrite_yaml_version(content: &str, key: &str, version: &str) -> Result<String, Box<dyn Error>>
+// This function sets a top level version key of a YAML file, keeping its
+// comments and formatting
+pub fn rewrite_yaml_version(
+    content: &str,
+    key: &str,
+    version: &str,
+) -> Result<String, Box<dyn Error>> {
+    let old: Value = serde_yaml::from_str(content)?;
+    let mut new = old.clone();
+    match new.as_mapping_mut() {
+        Some(mapping) => mapping.insert(Value::from(key), Value::from(version)),
+        None => return Err(format!("expected a mapping with a {} key", key).into()),
+    };
+
+    Ok(patch_yaml(content, &old, &new))
+}
+
+// ## replace_quoted_value(line: &str, value: &str) -> Option<String>
+// This function replaces the quoted value after the `=` or `:` of a line
+fn replace_quoted_value(line: &str, value: &str) -> Option<String> {
+    let separator = line.find(['=', ':'])?;
+    let start = separator + line[separator..].find('"')? + 1;
+    let end = start + line[start..].find('"')?;
+    Some(format!("{}{}{}", &line[..start], value, &line[end..]))
+}
+
+// ## rewrite_image_tags(content: &str, images: &[String], tag: &str) -> String
+// This function sets the tag of the `image:` lines of a compose file that
+// use one of the images, images pinned by digest are left as they are
+pub fn rewrite_image_tags(content: &str, images: &[String], tag: &str) -> String {
+    let mut rewritten = String::new();
+
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let value = trimmed
+            .strip_prefix("image:")
+            .map(|value| value.trim().trim_matches(['"', '\'']));
+
+        match value {
+            Some(image) if images.iter().any(|known| known == image) && !image.contains('@') => {
+                // the tag follows the last `:` after the last `/`, a registry may have a port
+                let name_start = image.rfind('/').map_or(0, |slash| slash + 1);
+                let repository = match image[name_start..].rfind(':') {
+                    Some(colon) => &image[..name_start + colon],
+                    None => image,
+                };
+                let indent = &line[..line.len() - trimmed.len()];
+                let newline = if line.ends_with('\n') { "\n" } else { "" };
+                rewritten.push_str(&format!(
+                    "{}image: {}:{}{}",
+                    indent, repository, tag, newline
+                ));
+            }
+            _ => rewritten.push_str(line),
+        }
+    }
+
+    rewritten
+}
+
+#[test]
+fn test_version_parse_args() {
+    let args = ["bump", "minor", "--pre", "rc.1", "--manifests", "-n"].map(String::from);
+
+    assert_eq!(
+        parse_args(&args),
+        Ok(VersionOptions {
+            action: VersionAction::Bump(Bump::Minor),
+            pre: Some("rc.1".to_string()),
+            manifests: true,
+            images: false,
+            dry_run: true,
+        })
+    );
+    assert_eq!(parse_args(&[]).unwrap().action, VersionAction::Show);
+    assert!(parse_args(&["set".to_string(), "one".to_string()]).is_err());
+    assert!(parse_args(&[
+        "set".to_string(),
+        "1.0.0".to_string(),
+        "--pre".to_string(),
+        "rc".to_string()
+    ])
+    .is_err());
+}
+
+#[test]
+fn test_version_rewrite_manifests() {
+    let cargo = "[package]\nname = \"web\"\nversion = \"0.1.0\" # the version\n\n[dependencies]\nserde = { version = \"1.0\" }\n";
+    assert_eq!(
+        rewrite_toml_version(cargo, &["package"], "0.2.0").unwrap(),
+        "[package]\nname = \"web\"\nversion = \"0.2.0\" # the version\n\n[dependencies]\nserde = { version = \"1.0\" }\n"
+    );
+    assert_eq!(
+        rewrite_toml_version("[dependencies]\nversion = \"1\"\n", &["package"], "0.2.0"),
+        None
+    );
+
+    let package = "{\n  \"name\": \"web\",\n  \"version\": \"0.1.0\",\n  \"dependencies\": {}\n}\n";
+    assert_eq!(
+        rewrite_json_version(package, "0.2.0").unwrap(),
+        "{\n  \"name\": \"web\",\n  \"version\": \"0.2.0\",\n  \"dependencies\": {}\n}\n"
+    );
+    // the version of a nested object isn't the version of the package
+    let package = "{\n  \"engines\": {\n    \"version\": \"18\"\n  },\n  \"name\": \"web {\",\n  \"version\": \"0.1.0\"\n}\n";
+    assert_eq!(
+        rewrite_json_version(package, "0.2.0").unwrap(),
+        "{\n  \"engines\": {\n    \"version\": \"18\"\n  },\n  \"name\": \"web {\",\n  \"version\": \"0.2.0\"\n}\n"
+    );
+
+    let config =
+        "---\n# the project\nproject_name: web\nproject_version: 0.1.0 # bumped by tools\n";
+    assert_eq!(
+        rewrite_yaml_version(config, "project_version", "0.2.0").unwrap(),
+        "---\n# the project\nproject_name: web\nproject_version: 0.2.0 # bumped by tools\n"
+    );
+
+    let compose = "services:\n  web:\n    image: registry:5000/web:latest\n  db:\n    image: \"postgres:15\"\n";
+    assert_eq!(
+        rewrite_image_tags(compose, &["registry:5000/web:latest".to_string()], "0.2.0-build"),
+        "services:\n  web:\n    image: registry:5000/web:0.2.0-build\n  db:\n    image: \"postgres:15\"\n"
+    );
+}
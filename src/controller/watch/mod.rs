@@ -0,0 +1,66 @@
+// Module: controller::watch
+use crate::helpers::config::environment::{parse_environment_option, select_environment};
+use crate::helpers::container_engine::get_engine;
+
+/// The options accepted by `tools watch`
+#[derive(Debug, PartialEq)]
+pub struct WatchOptions {
+    pub services: Vec<String>, // only watch these services, empty means all services
+    pub env: Option<String>,   // the environment to watch, TOOLS_ENV if None
+}
+
+// ## parse_args(args: &[String]) -> Result<WatchOptions, String>
+// This function parses the arguments of `tools watch`
+//
+// ### arguments:
+// - args: &[String] - the arguments after `watch`
+//
+// ### returns:
+// - Result<WatchOptions, String> - the parsed options or a message describing the error
+pub fn parse_args(args: &[String]) -> Result<WatchOptions, String> {
+    let (env, args) = parse_environment_option(args)?;
+    let mut services = Vec::new();
+
+    for arg in &args {
+        match arg.as_str() {
+            unknown if unknown.starts_with('-') => {
+                return Err(format!("Unknown option: {}", unknown))
+            }
+            service => services.push(service.to_string()),
+        }
+    }
+
+    Ok(WatchOptions { services, env })
+}
+
+// ## watch(options: WatchOptions)
+// This function starts the project and syncs or rebuilds the services
+// when their files change, with the `develop.watch` rules of the compose
+// file, until it is interrupted
+//
+// ### arguments:
+// - options: WatchOptions - the parsed options of the command
+//
+// ### returns:
+// - nothing, exits with 1 if compose failed or the engine can't watch
+pub fn watch(options: WatchOptions) {
+    select_environment(options.env.as_deref());
+    let engine = get_engine();
+
+    if let Err(error) = engine.watch(&options.services) {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    }
+}
+
+#[test]
+fn test_watch_parse_args() {
+    assert_eq!(
+        parse_args(&["--env".to_string(), "dev".to_string(), "api".to_string()]),
+        Ok(WatchOptions {
+            services: vec!["api".to_string()],
+            env: Some("dev".to_string()),
+        })
+    );
+    assert!(parse_args(&["--poll".to_string()]).is_err());
+}
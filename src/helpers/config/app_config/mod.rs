@@ -1,7 +1,7 @@
 use semver::{Prerelease, Version};
 
 use crate::helpers::filesystem::path::append_path;
-use crate::models::app_config::AppConfigFile;
+use crate::models::app_config::{AppConfigFile, ComposeFiles};
 use std::env::current_dir;
 use std::{fs::File, io::Write, path::Path};
 
@@ -28,14 +28,8 @@ pub fn get_config() -> AppConfigFile {
 
     // Check if the configuration file already exists
     if !config_path.exists() {
-        // If the file does not exist, create a default configuration
-        let config = AppConfigFile::default();
-
-        // Write the configuration to a YAML file
-        write_config(&config);
-
-        // Return the configuration
-        config
+        // If the file does not exist, create and write a default configuration
+        create_config()
     } else {
         // If the file exists, read the configuration from the file
         let raw_config_file =
@@ -77,18 +71,12 @@ pub fn write_config(config: &AppConfigFile) {
 
 /// Create a default configuration file.
 ///
-/// ## Arguments
-/// - `config_file: &str`: The path to the configuration file.
-///
 /// ## Returns
 /// - `AppConfigFile`: The default configuration.
-pub fn create_config(config_file: &str) -> AppConfigFile {
+pub fn create_config() -> AppConfigFile {
     // get current directory path
     let current_dir = current_dir().expect("Couldn't get current directory");
 
-    // get the path to the configuration file
-    let config_path = append_path(&current_dir, config_file);
-
     // If the file does not exist, create a default configuration
     let version = Version {
         major: 0,
@@ -100,10 +88,14 @@ pub fn create_config(config_file: &str) -> AppConfigFile {
     .to_string();
     let config = AppConfigFile {
         project_path: current_dir.to_str().unwrap().to_string(),
-        docker_compose: "docker-compose.yaml".to_string(),
+        docker_compose: ComposeFiles::File("docker-compose.yaml".to_string()),
         project_name: "project".to_string(),
         project_version: version,
         github_api_token: None,
+        version_mode: None,
+        hooks: None,
+        container_engine: None,
+        environments: None,
     };
 
     // Write the default configuration to a YAML file
@@ -117,6 +109,7 @@ pub fn create_config(config_file: &str) -> AppConfigFile {
 ///
 /// ## Returns
 /// - `bool`: `true` if the configuration file exists, `false` otherwise.
+#[allow(dead_code)]
 pub fn check_if_config_exists() -> bool {
     // get current directory path
     let current_dir = current_dir().expect("Couldn't get current directory");
@@ -125,9 +118,5 @@ pub fn check_if_config_exists() -> bool {
     let config_path = append_path(&current_dir, "config.yaml");
 
     // Check if the configuration file already exists
-    if !config_path.exists() {
-        false
-    } else {
-        true
-    }
+    config_path.exists()
 }
@@ -0,0 +1,96 @@
+use crate::models::app_config::Environment;
+use crate::CONFIG;
+
+/// The variable that selects the environment, `--env <name>` sets it for
+/// the tools and every command they run
+pub const ENVIRONMENT_VARIABLE: &str = "TOOLS_ENV";
+
+/// ## select_environment(name: Option<&str>)
+/// This function selects the environment given with `--env`, or keeps the
+/// one of `TOOLS_ENV`, and exits if it isn't configured
+///
+/// ### Arguments
+/// - name: Option<&str> - The name given on the command line, None to keep `TOOLS_ENV`
+pub fn select_environment(name: Option<&str>) {
+    if let Some(name) = name {
+        std::env::set_var(ENVIRONMENT_VARIABLE, name);
+    }
+    get_environment();
+}
+
+/// ## get_environment() -> Option<(String, Environment)>
+/// This function returns the environment selected with `--env` or
+/// `TOOLS_ENV`, and exits if it isn't configured in config.yaml
+///
+/// ### Returns
+/// - Option<(String, Environment)> - The name and the environment, None if none is selected
+pub fn get_environment() -> Option<(String, Environment)> {
+    let name = std::env::var(ENVIRONMENT_VARIABLE)
+        .ok()
+        .filter(|name| !name.is_empty())?;
+
+    match CONFIG.get_environment(&name) {
+        Some(environment) => Some((name, environment.clone())),
+        None => {
+            let names = CONFIG.get_environment_names();
+            match names.is_empty() {
+                true => eprintln!(
+                    "Unknown environment {}, config.yaml has no environments",
+                    name
+                ),
+                false => eprintln!(
+                    "Unknown environment {}, config.yaml has {}",
+                    name,
+                    names.join(", ")
+                ),
+            }
+            std::process::exit(1);
+        }
+    }
+}
+
+/// ## parse_environment_option(args: &[String]) -> Result<(Option<String>, Vec<String>), String>
+/// This function takes `--env <name>` out of the arguments of a command
+///
+/// ### Arguments
+/// - args: &[String] - The arguments of the command
+///
+/// ### Returns
+/// - Result<(Option<String>, Vec<String>), String> - The environment and the other arguments, or a message describing the error
+pub fn parse_environment_option(args: &[String]) -> Result<(Option<String>, Vec<String>), String> {
+    let mut environment = None;
+    let mut rest = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--env" => match args.next() {
+                Some(name) if !name.starts_with('-') => environment = Some(name.clone()),
+                _ => return Err("--env expects the name of an environment".to_string()),
+            },
+            _ => rest.push(arg.clone()),
+        }
+    }
+
+    Ok((environment, rest))
+}
+
+#[test]
+fn test_environment_parse_environment_option() {
+    let args = |args: &str| {
+        args.split_whitespace()
+            .map(String::from)
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(
+        parse_environment_option(&args("--build --env ci api")),
+        Ok((Some("ci".to_string()), args("--build api")))
+    );
+    assert_eq!(
+        parse_environment_option(&args("-v")),
+        Ok((None, args("-v")))
+    );
+    assert!(parse_environment_option(&args("--env")).is_err());
+    assert!(parse_environment_option(&args("--env --build")).is_err());
+}
@@ -0,0 +1,62 @@
+use crate::helpers::filesystem::path::append_path;
+use crate::models::lockfile::LockFile;
+use crate::CONFIG;
+use std::path::PathBuf;
+
+/// The name of the lock file in the project path
+pub const LOCK_FILE: &str = "tools.lock";
+
+/// ## get_path_to_lock_file() -> PathBuf
+/// This function returns a PathBuf to the lock file
+///
+/// ### Returns
+/// - PathBuf
+pub fn get_path_to_lock_file() -> PathBuf {
+    append_path(&PathBuf::from(&CONFIG.project_path), LOCK_FILE)
+}
+
+/// ## get_lock_file() -> LockFile
+/// This function reads the lock file, or returns an empty lock file if
+/// the project has not been locked yet, and exits if it can't be read or
+/// parsed
+///
+/// ### Returns
+/// - LockFile
+pub fn get_lock_file() -> LockFile {
+    let lock_path = get_path_to_lock_file();
+
+    if !lock_path.exists() {
+        return LockFile::default();
+    }
+
+    let parsed = std::fs::read_to_string(&lock_path)
+        .map_err(|error| format!("Could not read {}: {}", lock_path.display(), error))
+        .and_then(|raw_lock_file| {
+            LockFile::from_yaml(&raw_lock_file)
+                .map_err(|error| format!("Could not parse {}: {}", lock_path.display(), error))
+        });
+
+    match parsed {
+        Ok(lock_file) => lock_file,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// ## write_lock_file(lock_file: &LockFile)
+/// This function writes the lock file to the project path
+///
+/// ### Arguments
+/// - lock_file: &LockFile - The lock file to write
+///
+/// ### Panics
+/// - If the lock file cannot be serialized or written
+pub fn write_lock_file(lock_file: &LockFile) {
+    let lock_yaml = lock_file
+        .to_yaml()
+        .expect("Couldn't serialize lock file to YAML");
+
+    std::fs::write(get_path_to_lock_file(), lock_yaml).expect("Couldn't write lock file");
+}
@@ -1 +1,4 @@
 pub mod app_config;
+pub mod environment;
+pub mod lockfile;
+pub mod project;
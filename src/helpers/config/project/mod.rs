@@ -0,0 +1,53 @@
+use crate::helpers::filesystem::path::append_path;
+use crate::models::project::Project;
+use crate::CONFIG;
+use std::path::PathBuf;
+
+/// The default name of the project definition file in the project path
+pub const PROJECT_FILE: &str = "project.yaml";
+
+/// ## get_path_to_project_file() -> PathBuf
+/// This function returns a PathBuf to the project definition file
+///
+/// ### Returns
+/// - PathBuf
+pub fn get_path_to_project_file() -> PathBuf {
+    append_path(&PathBuf::from(&CONFIG.project_path), PROJECT_FILE)
+}
+
+/// ## get_project() -> Option<Project>
+/// This function reads the project definition file if one exists in the
+/// project path, and exits if it can't be parsed
+///
+/// ### Returns
+/// - Option<Project> - The project definition or None if the file does not exist
+pub fn get_project() -> Option<Project> {
+    let project_path = get_path_to_project_file();
+
+    if !project_path.exists() {
+        return None;
+    }
+
+    read_project(&project_path)
+}
+
+/// ## read_project(path: &Path) -> Option<Project>
+/// This function reads a project definition file from the given path,
+/// and exits if it can't be parsed
+///
+/// ### Arguments
+/// - path: &Path - The path to the project definition file
+///
+/// ### Returns
+/// - Option<Project> - The project definition or None if the file could not be read
+pub fn read_project(path: &std::path::Path) -> Option<Project> {
+    let raw_project = std::fs::read_to_string(path).ok()?;
+
+    match Project::from_yaml(&raw_project) {
+        Ok(project) => Some(project),
+        Err(error) => {
+            eprintln!("Could not parse {}: {}", path.display(), error);
+            std::process::exit(1);
+        }
+    }
+}
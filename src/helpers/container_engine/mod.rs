@@ -0,0 +1,418 @@
+pub mod ps;
+
+use crate::helpers::config::environment::get_environment;
+use crate::helpers::docker::docker_compose::file::get_paths_to_docker_compose;
+use crate::helpers::program_checker::{parse_version, Program};
+use crate::helpers::version::get_version_info;
+use crate::models::app_config::ContainerEngine;
+use crate::CONFIG;
+use ps::ContainerStatus;
+use std::process::Command;
+
+/// The engines tried by auto detection, in order of preference
+const ENGINES: [ContainerEngine; 5] = [
+    ContainerEngine::Docker,
+    ContainerEngine::DockerCompose,
+    ContainerEngine::Podman,
+    ContainerEngine::PodmanCompose,
+    ContainerEngine::Nerdctl,
+];
+
+const DOCKER: Program = Program {
+    name: "docker",
+    command: &["docker", "--version"],
+    minimum: (20, 10, 0),
+    required: true,
+    install: "install Docker 20.10 or newer from https://docs.docker.com/get-docker/",
+};
+
+const DOCKER_COMPOSE_PLUGIN: Program = Program {
+    name: "docker compose",
+    command: &["docker", "compose", "version"],
+    minimum: (2, 0, 0),
+    required: true,
+    install: "install the Compose plugin from https://docs.docker.com/compose/install/",
+};
+
+const DOCKER_COMPOSE_LEGACY: Program = Program {
+    name: "docker-compose",
+    command: &["docker-compose", "version"],
+    minimum: (1, 28, 0),
+    required: true,
+    install: "install docker-compose 1.28 or newer, or switch to `container_engine: docker`",
+};
+
+const PODMAN: Program = Program {
+    name: "podman",
+    command: &["podman", "--version"],
+    minimum: (4, 7, 0),
+    required: true,
+    install: "install podman 4.7 or newer from https://podman.io/docs/installation",
+};
+
+const PODMAN_COMPOSE_PROVIDER: Program = Program {
+    name: "podman compose",
+    command: &["podman", "compose", "version"],
+    minimum: (1, 0, 0),
+    required: true,
+    install: "install podman-compose or docker-compose, which `podman compose` runs",
+};
+
+const PODMAN_FOR_PODMAN_COMPOSE: Program = Program {
+    name: "podman",
+    command: &["podman", "--version"],
+    minimum: (3, 0, 0),
+    required: true,
+    install: "install podman 3.0 or newer from https://podman.io/docs/installation",
+};
+
+const PODMAN_COMPOSE: Program = Program {
+    name: "podman-compose",
+    command: &["podman-compose", "version"],
+    minimum: (1, 0, 0),
+    required: true,
+    install: "install podman-compose 1.0 or newer with `pip install podman-compose`",
+};
+
+const NERDCTL: Program = Program {
+    name: "nerdctl",
+    command: &["nerdctl", "--version"],
+    minimum: (1, 0, 0),
+    required: true,
+    install: "install nerdctl 1.0 or newer from https://github.com/containerd/nerdctl/releases",
+};
+
+/// A container engine with a compose implementation that was found on the
+/// machine, every compose command of the tools runs through it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Engine {
+    pub kind: ContainerEngine, // never ContainerEngine::Auto
+}
+
+impl Engine {
+    /// This function returns the engine configured in config.yaml, detected
+    /// if it is `auto`.
+    pub fn from_config() -> Result<Self, String> {
+        Engine::detect(CONFIG.get_container_engine())
+    }
+
+    /// This function returns the preferred engine if its compose command
+    /// works, or the first engine that works for `ContainerEngine::Auto`.
+    pub fn detect(preferred: ContainerEngine) -> Result<Self, String> {
+        let candidates = match preferred {
+            ContainerEngine::Auto => ENGINES.to_vec(),
+            kind => vec![kind],
+        };
+
+        for kind in candidates {
+            let engine = Engine { kind };
+            if engine.is_available() {
+                return Ok(engine);
+            }
+        }
+
+        match preferred {
+            ContainerEngine::Auto => {
+                Err("No container engine with compose support was found".to_string())
+            }
+            kind => Err(format!(
+                "`{}` doesn't work, install it or change container_engine in config.yaml",
+                Engine { kind }.name()
+            )),
+        }
+    }
+
+    /// This function returns the compose command of the engine, e.g.
+    /// `podman-compose`.
+    pub fn name(&self) -> String {
+        self.compose_command().join(" ")
+    }
+
+    /// This function returns the command line client of the engine.
+    pub fn cli(&self) -> &'static str {
+        match self.kind {
+            ContainerEngine::Podman | ContainerEngine::PodmanCompose => "podman",
+            ContainerEngine::Nerdctl => "nerdctl",
+            ContainerEngine::Auto | ContainerEngine::Docker | ContainerEngine::DockerCompose => {
+                "docker"
+            }
+        }
+    }
+
+    /// This function returns the programs the engine needs, for `tools doctor`.
+    pub fn programs(&self) -> &'static [Program] {
+        match self.kind {
+            ContainerEngine::Auto | ContainerEngine::Docker => &[DOCKER, DOCKER_COMPOSE_PLUGIN],
+            ContainerEngine::DockerCompose => &[DOCKER, DOCKER_COMPOSE_LEGACY],
+            ContainerEngine::Podman => &[PODMAN, PODMAN_COMPOSE_PROVIDER],
+            ContainerEngine::PodmanCompose => &[PODMAN_FOR_PODMAN_COMPOSE, PODMAN_COMPOSE],
+            ContainerEngine::Nerdctl => &[NERDCTL],
+        }
+    }
+
+    /// This function returns a compose command for the project, with the
+    /// compose files, the project name, the env files, profiles and
+    /// variables of the selected environment and the version environment
+    /// variables.
+    pub fn compose(&self, args: &[&str]) -> Command {
+        let compose_command = self.compose_command();
+        let mut command = Command::new(compose_command[0]);
+        command.args(&compose_command[1..]);
+        for path in get_paths_to_docker_compose() {
+            command.arg("-f").arg(path);
+        }
+        command
+            .arg("-p")
+            .arg(compose_project_name(&CONFIG.project_name));
+
+        let environment = get_environment()
+            .map(|(_, environment)| environment)
+            .unwrap_or_default();
+        for env_file in environment.env_files.iter().flatten() {
+            command.arg("--env-file").arg(env_file);
+        }
+        for profile in environment.profiles.iter().flatten() {
+            command.arg("--profile").arg(profile);
+        }
+
+        command
+            .args(args)
+            .current_dir(&CONFIG.project_path)
+            .envs(environment.variables.unwrap_or_default())
+            .envs(get_version_info().to_env());
+        command
+    }
+
+    /// This function creates and starts the containers of the services in
+    /// the background, every service if none are given.
+    pub fn up(&self, services: &[String], build: bool) -> Result<(), String> {
+        let mut args = vec!["up", "-d", "--remove-orphans"];
+        if build {
+            args.push("--build");
+        }
+        args.extend(services.iter().map(|service| service.as_str()));
+        self.run(&args)
+    }
+
+    /// This function stops and removes the containers of the project, and
+    /// its volumes if `volumes` is true.
+    pub fn down(&self, volumes: bool) -> Result<(), String> {
+        let mut args = vec!["down", "--remove-orphans"];
+        if volumes {
+            args.push("-v");
+        }
+        self.run(&args)
+    }
+
+    /// This function starts the services, every service if none are given,
+    /// and syncs or rebuilds them when their files change until it is
+    /// interrupted.
+    pub fn watch(&self, services: &[String]) -> Result<(), String> {
+        // `watch` only exists in Compose v2, which `podman compose` may run
+        if matches!(
+            self.kind,
+            ContainerEngine::DockerCompose
+                | ContainerEngine::PodmanCompose
+                | ContainerEngine::Nerdctl
+        ) {
+            return Err(format!(
+                "`{}` can't watch, use Docker with the Compose plugin 2.22 or newer",
+                self.name()
+            ));
+        }
+
+        let mut args = vec!["watch"];
+        args.extend(services.iter().map(|service| service.as_str()));
+        self.run(&args)
+    }
+
+    /// This function returns the containers of the project, including the
+    /// stopped ones.
+    pub fn ps(&self) -> Result<Vec<ContainerStatus>, String> {
+        match self.kind {
+            // the legacy docker-compose can't print JSON
+            ContainerEngine::DockerCompose => {
+                let services = self.output(&["ps", "--services"])?;
+                let running = self.output(&["ps", "--services", "--filter", "status=running"])?;
+                Ok(ps::from_service_lists(&services, &running))
+            }
+            // podman-compose always lists stopped containers and rejects --all,
+            // `podman compose` usually runs docker-compose v2 and needs it
+            ContainerEngine::PodmanCompose => {
+                ps::from_json(&self.output(&["ps", "--format", "json"])?)
+            }
+            _ => ps::from_json(&self.output(&["ps", "--all", "--format", "json"])?),
+        }
+    }
+
+    /// This function returns the repository digest of a local image, None if
+    /// the image wasn't pulled or has no digest.
+    pub fn image_digest(&self, image: &str) -> Option<String> {
+        let output = Command::new(self.cli())
+            .args([
+                "image",
+                "inspect",
+                "--format",
+                "{{index .RepoDigests 0}}",
+                image,
+            ])
+            .output()
+            .ok()?;
+
+        let digest = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !digest.is_empty()).then_some(digest)
+    }
+
+    /// This function returns the version of the engine server, or the first
+    /// line of the error if the server can't be reached.
+    pub fn server_version(&self) -> Result<String, String> {
+        let format = match self.kind {
+            ContainerEngine::Podman | ContainerEngine::PodmanCompose => "{{.Version.Version}}",
+            _ => "{{.ServerVersion}}",
+        };
+
+        let output = Command::new(self.cli())
+            .args(["info", "--format", format])
+            .output()
+            .map_err(|error| format!("failed to run `{} info`: {}", self.cli(), error))?;
+
+        // without a server the version is empty, even if `info` succeeds
+        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if output.status.success() && parse_version(&version).is_some() {
+            Ok(version)
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .find(|line| !line.trim().is_empty())
+                .unwrap_or("the server is not reachable")
+                .trim()
+                .to_string())
+        }
+    }
+
+    // ## compose_command() -> &'static [&'static str]
+    // This function returns the program and arguments that run compose
+    fn compose_command(&self) -> &'static [&'static str] {
+        match self.kind {
+            ContainerEngine::Auto | ContainerEngine::Docker => &["docker", "compose"],
+            ContainerEngine::DockerCompose => &["docker-compose"],
+            ContainerEngine::Podman => &["podman", "compose"],
+            ContainerEngine::PodmanCompose => &["podman-compose"],
+            ContainerEngine::Nerdctl => &["nerdctl", "compose"],
+        }
+    }
+
+    // ## is_available() -> bool
+    // This function checks that the compose command of the engine runs
+    fn is_available(&self) -> bool {
+        let compose_command = self.compose_command();
+        Command::new(compose_command[0])
+            .args(&compose_command[1..])
+            .arg("version")
+            .output()
+            .is_ok_and(|output| output.status.success())
+    }
+
+    // ## run(args: &[&str]) -> Result<(), String>
+    // This function runs a compose command with the output shown to the user
+    fn run(&self, args: &[&str]) -> Result<(), String> {
+        let status = self
+            .compose(args)
+            .status()
+            .map_err(|error| format!("failed to run `{}`: {}", self.name(), error))?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "`{} {}` failed with exit code {}",
+                self.name(),
+                args.join(" "),
+                status
+                    .code()
+                    .map(|code| code.to_string())
+                    .unwrap_or("unknown".to_string())
+            ))
+        }
+    }
+
+    // ## output(args: &[&str]) -> Result<String, String>
+    // This function runs a compose command and returns its stdout
+    fn output(&self, args: &[&str]) -> Result<String, String> {
+        let output = self
+            .compose(args)
+            .output()
+            .map_err(|error| format!("failed to run `{}`: {}", self.name(), error))?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        } else {
+            Err(format!(
+                "`{} {}` failed: {}",
+                self.name(),
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    }
+}
+
+/// ## get_engine() -> Engine
+/// This function returns the container engine configured in config.yaml,
+/// and exits if it isn't installed
+///
+/// ### Returns
+/// - Engine - The engine every compose command runs through
+pub fn get_engine() -> Engine {
+    match Engine::from_config() {
+        Ok(engine) => engine,
+        Err(error) => {
+            eprintln!("{}", error);
+            eprintln!("Run `tools doctor` to check the container engine");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// ## compose_project_name(name: &str) -> String
+/// This function returns a project name every compose implementation
+/// accepts, lowercase letters, digits, dashes and underscores
+///
+/// ### Arguments
+/// - name: &str - The name of the project
+///
+/// ### Returns
+/// - String - The compose project name
+pub fn compose_project_name(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>()
+        .trim_start_matches(['-', '_'])
+        .to_string()
+}
+
+#[test]
+fn test_container_engine() {
+    assert_eq!(compose_project_name("My Shop.v2"), "my-shop-v2");
+    assert_eq!(compose_project_name("_tools"), "tools");
+
+    let engine = Engine {
+        kind: ContainerEngine::PodmanCompose,
+    };
+    assert_eq!(engine.name(), "podman-compose");
+    assert_eq!(engine.cli(), "podman");
+    assert_eq!(engine.programs()[1].name, "podman-compose");
+
+    let engine = Engine {
+        kind: ContainerEngine::Docker,
+    };
+    assert_eq!(engine.name(), "docker compose");
+    assert_eq!(engine.programs()[0].name, "docker");
+}
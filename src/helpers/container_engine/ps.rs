@@ -0,0 +1,172 @@
+use serde_json::Value;
+
+/// The label compose implementations put the service name of a container in
+const SERVICE_LABEL: &str = "com.docker.compose.service";
+
+/// A container of the project, the same for every compose implementation
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerStatus {
+    pub service: String, // the compose service the container runs
+    pub name: String,    // the name of the container
+    pub state: String,   // running, exited, created, ... in lowercase
+    pub status: String,  // the human readable status, e.g. `Up 2 minutes (healthy)`
+}
+
+/// ## from_json(output: &str) -> Result<Vec<ContainerStatus>, String>
+/// This function parses the JSON output of `ps --format json`. Compose v2
+/// and nerdctl print one object per line, older v2 releases print an array
+/// and podman prints an array of `podman ps` containers with the service in
+/// their labels
+///
+/// ### Arguments
+/// - output: &str - The output of `ps --format json`
+///
+/// ### Returns
+/// - Result<Vec<ContainerStatus>, String> - The containers sorted by service, or the parse error
+pub fn from_json(output: &str) -> Result<Vec<ContainerStatus>, String> {
+    let output = output.trim();
+    let values = if output.starts_with('[') {
+        match serde_json::from_str::<Value>(output).map_err(|error| error.to_string())? {
+            Value::Array(values) => values,
+            _ => Vec::new(),
+        }
+    } else {
+        output
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str::<Value>(line).map_err(|error| error.to_string()))
+            .collect::<Result<Vec<Value>, String>>()?
+    };
+
+    let mut containers = values
+        .iter()
+        .map(|value| {
+            let name = match &value["Name"] {
+                Value::String(name) => name.clone(),
+                _ => match &value["Names"] {
+                    Value::Array(names) => names
+                        .first()
+                        .and_then(|name| name.as_str())
+                        .unwrap_or_default()
+                        .to_string(),
+                    Value::String(names) => names.clone(),
+                    _ => String::new(),
+                },
+            };
+            let service = match &value["Service"] {
+                Value::String(service) => service.clone(),
+                _ => service_label(&value["Labels"]).unwrap_or_else(|| name.clone()),
+            };
+            let state = value["State"].as_str().unwrap_or("unknown").to_lowercase();
+            let status = value["Status"].as_str().unwrap_or(&state).to_string();
+
+            ContainerStatus {
+                service,
+                name,
+                state,
+                status,
+            }
+        })
+        .collect::<Vec<ContainerStatus>>();
+
+    containers.sort_by(|a, b| (&a.service, &a.name).cmp(&(&b.service, &b.name)));
+    Ok(containers)
+}
+
+/// ## from_service_lists(services: &str, running: &str) -> Vec<ContainerStatus>
+/// This function builds the status of the services from the output of
+/// `ps --services` and `ps --services --filter status=running`, for the
+/// legacy docker-compose that can't print JSON
+///
+/// ### Arguments
+/// - services: &str - Every service of the compose file, one per line
+/// - running: &str - The running services, one per line
+///
+/// ### Returns
+/// - Vec<ContainerStatus> - The services sorted by name
+pub fn from_service_lists(services: &str, running: &str) -> Vec<ContainerStatus> {
+    let running = running.lines().map(str::trim).collect::<Vec<&str>>();
+
+    let mut containers = services
+        .lines()
+        .map(str::trim)
+        .filter(|service| !service.is_empty())
+        .map(|service| {
+            let state = if running.contains(&service) {
+                "running"
+            } else {
+                "stopped"
+            };
+            ContainerStatus {
+                service: service.to_string(),
+                name: service.to_string(),
+                state: state.to_string(),
+                status: state.to_string(),
+            }
+        })
+        .collect::<Vec<ContainerStatus>>();
+
+    containers.sort_by(|a, b| a.service.cmp(&b.service));
+    containers
+}
+
+// ## service_label(labels: &Value) -> Option<String>
+// This function returns the compose service of the labels of a container,
+// podman prints them as an object and docker as `key=value,key=value`
+fn service_label(labels: &Value) -> Option<String> {
+    match labels {
+        Value::Object(labels) => labels
+            .get(SERVICE_LABEL)
+            .and_then(|service| service.as_str())
+            .map(String::from),
+        Value::String(labels) => labels.split(',').find_map(|label| {
+            label
+                .strip_prefix(SERVICE_LABEL)
+                .and_then(|value| value.strip_prefix('='))
+                .map(String::from)
+        }),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_container_engine_ps_from_json() {
+    let expected = vec![
+        ContainerStatus {
+            service: "api".to_string(),
+            name: "shop-api-1".to_string(),
+            state: "running".to_string(),
+            status: "Up 2 minutes".to_string(),
+        },
+        ContainerStatus {
+            service: "db".to_string(),
+            name: "shop-db-1".to_string(),
+            state: "exited".to_string(),
+            status: "Exited (0) 1 second ago".to_string(),
+        },
+    ];
+
+    // docker compose v2 and nerdctl
+    let ndjson = r#"{"Name":"shop-db-1","Service":"db","State":"exited","Status":"Exited (0) 1 second ago"}
+{"Name":"shop-api-1","Service":"api","State":"running","Status":"Up 2 minutes"}
+"#;
+    assert_eq!(from_json(ndjson).unwrap(), expected);
+
+    // podman-compose
+    let podman = r#"[
+        {"Names":["shop-api-1"],"Labels":{"com.docker.compose.service":"api"},"State":"running","Status":"Up 2 minutes"},
+        {"Names":["shop-db-1"],"Labels":{"com.docker.compose.service":"db"},"State":"exited","Status":"Exited (0) 1 second ago"}
+    ]"#;
+    assert_eq!(from_json(podman).unwrap(), expected);
+
+    assert_eq!(from_json("").unwrap(), Vec::new());
+    assert!(from_json("{").is_err());
+
+    assert_eq!(
+        from_service_lists("db\napi\n", "api\n")
+            .iter()
+            .map(|container| (container.service.as_str(), container.state.as_str()))
+            .collect::<Vec<(&str, &str)>>(),
+        vec![("api", "running"), ("db", "stopped")]
+    );
+}
@@ -0,0 +1,102 @@
+/// The commit types of the Conventional Commits specification and the
+/// changelog section they are grouped under, in the order of the changelog
+pub const COMMIT_TYPES: [(&str, &str); 11] = [
+    ("feat", "Features"),
+    ("fix", "Bug Fixes"),
+    ("perf", "Performance Improvements"),
+    ("refactor", "Code Refactoring"),
+    ("revert", "Reverts"),
+    ("docs", "Documentation"),
+    ("style", "Styles"),
+    ("test", "Tests"),
+    ("build", "Build System"),
+    ("ci", "Continuous Integration"),
+    ("chore", "Chores"),
+];
+
+/// A commit message following the Conventional Commits specification,
+/// `<type>[(scope)][!]: <description>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConventionalCommit {
+    pub commit_type: String,   // the type of the change, e.g. feat or fix
+    pub scope: Option<String>, // the optional scope in parentheses
+    pub breaking: bool,        // true for `!` or a `BREAKING CHANGE:` footer
+    pub description: String,
+}
+
+impl ConventionalCommit {
+    /// This function parses the subject and body of a commit message and
+    /// returns None if the subject is not a conventional commit.
+    pub fn parse(subject: &str, body: &str) -> Option<Self> {
+        let (header, description) = subject.split_once(": ")?;
+        let description = description.trim();
+        if description.is_empty() {
+            return None;
+        }
+
+        let (header, breaking) = match header.strip_suffix('!') {
+            Some(header) => (header, true),
+            None => (header, false),
+        };
+
+        let (commit_type, scope) = match header.split_once('(') {
+            Some((commit_type, scope)) => {
+                let scope = scope.strip_suffix(')')?;
+                if scope.is_empty() || scope.contains(['(', ')']) {
+                    return None;
+                }
+                (commit_type, Some(scope.to_string()))
+            }
+            None => (header, None),
+        };
+
+        if commit_type.is_empty() || !commit_type.chars().all(|c| c.is_ascii_alphabetic()) {
+            return None;
+        }
+
+        let breaking = breaking
+            || body.lines().any(|line| {
+                line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+            });
+
+        Some(ConventionalCommit {
+            commit_type: commit_type.to_ascii_lowercase(),
+            scope,
+            breaking,
+            description: description.to_string(),
+        })
+    }
+
+    /// This function returns true if the commit type is one of the known
+    /// `COMMIT_TYPES`.
+    pub fn is_known_type(&self) -> bool {
+        COMMIT_TYPES
+            .iter()
+            .any(|(commit_type, _)| *commit_type == self.commit_type)
+    }
+}
+
+#[test]
+fn test_conventional_commit_parse() {
+    assert_eq!(
+        ConventionalCommit::parse("feat(api)!: drop v1 endpoints", ""),
+        Some(ConventionalCommit {
+            commit_type: "feat".to_string(),
+            scope: Some("api".to_string()),
+            breaking: true,
+            description: "drop v1 endpoints".to_string(),
+        })
+    );
+
+    let fix = ConventionalCommit::parse("fix: handle empty config", "BREAKING CHANGE: x").unwrap();
+    assert!(fix.breaking);
+    assert!(fix.is_known_type());
+    assert_eq!(fix.scope, None);
+
+    assert!(!ConventionalCommit::parse("wip: stuff", "")
+        .unwrap()
+        .is_known_type());
+    assert_eq!(ConventionalCommit::parse("Update README", ""), None);
+    assert_eq!(ConventionalCommit::parse("feat(): empty scope", ""), None);
+    assert_eq!(ConventionalCommit::parse("feat:missing space", ""), None);
+}
@@ -0,0 +1,163 @@
+/// The number of unchanged lines shown around every change
+const CONTEXT_LINES: usize = 2;
+
+/// A line of a diff between two texts
+#[derive(Debug, PartialEq)]
+pub enum DiffLine<'a> {
+    Unchanged(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// ## diff_lines(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>>
+/// This function compares two texts line by line using their longest
+/// common subsequence
+///
+/// ### Arguments
+/// - old: &str - The original text
+/// - new: &str - The changed text
+///
+/// ### Returns
+/// - Vec<DiffLine> - Every line of both texts, in order
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let old_lines = old.lines().collect::<Vec<&str>>();
+    let new_lines = new.lines().collect::<Vec<&str>>();
+
+    // lengths[i][j] is the length of the common subsequence of old[i..] and new[j..]
+    let mut lengths = vec![vec![0usize; new_lines.len() + 1]; old_lines.len() + 1];
+    for i in (0..old_lines.len()).rev() {
+        for j in (0..new_lines.len()).rev() {
+            lengths[i][j] = if old_lines[i] == new_lines[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old_lines.len() || j < new_lines.len() {
+        if i < old_lines.len() && j < new_lines.len() && old_lines[i] == new_lines[j] {
+            lines.push(DiffLine::Unchanged(old_lines[i]));
+            i += 1;
+            j += 1;
+        } else if i < old_lines.len()
+            && (j == new_lines.len() || lengths[i + 1][j] >= lengths[i][j + 1])
+        {
+            lines.push(DiffLine::Removed(old_lines[i]));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new_lines[j]));
+            j += 1;
+        }
+    }
+
+    lines
+}
+
+/// ## unified_diff(path: &str, old: &str, new: &str) -> String
+/// This function renders the changes between two versions of a file as a
+/// unified diff
+///
+/// ### Arguments
+/// - path: &str - The path of the file shown in the header
+/// - old: &str - The original content of the file
+/// - new: &str - The changed content of the file
+///
+/// ### Returns
+/// - String - The diff, empty if the contents are the same
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    let lines = diff_lines(old, new);
+
+    // the indexes of the lines shown, every change and its context
+    let mut shown = vec![false; lines.len()];
+    for (index, line) in lines.iter().enumerate() {
+        if !matches!(line, DiffLine::Unchanged(_)) {
+            let start = index.saturating_sub(CONTEXT_LINES);
+            let end = (index + CONTEXT_LINES + 1).min(lines.len());
+            shown[start..end].iter_mut().for_each(|show| *show = true);
+        }
+    }
+
+    if !lines
+        .iter()
+        .any(|line| !matches!(line, DiffLine::Unchanged(_)))
+    {
+        return String::new();
+    }
+
+    let mut diff = format!("--- a/{}\n+++ b/{}\n", path, path);
+    let (mut old_line, mut new_line) = (1, 1);
+    let mut index = 0;
+    while index < lines.len() {
+        if !shown[index] {
+            match lines[index] {
+                DiffLine::Removed(_) => old_line += 1,
+                DiffLine::Added(_) => new_line += 1,
+                DiffLine::Unchanged(_) => {
+                    old_line += 1;
+                    new_line += 1;
+                }
+            }
+            index += 1;
+            continue;
+        }
+
+        // a hunk runs until the next line that is not shown
+        let end = (index..lines.len())
+            .find(|end| !shown[*end])
+            .unwrap_or(lines.len());
+        let hunk = &lines[index..end];
+        let old_count = hunk
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Added(_)))
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Removed(_)))
+            .count();
+
+        diff.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_line, old_count, new_line, new_count
+        ));
+        for line in hunk {
+            match line {
+                DiffLine::Unchanged(text) => diff.push_str(&format!(" {}\n", text)),
+                DiffLine::Removed(text) => diff.push_str(&format!("-{}\n", text)),
+                DiffLine::Added(text) => diff.push_str(&format!("+{}\n", text)),
+            }
+        }
+
+        old_line += old_count;
+        new_line += new_count;
+        index = end;
+    }
+
+    diff
+}
+
+#[test]
+fn test_unified_diff() {
+    let old = "name: web\nversion: 0.1.0\nimage: web:latest\na\nb\nc\nd\ne\nlast\n";
+    let new = "name: web\nversion: 0.2.0\nimage: web:latest\na\nb\nc\nd\ne\nlast\nadded\n";
+
+    assert_eq!(
+        unified_diff("config.yaml", old, new),
+        "--- a/config.yaml
++++ b/config.yaml
+@@ -1,4 +1,4 @@
+ name: web
+-version: 0.1.0
++version: 0.2.0
+ image: web:latest
+ a
+@@ -8,2 +8,3 @@
+ e
+ last
++added
+"
+    );
+    assert_eq!(unified_diff("config.yaml", old, old), "");
+}
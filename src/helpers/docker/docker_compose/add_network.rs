@@ -0,0 +1,138 @@
+use super::add_service::check_name;
+use crate::models::docker_compose::service::ServiceNetworks;
+use crate::models::docker_compose::{DockerCompose, Network};
+
+impl DockerCompose {
+    /// This function adds a top level network, it fails if the name is taken.
+    pub fn add_network(&mut self, name: &str, network: Option<Network>) -> Result<(), String> {
+        check_name("network", name)?;
+        if self.networks.contains_key(name) {
+            return Err(format!("Network {} already exists", name));
+        }
+        self.networks.insert(name.to_string(), network);
+        Ok(())
+    }
+
+    /// This function removes a top level network, it fails if a service is
+    /// still attached to it.
+    pub fn remove_network(&mut self, name: &str) -> Result<(), String> {
+        if !self.networks.contains_key(name) {
+            return Err(format!("Network {} doesn't exist", name));
+        }
+        let services = self.network_references(name);
+        if !services.is_empty() {
+            return Err(format!(
+                "Network {} is still used by {}",
+                name,
+                services.join(", ")
+            ));
+        }
+        self.networks.shift_remove(name);
+        Ok(())
+    }
+
+    /// This function attaches a service to a network of the file, or to the
+    /// `default` network.
+    pub fn attach_network(&mut self, service: &str, network: &str) -> Result<(), String> {
+        if network != "default" && !self.networks.contains_key(network) {
+            return Err(format!(
+                "Network {} doesn't exist, add it with `tools compose add network {}`",
+                network, network
+            ));
+        }
+
+        let entry = self.service_mut(service)?;
+        if let Some(network_mode) = &entry.network_mode {
+            return Err(format!(
+                "Service {} uses network_mode {} and can't join networks",
+                service, network_mode
+            ));
+        }
+        match entry
+            .networks
+            .get_or_insert_with(|| ServiceNetworks::List(Vec::new()))
+        {
+            ServiceNetworks::List(networks) if networks.iter().any(|n| n == network) => {
+                return Err(format!(
+                    "Service {} is already in network {}",
+                    service, network
+                ))
+            }
+            ServiceNetworks::Map(networks) if networks.contains_key(network) => {
+                return Err(format!(
+                    "Service {} is already in network {}",
+                    service, network
+                ))
+            }
+            ServiceNetworks::List(networks) => networks.push(network.to_string()),
+            ServiceNetworks::Map(networks) => {
+                networks.insert(network.to_string(), None);
+            }
+        }
+        Ok(())
+    }
+
+    /// This function detaches a service from a network.
+    pub fn detach_network(&mut self, service: &str, network: &str) -> Result<(), String> {
+        let entry = self.service_mut(service)?;
+        let removed = match &mut entry.networks {
+            Some(ServiceNetworks::List(networks)) => {
+                let count = networks.len();
+                networks.retain(|n| n != network);
+                networks.len() != count
+            }
+            Some(ServiceNetworks::Map(networks)) => networks.shift_remove(network).is_some(),
+            None => false,
+        };
+        if !removed {
+            return Err(format!("Service {} is not in network {}", service, network));
+        }
+        if entry
+            .networks
+            .as_ref()
+            .is_some_and(|networks| networks.names().is_empty())
+        {
+            entry.networks = None;
+        }
+        Ok(())
+    }
+
+    /// This function returns the services attached to a network.
+    pub fn network_references(&self, name: &str) -> Vec<String> {
+        self.services
+            .iter()
+            .filter(|(_, service)| {
+                service
+                    .networks
+                    .as_ref()
+                    .is_some_and(|networks| networks.names().iter().any(|n| n == name))
+            })
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+}
+
+#[test]
+fn test_docker_compose_edit_networks() {
+    let mut docker_compose = DockerCompose::from_yaml(
+        "services:\n  api:\n    networks:\n      backend:\n        aliases: [api]\n  db: {}\n",
+    )
+    .unwrap();
+
+    assert!(docker_compose.attach_network("db", "backend").is_err());
+    docker_compose.add_network("backend", None).unwrap();
+    assert!(docker_compose.add_network("backend", None).is_err());
+    docker_compose.attach_network("db", "backend").unwrap();
+    assert!(docker_compose.attach_network("api", "backend").is_err());
+    assert_eq!(
+        docker_compose.network_references("backend"),
+        vec!["api".to_string(), "db".to_string()]
+    );
+    assert!(docker_compose.remove_network("backend").is_err());
+
+    docker_compose.detach_network("api", "backend").unwrap();
+    docker_compose.detach_network("db", "backend").unwrap();
+    assert_eq!(docker_compose.services["db"].networks, None);
+    docker_compose.remove_network("backend").unwrap();
+    assert!(docker_compose.networks.is_empty());
+}
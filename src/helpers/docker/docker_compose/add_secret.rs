@@ -0,0 +1,209 @@
+use super::add_service::check_name;
+use crate::models::docker_compose::types::FileReference;
+use crate::models::docker_compose::{Config, DockerCompose, Secret};
+
+impl DockerCompose {
+    /// This function adds a top level secret, it fails if the name is taken.
+    pub fn add_secret(&mut self, name: &str, secret: Secret) -> Result<(), String> {
+        check_name("secret", name)?;
+        if self.secrets.contains_key(name) {
+            return Err(format!("Secret {} already exists", name));
+        }
+        self.secrets.insert(name.to_string(), Some(secret));
+        Ok(())
+    }
+
+    /// This function removes a top level secret, it fails if a service is
+    /// still granted it.
+    pub fn remove_secret(&mut self, name: &str) -> Result<(), String> {
+        if !self.secrets.contains_key(name) {
+            return Err(format!("Secret {} doesn't exist", name));
+        }
+        let services = self.secret_references(name);
+        if !services.is_empty() {
+            return Err(format!(
+                "Secret {} is still used by {}",
+                name,
+                services.join(", ")
+            ));
+        }
+        self.secrets.shift_remove(name);
+        Ok(())
+    }
+
+    /// This function grants a secret of the file to a service.
+    pub fn attach_secret(&mut self, service: &str, secret: &str) -> Result<(), String> {
+        if !self.secrets.contains_key(secret) {
+            return Err(format!(
+                "Secret {} doesn't exist, add it with `tools compose add secret {} --file <path>`",
+                secret, secret
+            ));
+        }
+        let entry = self.service_mut(service)?;
+        attach_reference(&mut entry.secrets, "secret", service, secret)
+    }
+
+    /// This function revokes a secret from a service.
+    pub fn detach_secret(&mut self, service: &str, secret: &str) -> Result<(), String> {
+        let entry = self.service_mut(service)?;
+        detach_reference(&mut entry.secrets, "secret", service, secret)
+    }
+
+    /// This function returns the services granted a secret.
+    pub fn secret_references(&self, name: &str) -> Vec<String> {
+        self.services
+            .iter()
+            .filter(|(_, service)| {
+                service
+                    .secrets
+                    .iter()
+                    .flatten()
+                    .any(|secret| secret.source() == name)
+            })
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// This function adds a top level config, it fails if the name is taken.
+    pub fn add_config(&mut self, name: &str, config: Config) -> Result<(), String> {
+        check_name("config", name)?;
+        if self.configs.contains_key(name) {
+            return Err(format!("Config {} already exists", name));
+        }
+        self.configs.insert(name.to_string(), Some(config));
+        Ok(())
+    }
+
+    /// This function removes a top level config, it fails if a service is
+    /// still granted it.
+    pub fn remove_config(&mut self, name: &str) -> Result<(), String> {
+        if !self.configs.contains_key(name) {
+            return Err(format!("Config {} doesn't exist", name));
+        }
+        let services = self.config_references(name);
+        if !services.is_empty() {
+            return Err(format!(
+                "Config {} is still used by {}",
+                name,
+                services.join(", ")
+            ));
+        }
+        self.configs.shift_remove(name);
+        Ok(())
+    }
+
+    /// This function grants a config of the file to a service.
+    pub fn attach_config(&mut self, service: &str, config: &str) -> Result<(), String> {
+        if !self.configs.contains_key(config) {
+            return Err(format!(
+                "Config {} doesn't exist, add it with `tools compose add config {} --file <path>`",
+                config, config
+            ));
+        }
+        let entry = self.service_mut(service)?;
+        attach_reference(&mut entry.configs, "config", service, config)
+    }
+
+    /// This function revokes a config from a service.
+    pub fn detach_config(&mut self, service: &str, config: &str) -> Result<(), String> {
+        let entry = self.service_mut(service)?;
+        detach_reference(&mut entry.configs, "config", service, config)
+    }
+
+    /// This function returns the services granted a config.
+    pub fn config_references(&self, name: &str) -> Vec<String> {
+        self.services
+            .iter()
+            .filter(|(_, service)| {
+                service
+                    .configs
+                    .iter()
+                    .flatten()
+                    .any(|config| config.source() == name)
+            })
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+}
+
+// ## attach_reference(references, kind, service, name) -> Result<(), String>
+// This function adds a config or secret to the references of a service
+fn attach_reference(
+    references: &mut Option<Vec<FileReference>>,
+    kind: &str,
+    service: &str,
+    name: &str,
+) -> Result<(), String> {
+    let references = references.get_or_insert_with(Vec::new);
+    if references
+        .iter()
+        .any(|reference| reference.source() == name)
+    {
+        return Err(format!(
+            "Service {} already has the {} {}",
+            service, kind, name
+        ));
+    }
+    references.push(FileReference::Short(name.to_string()));
+    Ok(())
+}
+
+// ## detach_reference(references, kind, service, name) -> Result<(), String>
+// This function removes a config or secret from the references of a service
+fn detach_reference(
+    references: &mut Option<Vec<FileReference>>,
+    kind: &str,
+    service: &str,
+    name: &str,
+) -> Result<(), String> {
+    let removed = match references {
+        Some(list) => {
+            let count = list.len();
+            list.retain(|reference| reference.source() != name);
+            list.len() != count
+        }
+        None => false,
+    };
+    if !removed {
+        return Err(format!(
+            "Service {} doesn't have the {} {}",
+            service, kind, name
+        ));
+    }
+    if references.as_ref().is_some_and(|list| list.is_empty()) {
+        *references = None;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_docker_compose_edit_secrets() {
+    let mut docker_compose = DockerCompose::from_yaml("services:\n  api: {}\n").unwrap();
+
+    assert!(docker_compose.attach_secret("api", "token").is_err());
+    docker_compose
+        .add_secret(
+            "token",
+            Secret {
+                file: Some("./secrets/token.txt".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    docker_compose.attach_secret("api", "token").unwrap();
+    assert!(docker_compose.attach_secret("api", "token").is_err());
+    assert!(docker_compose.remove_secret("token").is_err());
+    docker_compose.detach_secret("api", "token").unwrap();
+    assert!(docker_compose.detach_secret("api", "token").is_err());
+    docker_compose.remove_secret("token").unwrap();
+
+    docker_compose
+        .add_config("nginx", Config::default())
+        .unwrap();
+    docker_compose.attach_config("api", "nginx").unwrap();
+    assert_eq!(
+        docker_compose.config_references("nginx"),
+        vec!["api".to_string()]
+    );
+    assert_eq!(docker_compose.services["api"].secrets, None);
+}
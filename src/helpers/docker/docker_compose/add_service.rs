@@ -0,0 +1,417 @@
+use crate::models::docker_compose::service::{DependsOn, Extends, Port, Service};
+use crate::models::docker_compose::types::{ListOrDict, Scalar, StringOrNumber};
+use crate::models::docker_compose::DockerCompose;
+
+impl DockerCompose {
+    /// This function adds a service, it fails if the name is taken.
+    pub fn add_service(&mut self, name: &str, service: Service) -> Result<(), String> {
+        check_name("service", name)?;
+        if self.services.contains_key(name) {
+            return Err(format!("Service {} already exists", name));
+        }
+        self.services.insert(name.to_string(), service);
+        Ok(())
+    }
+
+    /// This function removes a service, it fails if another service still
+    /// references it.
+    pub fn remove_service(&mut self, name: &str) -> Result<(), String> {
+        self.service(name)?;
+        let references = self.service_references(name);
+        if !references.is_empty() {
+            return Err(format!(
+                "Service {} is still used by {}",
+                name,
+                references.join(", ")
+            ));
+        }
+        self.services.shift_remove(name);
+        Ok(())
+    }
+
+    /// This function renames a service and updates the `depends_on`,
+    /// `links`, `extends`, `volumes_from` and `service:` modes of the other
+    /// services.
+    pub fn rename_service(&mut self, name: &str, new_name: &str) -> Result<(), String> {
+        self.service(name)?;
+        check_name("service", new_name)?;
+        if self.services.contains_key(new_name) {
+            return Err(format!("Service {} already exists", new_name));
+        }
+
+        // rebuild the map so the service keeps its place in the file
+        self.services = std::mem::take(&mut self.services)
+            .into_iter()
+            .map(|(key, service)| match key == name {
+                true => (new_name.to_string(), service),
+                false => (key, service),
+            })
+            .collect();
+
+        for service in self.services.values_mut() {
+            match &mut service.depends_on {
+                Some(DependsOn::List(services)) => services
+                    .iter_mut()
+                    .filter(|service| *service == name)
+                    .for_each(|service| *service = new_name.to_string()),
+                Some(DependsOn::Map(services)) => {
+                    *services = std::mem::take(services)
+                        .into_iter()
+                        .map(|(key, dependency)| match key == name {
+                            true => (new_name.to_string(), dependency),
+                            false => (key, dependency),
+                        })
+                        .collect();
+                }
+                None => {}
+            }
+            match &mut service.extends {
+                Some(Extends::Service(service)) if service == name => {
+                    *service = new_name.to_string()
+                }
+                Some(Extends::Long {
+                    service,
+                    file: None,
+                }) if service == name => *service = new_name.to_string(),
+                _ => {}
+            }
+            for link in service.links.iter_mut().flatten() {
+                *link = rename_prefix(link, name, new_name);
+            }
+            for volumes_from in service.volumes_from.iter_mut().flatten() {
+                *volumes_from = rename_prefix(volumes_from, name, new_name);
+            }
+            for mode in [
+                &mut service.network_mode,
+                &mut service.ipc,
+                &mut service.pid,
+            ] {
+                if mode.as_deref() == Some(&format!("service:{}", name)) {
+                    *mode = Some(format!("service:{}", new_name));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// This function returns the services that reference a service through
+    /// `depends_on`, `links`, `extends`, `volumes_from` or a `service:` mode.
+    pub fn service_references(&self, name: &str) -> Vec<String> {
+        let service_mode = format!("service:{}", name);
+        self.services
+            .iter()
+            .filter(|(key, _)| *key != name)
+            .filter(|(_, service)| {
+                service
+                    .depends_on
+                    .as_ref()
+                    .is_some_and(|depends_on| depends_on.services().iter().any(|s| s == name))
+                    || matches!(&service.extends, Some(Extends::Service(s)) if s == name)
+                    || matches!(&service.extends, Some(Extends::Long { service: s, file: None }) if s == name)
+                    || service
+                        .links
+                        .iter()
+                        .flatten()
+                        .chain(service.volumes_from.iter().flatten())
+                        .any(|reference| reference.split(':').next() == Some(name))
+                    || [&service.network_mode, &service.ipc, &service.pid]
+                        .iter()
+                        .any(|mode| mode.as_deref() == Some(service_mode.as_str()))
+            })
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// This function sets an environment variable of a service, a variable
+    /// without a value is passed through from the host.
+    pub fn set_environment(
+        &mut self,
+        service: &str,
+        key: &str,
+        value: Option<&str>,
+    ) -> Result<(), String> {
+        if key.is_empty() || key.contains('=') || key.contains(char::is_whitespace) {
+            return Err(format!("Invalid environment variable name: {}", key));
+        }
+
+        let environment = self
+            .service_mut(service)?
+            .environment
+            .get_or_insert_with(|| ListOrDict::Dict(Default::default()));
+        match environment {
+            ListOrDict::List(entries) => {
+                let entry = match value {
+                    Some(value) => format!("{}={}", key, value),
+                    None => key.to_string(),
+                };
+                match entries.iter_mut().find(|entry| entry_key(entry) == key) {
+                    Some(existing) => *existing = entry,
+                    None => entries.push(entry),
+                }
+            }
+            ListOrDict::Dict(entries) => {
+                entries.insert(
+                    key.to_string(),
+                    value.map(|value| Scalar::String(value.to_string())),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// This function removes an environment variable of a service.
+    pub fn unset_environment(&mut self, service: &str, key: &str) -> Result<(), String> {
+        let entry = self.service_mut(service)?;
+        let removed = match &mut entry.environment {
+            Some(ListOrDict::List(entries)) => {
+                let count = entries.len();
+                entries.retain(|entry| entry_key(entry) != key);
+                entries.len() != count
+            }
+            Some(ListOrDict::Dict(entries)) => entries.shift_remove(key).is_some(),
+            None => false,
+        };
+        if !removed {
+            return Err(format!(
+                "Service {} has no environment variable {}",
+                service, key
+            ));
+        }
+        if matches!(&entry.environment, Some(ListOrDict::List(entries)) if entries.is_empty())
+            || matches!(&entry.environment, Some(ListOrDict::Dict(entries)) if entries.is_empty())
+        {
+            entry.environment = None;
+        }
+        Ok(())
+    }
+
+    /// This function publishes a port of a service,
+    /// `[[host_ip:]published:]target[/protocol]`.
+    pub fn expose_port(&mut self, service: &str, port: &str) -> Result<(), String> {
+        check_port(port)?;
+        let ports = self
+            .service_mut(service)?
+            .ports
+            .get_or_insert_with(Vec::new);
+        if ports.iter().any(|existing| port_matches(existing, port)) {
+            return Err(format!("Service {} already publishes {}", service, port));
+        }
+        ports.push(Port::Short(StringOrNumber::String(port.to_string())));
+        Ok(())
+    }
+
+    /// This function removes a published port of a service, given as it is
+    /// written in the file or as `published:target`.
+    pub fn unexpose_port(&mut self, service: &str, port: &str) -> Result<(), String> {
+        let entry = self.service_mut(service)?;
+        let removed = match &mut entry.ports {
+            Some(ports) => {
+                let count = ports.len();
+                ports.retain(|existing| !port_matches(existing, port));
+                ports.len() != count
+            }
+            None => false,
+        };
+        if !removed {
+            return Err(format!("Service {} doesn't publish {}", service, port));
+        }
+        if entry.ports.as_ref().is_some_and(|ports| ports.is_empty()) {
+            entry.ports = None;
+        }
+        Ok(())
+    }
+
+    /// This function returns a service, or an error naming the services of
+    /// the file if it doesn't exist.
+    pub fn service(&self, name: &str) -> Result<&Service, String> {
+        self.services
+            .get(name)
+            .ok_or_else(|| self.unknown_service(name))
+    }
+
+    /// This function returns a service to edit, or an error naming the
+    /// services of the file if it doesn't exist.
+    pub fn service_mut(&mut self, name: &str) -> Result<&mut Service, String> {
+        let error = self.unknown_service(name);
+        self.services.get_mut(name).ok_or(error)
+    }
+
+    // ## unknown_service(name: &str) -> String
+    // This function returns the error for a service that isn't in the file
+    fn unknown_service(&self, name: &str) -> String {
+        format!(
+            "Service {} doesn't exist, the services are: {}",
+            name,
+            self.services
+                .keys()
+                .cloned()
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+}
+
+/// ## check_name(kind: &str, name: &str) -> Result<(), String>
+/// This function checks a name of a service, network, volume, config or
+/// secret, compose only accepts letters, digits, `_`, `.` and `-`
+///
+/// ### Arguments
+/// - kind: &str - What is named, for the error message
+/// - name: &str - The name to check
+///
+/// ### Returns
+/// - Result<(), String> - Ok if the name is valid, a message describing the error if not
+pub fn check_name(kind: &str, name: &str) -> Result<(), String> {
+    let valid = name.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+    match valid {
+        true => Ok(()),
+        false => Err(format!(
+            "Invalid {} name: {}, use letters, digits, `_`, `.` and `-`",
+            kind, name
+        )),
+    }
+}
+
+/// ## check_port(port: &str) -> Result<(), String>
+/// This function checks the short syntax of a port,
+/// `[[host_ip:]published:]target[/protocol]` where ports may be ranges
+///
+/// ### Arguments
+/// - port: &str - The port to check
+///
+/// ### Returns
+/// - Result<(), String> - Ok if the port is valid, a message describing the error if not
+pub fn check_port(port: &str) -> Result<(), String> {
+    let invalid = || Err(format!("Invalid port: {}", port));
+    let is_port = |value: &str| {
+        let mut bounds = value.splitn(2, '-');
+        bounds.all(|bound| bound.parse::<u16>().is_ok_and(|number| number > 0))
+    };
+
+    let (ports, protocol) = match port.split_once('/') {
+        Some((ports, protocol)) => (ports, Some(protocol)),
+        None => (port, None),
+    };
+    if protocol.is_some_and(|protocol| !matches!(protocol, "tcp" | "udp" | "sctp")) {
+        return invalid();
+    }
+
+    // the host ip may be an IPv6 address with colons, so split from the right
+    let parts = ports.rsplitn(3, ':').collect::<Vec<&str>>();
+    let valid = match parts[..] {
+        [target] => is_port(target),
+        [target, published] => is_port(target) && is_port(published),
+        [target, published, host_ip] => {
+            is_port(target) && (published.is_empty() || is_port(published)) && !host_ip.is_empty()
+        }
+        _ => false,
+    };
+    match valid {
+        true => Ok(()),
+        false => invalid(),
+    }
+}
+
+// ## port_matches(port: &Port, spec: &str) -> bool
+// This function checks if a port is the one written as `spec`, the short
+// syntax as written or `published:target`
+fn port_matches(port: &Port, spec: &str) -> bool {
+    match port {
+        Port::Short(port) => port.to_string() == spec,
+        Port::Long(port) => {
+            let target = port.target.as_ref().map(|target| target.to_string());
+            let published = port
+                .published
+                .as_ref()
+                .map(|published| published.to_string());
+            match (published, target) {
+                (Some(published), Some(target)) => format!("{}:{}", published, target) == spec,
+                (None, Some(target)) => target == spec,
+                _ => false,
+            }
+        }
+    }
+}
+
+// ## entry_key(entry: &str) -> &str
+// This function returns the name of a `KEY=VALUE` environment entry
+fn entry_key(entry: &str) -> &str {
+    entry.split('=').next().unwrap_or(entry)
+}
+
+// ## rename_prefix(reference: &str, name: &str, new_name: &str) -> String
+// This function renames the service of a `service[:option]` reference
+fn rename_prefix(reference: &str, name: &str, new_name: &str) -> String {
+    match reference.split_once(':') {
+        Some((service, option)) if service == name => format!("{}:{}", new_name, option),
+        None if reference == name => new_name.to_string(),
+        _ => reference.to_string(),
+    }
+}
+
+#[test]
+fn test_docker_compose_edit_services() {
+    let mut docker_compose = DockerCompose::from_yaml(
+        "services:\n  api:\n    image: api\n    depends_on: [db]\n    links: ['db:database']\n  db:\n    image: postgres\n  worker:\n    image: worker\n",
+    )
+    .unwrap();
+
+    assert!(docker_compose
+        .add_service("db", Service::default())
+        .is_err());
+    assert!(docker_compose
+        .add_service("web app", Service::default())
+        .is_err());
+    assert_eq!(
+        docker_compose.remove_service("db"),
+        Err("Service db is still used by api".to_string())
+    );
+
+    docker_compose.rename_service("db", "postgres").unwrap();
+    assert_eq!(
+        docker_compose.services.keys().collect::<Vec<&String>>(),
+        vec!["api", "postgres", "worker"]
+    );
+    let api = &docker_compose.services["api"];
+    assert_eq!(
+        api.depends_on.as_ref().unwrap().services(),
+        vec!["postgres".to_string()]
+    );
+    assert_eq!(api.links, Some(vec!["postgres:database".to_string()]));
+
+    docker_compose
+        .set_environment("api", "DEBUG", Some("1"))
+        .unwrap();
+    docker_compose
+        .set_environment("api", "TOKEN", None)
+        .unwrap();
+    assert_eq!(
+        docker_compose.services["api"]
+            .environment
+            .as_ref()
+            .unwrap()
+            .to_map()["DEBUG"],
+        Some("1".to_string())
+    );
+    docker_compose.unset_environment("api", "DEBUG").unwrap();
+    assert!(docker_compose.unset_environment("api", "DEBUG").is_err());
+
+    docker_compose.expose_port("api", "8080:80").unwrap();
+    assert!(docker_compose.expose_port("api", "8080:80").is_err());
+    assert!(docker_compose.expose_port("api", "80/http").is_err());
+    assert!(docker_compose.expose_port("missing", "80").is_err());
+    docker_compose.unexpose_port("api", "8080:80").unwrap();
+    assert_eq!(docker_compose.services["api"].ports, None);
+    assert!(docker_compose.unexpose_port("api", "8080:80").is_err());
+    assert_eq!(docker_compose.services["api"].ports, None);
+
+    docker_compose.remove_service("worker").unwrap();
+    assert!(!docker_compose.services.contains_key("worker"));
+
+    assert!(check_port("127.0.0.1:8080-8081:80-81/udp").is_ok());
+    assert!(check_port("[::1]::80").is_ok());
+    assert!(check_port("0:80").is_err());
+}
@@ -0,0 +1,146 @@
+use super::add_service::check_name;
+use crate::models::docker_compose::service::{is_volume_name, ServiceVolume};
+use crate::models::docker_compose::{DockerCompose, Volume};
+
+impl DockerCompose {
+    /// This function adds a top level volume, it fails if the name is taken.
+    pub fn add_volume(&mut self, name: &str, volume: Option<Volume>) -> Result<(), String> {
+        check_name("volume", name)?;
+        if self.volumes.contains_key(name) {
+            return Err(format!("Volume {} already exists", name));
+        }
+        self.volumes.insert(name.to_string(), volume);
+        Ok(())
+    }
+
+    /// This function removes a top level volume, it fails if a service still
+    /// mounts it.
+    pub fn remove_volume(&mut self, name: &str) -> Result<(), String> {
+        if !self.volumes.contains_key(name) {
+            return Err(format!("Volume {} doesn't exist", name));
+        }
+        let services = self.volume_references(name);
+        if !services.is_empty() {
+            return Err(format!(
+                "Volume {} is still used by {}",
+                name,
+                services.join(", ")
+            ));
+        }
+        self.volumes.shift_remove(name);
+        Ok(())
+    }
+
+    /// This function mounts a named volume of the file or a host path in a
+    /// service.
+    pub fn attach_volume(
+        &mut self,
+        service: &str,
+        source: &str,
+        target: &str,
+        read_only: bool,
+    ) -> Result<(), String> {
+        if is_volume_name(source) && !self.volumes.contains_key(source) {
+            return Err(format!(
+                "Volume {} doesn't exist, add it with `tools compose add volume {}`",
+                source, source
+            ));
+        }
+        if !target.starts_with('/') {
+            return Err(format!("The target {} must be an absolute path", target));
+        }
+
+        let volumes = self
+            .service_mut(service)?
+            .volumes
+            .get_or_insert_with(Vec::new);
+        if volumes
+            .iter()
+            .any(|volume| volume.target().as_deref() == Some(target))
+        {
+            return Err(format!("Service {} already mounts {}", service, target));
+        }
+        volumes.push(ServiceVolume::Short(match read_only {
+            true => format!("{}:{}:ro", source, target),
+            false => format!("{}:{}", source, target),
+        }));
+        Ok(())
+    }
+
+    /// This function removes the mounts of a service with the given source
+    /// or target.
+    pub fn detach_volume(&mut self, service: &str, volume: &str) -> Result<(), String> {
+        let entry = self.service_mut(service)?;
+        let removed = match &mut entry.volumes {
+            Some(volumes) => {
+                let count = volumes.len();
+                volumes.retain(|mount| {
+                    mount.source().as_deref() != Some(volume)
+                        && mount.target().as_deref() != Some(volume)
+                });
+                volumes.len() != count
+            }
+            None => false,
+        };
+        if !removed {
+            return Err(format!("Service {} doesn't mount {}", service, volume));
+        }
+        if entry
+            .volumes
+            .as_ref()
+            .is_some_and(|volumes| volumes.is_empty())
+        {
+            entry.volumes = None;
+        }
+        Ok(())
+    }
+
+    /// This function returns the services mounting a named volume.
+    pub fn volume_references(&self, name: &str) -> Vec<String> {
+        self.services
+            .iter()
+            .filter(|(_, service)| {
+                service
+                    .volumes
+                    .iter()
+                    .flatten()
+                    .any(|volume| volume.is_named() && volume.source().as_deref() == Some(name))
+            })
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+}
+
+#[test]
+fn test_docker_compose_edit_volumes() {
+    let mut docker_compose =
+        DockerCompose::from_yaml("services:\n  db:\n    volumes:\n      - ./init:/init:ro\n")
+            .unwrap();
+
+    assert!(docker_compose
+        .attach_volume("db", "data", "/var/lib/postgresql/data", false)
+        .is_err());
+    docker_compose.add_volume("data", None).unwrap();
+    docker_compose
+        .attach_volume("db", "data", "/var/lib/postgresql/data", false)
+        .unwrap();
+    assert!(docker_compose
+        .attach_volume("db", "./other", "/init", true)
+        .is_err());
+    assert!(docker_compose
+        .attach_volume("db", "data", "relative", false)
+        .is_err());
+    assert_eq!(
+        docker_compose.volume_references("data"),
+        vec!["db".to_string()]
+    );
+    assert!(docker_compose.remove_volume("data").is_err());
+
+    docker_compose.detach_volume("db", "data").unwrap();
+    docker_compose.detach_volume("db", "/init").unwrap();
+    assert_eq!(docker_compose.services["db"].volumes, None);
+    // a failed detach leaves the service as it was
+    assert!(docker_compose.detach_volume("db", "data").is_err());
+    assert_eq!(docker_compose.services["db"].volumes, None);
+    docker_compose.remove_volume("data").unwrap();
+}
@@ -1,27 +1,121 @@
+use crate::helpers::config::environment::get_environment;
+use crate::helpers::docker::docker_compose::merge::merge_compose_texts;
 use crate::helpers::filesystem::path::append_path;
+use crate::helpers::yaml_edit::patch_yaml;
+use crate::models::app_config::ComposeFiles;
 use crate::models::docker_compose::DockerCompose;
 use crate::CONFIG;
+use serde_yaml::{Mapping, Value};
 use std::fs::read_to_string;
 use std::path::PathBuf;
 
 /// ## get_path_to_docker_compose() -> PathBuf
-/// This function returns a PathBuf to the docker compose file
+/// This function returns a PathBuf to the base docker compose file, the
+/// one the tools edit
 ///
 /// ### Returns
 /// - PathBuf
-fn get_path_to_docker_compose() -> PathBuf {
+pub fn get_path_to_docker_compose() -> PathBuf {
     let raw_project_path = &CONFIG.project_path;
     let project_path = PathBuf::from(raw_project_path);
-    let docker_compose_filename = &CONFIG.docker_compose;
+    let docker_compose_filename = CONFIG.docker_compose.base();
     append_path(&project_path, docker_compose_filename)
 }
 
+/// ## get_paths_to_docker_compose() -> Vec<PathBuf>
+/// This function returns the PathBufs to every docker compose file in the
+/// order they are merged, the compose files of the selected environment
+/// if it has any. Like compose without `-f`, a single configured file is
+/// followed by its override file, e.g. `docker-compose.override.yaml`, if
+/// it exists
+///
+/// ### Returns
+/// - Vec<PathBuf>
+pub fn get_paths_to_docker_compose() -> Vec<PathBuf> {
+    let project_path = PathBuf::from(&CONFIG.project_path);
+    if let Some(files) = get_environment().and_then(|(_, environment)| environment.compose_files) {
+        return files
+            .iter()
+            .map(|file| append_path(&project_path, file))
+            .collect();
+    }
+
+    let mut paths = CONFIG
+        .docker_compose
+        .files()
+        .iter()
+        .map(|file| append_path(&project_path, file))
+        .collect::<Vec<PathBuf>>();
+
+    if let ([base], ComposeFiles::File(_)) = (&paths[..], &CONFIG.docker_compose) {
+        let override_path = match (base.file_stem(), base.extension()) {
+            (Some(stem), Some(extension)) => base.with_file_name(format!(
+                "{}.override.{}",
+                stem.to_string_lossy(),
+                extension.to_string_lossy()
+            )),
+            _ => base.with_file_name(format!(
+                "{}.override",
+                base.file_name().unwrap_or_default().to_string_lossy()
+            )),
+        };
+        if override_path.is_file() {
+            paths.push(override_path);
+        }
+    }
+
+    paths
+}
+
+/// ## read_docker_compose_files() -> Result<Vec<(PathBuf, String)>, String>
+/// This function reads every docker compose file in the order they are
+/// merged
+///
+/// ### Returns
+/// - Result<Vec<(PathBuf, String)>, String> - The path and text of every file, or the first file that can't be read
+pub fn read_docker_compose_files() -> Result<Vec<(PathBuf, String)>, String> {
+    get_paths_to_docker_compose()
+        .into_iter()
+        .map(|path| match read_to_string(&path) {
+            Ok(text) => Ok((path, text)),
+            Err(error) => Err(format!("Could not read {}: {}", path.display(), error)),
+        })
+        .collect()
+}
+
 /// ## get_docker_compose_file() -> DockerCompose
-/// This function returns a DockerComposeFile struct from a yaml string
+/// This function returns the effective DockerCompose struct, every
+/// compose file merged into the base file the way compose does, and exits
+/// if a file can't be read or merged
 ///
 /// ### Returns
 /// - DockerComposeFile
 pub fn get_docker_compose_file() -> DockerCompose {
+    let merged = read_docker_compose_files().and_then(|files| {
+        let texts = files
+            .into_iter()
+            .map(|(_, text)| text)
+            .collect::<Vec<String>>();
+        merge_compose_texts(&texts)
+            .map_err(|error| format!("Could not parse docker compose file: {}", error))
+    });
+
+    match merged {
+        Ok(docker_compose) => docker_compose,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// ## get_base_docker_compose_file() -> DockerCompose
+/// This function returns a DockerComposeFile struct of the base docker
+/// compose file alone, to be edited and written back
+///
+/// ### Returns
+/// - DockerComposeFile
+pub fn get_base_docker_compose_file() -> DockerCompose {
     // get absolute path to docker compose file
     let docker_compose_path = get_path_to_docker_compose();
 
@@ -38,7 +132,9 @@ pub fn get_docker_compose_file() -> DockerCompose {
 }
 
 /// ## write_docker_compose_file(docker_compose: &DockerCompose) -> bool
-/// This function writes a DockerCompose struct to the docker compose file
+/// This function writes a DockerCompose struct to the base docker compose file.
+/// An existing file is patched so only the changed entries are rewritten,
+/// and its comments, anchors and order are kept
 ///
 /// ### Arguments
 /// - docker_compose: &DockerCompose - DockerCompose struct to write to file
@@ -47,7 +143,24 @@ pub fn get_docker_compose_file() -> DockerCompose {
 /// - bool: true if successful, false if not
 pub fn write_docker_compose_file(docker_compose: &DockerCompose) -> bool {
     let docker_compose_path = get_path_to_docker_compose();
-    let docker_compose_yaml = docker_compose.to_yaml().unwrap();
-    std::fs::write(docker_compose_path, docker_compose_yaml).unwrap();
-    true
+
+    let docker_compose_yaml = match read_to_string(&docker_compose_path) {
+        Ok(existing) => {
+            let old = DockerCompose::from_yaml(&existing)
+                .and_then(|old| Ok(serde_yaml::to_value(old)?))
+                .expect("Could not parse the existing docker compose file");
+            let new = serde_yaml::to_value(docker_compose)
+                .expect("Could not serialize the docker compose file");
+            patch_yaml(&existing, &old, &new)
+        }
+        // a new file is written in the same style as a patched one
+        Err(_) => patch_yaml(
+            "---\n",
+            &Value::Mapping(Mapping::new()),
+            &serde_yaml::to_value(docker_compose)
+                .expect("Could not serialize the docker compose file"),
+        ),
+    };
+
+    std::fs::write(docker_compose_path, docker_compose_yaml).is_ok()
 }
@@ -0,0 +1,299 @@
+use crate::helpers::container_engine::compose_project_name;
+use crate::helpers::docker::docker_compose::lint::host_ports;
+use crate::models::docker_compose::service::{Build, Dependency, DependsOn, Service};
+use crate::models::docker_compose::DockerCompose;
+use crate::models::project::Project;
+
+/// The port a framework listens on by default, by lowercase name without
+/// punctuation
+const DEFAULT_PORTS: &[(&str, u16)] = &[
+    ("actix", 8080),
+    ("angular", 4200),
+    ("aspnet", 8080),
+    ("axum", 3000),
+    ("django", 8000),
+    ("express", 3000),
+    ("fastapi", 8000),
+    ("flask", 5000),
+    ("gin", 8080),
+    ("laravel", 8000),
+    ("nestjs", 3000),
+    ("nextjs", 3000),
+    ("rails", 3000),
+    ("react", 3000),
+    ("rocket", 8000),
+    ("spring", 8080),
+    ("springboot", 8080),
+    ("svelte", 5173),
+    ("vue", 5173),
+];
+
+/// A service of project.yaml as it is generated into the compose file
+#[derive(Debug, PartialEq)]
+pub struct GeneratedService {
+    pub name: String,            // the name of the compose service
+    pub context: String,         // the build context, the path of the service
+    pub port: Option<u16>,       // the default port of its framework
+    pub networks: Vec<String>,   // the network of its parent and of its dependencies
+    pub depends_on: Vec<String>, // the compose services it depends on
+}
+
+impl DockerCompose {
+    /// This function creates a service for every service of project.yaml
+    /// that has no services of its own, or completes the existing one
+    /// without changing what was written by hand, and returns what changed.
+    pub fn generate_services(&mut self, project: &Project) -> Result<Vec<String>, String> {
+        let mut changes = Vec::new();
+
+        for generated in generated_services(project)? {
+            let name = generated.name.as_str();
+            let mut changed = Vec::new();
+
+            if !self.services.contains_key(name) {
+                self.add_service(name, Service::default())?;
+                changes.push(format!("added service {}", name));
+            }
+            let service = self.service_mut(name)?;
+            if service.image.is_none() && service.build.is_none() && service.extends.is_none() {
+                service.build = Some(Build::Context(generated.context.clone()));
+                changed.push("build");
+            }
+
+            if let (Some(port), None) = (generated.port, &self.service(name)?.ports) {
+                let published = self.published_ports();
+                let host = (port..=u16::MAX)
+                    .find(|host| !published.contains(host))
+                    .ok_or(format!("No free host port for service {}", name))?;
+                self.expose_port(name, &format!("{}:{}", host, port))?;
+                changed.push("ports");
+            }
+
+            for network in &generated.networks {
+                if !self.networks.contains_key(network) {
+                    self.add_network(network, None)?;
+                    changes.push(format!("added network {}", network));
+                }
+                let service = self.service(name)?;
+                let attached = service
+                    .networks
+                    .as_ref()
+                    .is_some_and(|networks| networks.names().contains(network));
+                if !attached && service.network_mode.is_none() {
+                    self.attach_network(name, network)?;
+                    changed.push("networks");
+                }
+            }
+
+            let service = self.service_mut(name)?;
+            for dependency in &generated.depends_on {
+                let depends_on = service
+                    .depends_on
+                    .get_or_insert_with(|| DependsOn::List(Vec::new()));
+                match depends_on {
+                    DependsOn::List(services) if !services.contains(dependency) => {
+                        services.push(dependency.clone())
+                    }
+                    DependsOn::Map(services) if !services.contains_key(dependency) => {
+                        services.insert(dependency.clone(), Dependency::default());
+                    }
+                    _ => continue,
+                }
+                changed.push("depends_on");
+            }
+
+            changed.dedup();
+            if !changed.is_empty() && !changes.contains(&format!("added service {}", name)) {
+                changes.push(format!(
+                    "updated {} of service {}",
+                    changed.join(", "),
+                    name
+                ));
+            }
+        }
+
+        Ok(changes)
+    }
+
+    // ## published_ports() -> Vec<u16>
+    // This function returns the host ports published by every service
+    fn published_ports(&self) -> Vec<u16> {
+        self.services
+            .values()
+            .flat_map(|service| service.ports.iter().flatten())
+            .filter_map(host_ports)
+            .flat_map(|(_, ports, _, _)| ports)
+            .collect()
+    }
+}
+
+/// ## generated_services(project: &Project) -> Result<Vec<GeneratedService>, String>
+/// This function returns the compose services of the services of
+/// project.yaml that have no services of their own, in the order they are
+/// declared. The other projects of the tree become networks
+///
+/// ### Arguments
+/// - project: &Project - The project definition
+///
+/// ### Returns
+/// - Result<Vec<GeneratedService>, String> - The services, or an error if a service depends on a service that isn't declared
+pub fn generated_services(project: &Project) -> Result<Vec<GeneratedService>, String> {
+    // the services with the network of the project that declares them
+    let mut leaves: Vec<(&Project, String)> = Vec::new();
+    collect_leaves(project, &mut leaves);
+
+    let network_of = |name: &str| {
+        leaves
+            .iter()
+            .find(|(service, _)| compose_project_name(&service.name) == name)
+            .map(|(_, network)| network.clone())
+    };
+
+    let mut services = Vec::new();
+    for (service, network) in &leaves {
+        let mut networks = vec![network.clone()];
+        let mut depends_on = Vec::new();
+        for dependency in service.depends_on.iter().flatten() {
+            let dependency_name = compose_project_name(dependency);
+            let Some(dependency_network) = network_of(&dependency_name) else {
+                return Err(format!(
+                    "Service {} depends on {}, which isn't a service of project.yaml",
+                    service.name, dependency
+                ));
+            };
+            if !networks.contains(&dependency_network) {
+                networks.push(dependency_network);
+            }
+            depends_on.push(dependency_name);
+        }
+
+        let path = service.service_path();
+        let context = match path.starts_with('.') || path.starts_with('/') {
+            true => path,
+            false => format!("./{}", path),
+        };
+
+        services.push(GeneratedService {
+            name: compose_project_name(&service.name),
+            context,
+            port: default_port(service),
+            networks,
+            depends_on,
+        });
+    }
+
+    Ok(services)
+}
+
+// ## collect_leaves(project: &Project, leaves: &mut Vec<(&Project, String)>)
+// This function collects the services without services of their own, with
+// the network of their parent
+fn collect_leaves<'a>(project: &'a Project, leaves: &mut Vec<(&'a Project, String)>) {
+    for service in project.services.iter().flatten() {
+        match &service.services {
+            Some(children) if !children.is_empty() => collect_leaves(service, leaves),
+            _ => leaves.push((service, compose_project_name(&project.name))),
+        }
+    }
+}
+
+/// ## default_port(service: &Project) -> Option<u16>
+/// This function returns the default port of the first framework of a
+/// service that has one
+///
+/// ### Arguments
+/// - service: &Project - The service of project.yaml
+///
+/// ### Returns
+/// - Option<u16> - The port, None if no framework of the service is known
+pub fn default_port(service: &Project) -> Option<u16> {
+    service.frameworks.iter().flatten().find_map(|framework| {
+        let framework = framework
+            .to_lowercase()
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>();
+        DEFAULT_PORTS
+            .iter()
+            .find(|(name, _)| *name == framework)
+            .map(|(_, port)| *port)
+    })
+}
+
+#[test]
+fn test_docker_compose_generate_services() {
+    let project = Project::from_yaml(
+        r#"---
+name: Shop
+domain: shop.test
+version: 1.0.0
+description: The shop
+services:
+  - name: api
+    domain: shop.test
+    version: 1.0.0
+    description: The API
+    frameworks: [Actix]
+    path: services/api
+    depends_on: [db]
+  - name: web
+    domain: shop.test
+    version: 1.0.0
+    description: The storefront
+    frameworks: [Next.js]
+    depends_on: [api]
+  - name: data
+    domain: shop.test
+    version: 1.0.0
+    description: The data services
+    services:
+      - name: db
+        domain: shop.test
+        version: 1.0.0
+        description: The database
+"#,
+    )
+    .unwrap();
+    let mut docker_compose = DockerCompose::from_yaml(
+        "services:\n  api:\n    image: shop/api\n    ports: [\"3000:8080\"]\n    depends_on: [cache]\n  cache:\n    image: redis\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        docker_compose.generate_services(&project),
+        Ok(vec![
+            "added network shop".to_string(),
+            "added network data".to_string(),
+            "updated networks, depends_on of service api".to_string(),
+            "added service web".to_string(),
+            "added service db".to_string(),
+        ])
+    );
+
+    let expected = DockerCompose::from_yaml(
+        r#"services:
+  api:
+    image: shop/api
+    ports: ["3000:8080"]
+    depends_on: [cache, db]
+    networks: [shop, data]
+  cache:
+    image: redis
+  web:
+    build: ./web
+    ports: ["3001:3000"]
+    networks: [shop]
+    depends_on: [api]
+  db:
+    build: ./db
+    networks: [data]
+networks:
+  shop:
+  data:
+"#,
+    )
+    .unwrap();
+    assert_eq!(docker_compose, expected);
+
+    // a second run changes nothing
+    assert_eq!(docker_compose.generate_services(&project), Ok(Vec::new()));
+}
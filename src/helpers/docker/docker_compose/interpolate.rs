@@ -0,0 +1,503 @@
+use crate::helpers::docker::docker_compose::merge::merge_compose_files;
+use crate::models::docker_compose::service::{EnvFile, EnvFileEntry};
+use crate::models::docker_compose::types::{ListOrDict, Scalar};
+use crate::models::docker_compose::DockerCompose;
+use indexmap::IndexMap;
+use serde_yaml::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// The variables a compose file is interpolated with, by name
+pub type Variables = HashMap<String, String>;
+
+/// ## load_variables(project_path: &Path, env_files: &[PathBuf]) -> Result<Variables, String>
+/// This function returns the variables compose interpolates a file with,
+/// the `.env` file of the project overridden by the shell environment
+///
+/// ### Arguments
+/// - project_path: &Path - The directory of the compose file, where `.env` is read from
+/// - env_files: &[PathBuf] - The files to read instead of `.env`, later files win and all must exist
+///
+/// ### Returns
+/// - Result<Variables, String> - The variables, or the error of the env file
+pub fn load_variables(project_path: &Path, env_files: &[PathBuf]) -> Result<Variables, String> {
+    let shell = std::env::vars().collect::<Variables>();
+    let mut variables = Variables::new();
+
+    let paths = match env_files {
+        [] => vec![project_path.join(".env")],
+        env_files => env_files.to_vec(),
+    };
+    for path in paths {
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(_) if env_files.is_empty() => continue,
+            Err(error) => return Err(format!("Could not read {}: {}", path.display(), error)),
+        };
+        // an env file can use the variables of the files before it
+        let mut known = variables.clone();
+        known.extend(shell.clone());
+        let entries = parse_env_file(&text, &known)
+            .map_err(|error| format!("{}: {}", path.display(), error))?;
+        variables.extend(entries);
+    }
+
+    variables.extend(shell);
+    Ok(variables)
+}
+
+/// ## apply_profiles(docker_compose: &mut DockerCompose, profiles: &[String])
+/// This function leaves out the services compose doesn't start with the
+/// enabled profiles, a service without profiles is always started and `*`
+/// enables every profile
+///
+/// ### Arguments
+/// - docker_compose: &mut DockerCompose - The compose file
+/// - profiles: &[String] - The enabled profiles
+pub fn apply_profiles(docker_compose: &mut DockerCompose, profiles: &[String]) {
+    if profiles.iter().any(|profile| profile == "*") {
+        return;
+    }
+
+    docker_compose
+        .services
+        .retain(|_, service| match &service.profiles {
+            Some(service_profiles) if !service_profiles.is_empty() => service_profiles
+                .iter()
+                .any(|profile| profiles.contains(profile)),
+            _ => true,
+        });
+}
+
+/// ## resolve(texts: &[String], project_path: &Path, variables: &Variables) -> Result<(DockerCompose, Vec<String>), String>
+/// This function returns the compose files the way compose sees them, with
+/// the variables interpolated, the `<<` merge keys applied, the files
+/// merged and the `env_file` of every service merged into its `environment`
+///
+/// ### Arguments
+/// - texts: &[String] - The texts of the compose files, the base file first
+/// - project_path: &Path - The directory the env files are relative to
+/// - variables: &Variables - The variables to interpolate with
+///
+/// ### Returns
+/// - Result<(DockerCompose, Vec<String>), String> - The resolved file and the warnings about unset variables, or the first error
+pub fn resolve(
+    texts: &[String],
+    project_path: &Path,
+    variables: &Variables,
+) -> Result<(DockerCompose, Vec<String>), String> {
+    let mut warnings = Vec::new();
+
+    let mut files = Vec::new();
+    for text in texts {
+        let mut file: Value = serde_yaml::from_str(text).map_err(|error| error.to_string())?;
+        file.apply_merge().map_err(|error| error.to_string())?;
+        interpolate_value(&mut file, variables, &mut warnings)?;
+        files.push(file);
+    }
+    let value = merge_compose_files(files);
+
+    let mut docker_compose: DockerCompose =
+        serde_yaml::from_value(value).map_err(|error| error.to_string())?;
+
+    for (name, service) in docker_compose.services.iter_mut() {
+        let env_files = match service.env_file.take() {
+            Some(EnvFile::Path(path)) => vec![(path, true)],
+            Some(EnvFile::List(entries)) => entries
+                .into_iter()
+                .map(|entry| match entry {
+                    EnvFileEntry::Path(path) => (path, true),
+                    EnvFileEntry::Long { path, required, .. } => (path, required.unwrap_or(true)),
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        if env_files.is_empty() {
+            continue;
+        }
+
+        // the environment of the file wins over the env files, later env files over earlier ones
+        let mut environment: IndexMap<String, Option<String>> = IndexMap::new();
+        for (path, required) in env_files {
+            let path = project_path.join(path);
+            let text = match std::fs::read_to_string(&path) {
+                Ok(text) => text,
+                Err(_) if !required => continue,
+                Err(error) => {
+                    return Err(format!(
+                        "Could not read the env file {} of service {}: {}",
+                        path.display(),
+                        name,
+                        error
+                    ))
+                }
+            };
+            let entries = parse_env_file(&text, variables)
+                .map_err(|error| format!("{}: {}", path.display(), error))?;
+            environment.extend(entries.into_iter().map(|(key, value)| (key, Some(value))));
+        }
+        if let Some(existing) = &service.environment {
+            environment.extend(existing.to_map());
+        }
+
+        service.environment = Some(ListOrDict::Dict(
+            environment
+                .into_iter()
+                .map(|(key, value)| {
+                    // a variable without a value takes the value of the shell
+                    let value = value.or_else(|| variables.get(&key).cloned());
+                    (key, value.map(Scalar::String))
+                })
+                .collect(),
+        ));
+    }
+
+    Ok((docker_compose, warnings))
+}
+
+/// ## interpolate(text: &str, variables: &Variables, warnings: &mut Vec<String>) -> Result<String, String>
+/// This function replaces the variables of a string the way compose does,
+/// `$VAR`, `${VAR}`, `${VAR:-default}` and `${VAR-default}`,
+/// `${VAR:?error}` and `${VAR?error}`, `${VAR:+replacement}` and
+/// `${VAR+replacement}`, and `$$` for a literal `$`. The forms with `:`
+/// also treat an empty variable as unset
+///
+/// ### Arguments
+/// - text: &str - The string to interpolate
+/// - variables: &Variables - The values of the variables
+/// - warnings: &mut Vec<String> - The unset variables without a default are reported here
+///
+/// ### Returns
+/// - Result<String, String> - The interpolated string, or the error of a required variable
+pub fn interpolate(
+    text: &str,
+    variables: &Variables,
+    warnings: &mut Vec<String>,
+) -> Result<String, String> {
+    let mut result = String::new();
+    let mut rest = text;
+
+    while let Some(dollar) = rest.find('$') {
+        result.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+
+        if let Some(after) = after.strip_prefix('$') {
+            result.push('$');
+            rest = after;
+        } else if let Some(braced) = after.strip_prefix('{') {
+            let close = matching_brace(braced)
+                .ok_or_else(|| format!("Invalid interpolation format: {}", text))?;
+            result.push_str(&substitute(&braced[..close], variables, warnings, text)?);
+            rest = &braced[close + 1..];
+        } else {
+            let length = variable_name_length(after);
+            if length == 0 {
+                // a `$` that doesn't start a variable is kept
+                result.push('$');
+            } else {
+                let name = &after[..length];
+                result.push_str(&lookup(name, variables, warnings));
+            }
+            rest = &after[length..];
+        }
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// ## parse_env_file(text: &str, variables: &Variables) -> Result<Vec<(String, String)>, String>
+/// This function reads the `KEY=VALUE` lines of an env file. Single quoted
+/// values are taken literally, double quoted values may span lines and
+/// use `\n` escapes, and unquoted values end at a ` #` comment. Both are
+/// interpolated with the earlier entries and the given variables, and a
+/// `KEY` without a value takes the value of the given variables
+///
+/// ### Arguments
+/// - text: &str - The text of the env file
+/// - variables: &Variables - The variables outside of the file
+///
+/// ### Returns
+/// - Result<Vec<(String, String)>, String> - The entries in the order of the file, or the error and its line
+pub fn parse_env_file(text: &str, variables: &Variables) -> Result<Vec<(String, String)>, String> {
+    let mut entries: Vec<(String, String)> = Vec::new();
+    let mut lines = text.lines().enumerate();
+
+    while let Some((number, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+
+        let known = || {
+            let mut known = variables.clone();
+            known.extend(entries.iter().cloned());
+            known
+        };
+        let Some((key, value)) = line.split_once('=') else {
+            if let Some(value) = known().get(line) {
+                entries.push((line.to_string(), value.clone()));
+            }
+            continue;
+        };
+        let key = key.trim();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err(format!(
+                "line {}: invalid variable name `{}`",
+                number + 1,
+                key
+            ));
+        }
+
+        let value = value.trim_start();
+        let value = match value.chars().next() {
+            Some(quote @ ('\'' | '"')) => {
+                // quoted values continue until the closing quote
+                let mut quoted = value[1..].to_string();
+                while closing_quote(&quoted, quote).is_none() {
+                    match lines.next() {
+                        Some((_, next)) => {
+                            quoted.push('\n');
+                            quoted.push_str(next);
+                        }
+                        None => {
+                            return Err(format!(
+                                "line {}: the value of {} has no closing quote",
+                                number + 1,
+                                key
+                            ))
+                        }
+                    }
+                }
+                let quoted = &quoted[..closing_quote(&quoted, quote).unwrap_or_default()];
+                match quote {
+                    '\'' => quoted.to_string(),
+                    _ => interpolate(&unescape(quoted), &known(), &mut Vec::new())
+                        .map_err(|error| format!("line {}: {}", number + 1, error))?,
+                }
+            }
+            _ => {
+                let value = match value.find(" #") {
+                    Some(comment) => &value[..comment],
+                    None => value,
+                };
+                interpolate(value.trim_end(), &known(), &mut Vec::new())
+                    .map_err(|error| format!("line {}: {}", number + 1, error))?
+            }
+        };
+
+        entries.retain(|(existing, _)| existing != key);
+        entries.push((key.to_string(), value));
+    }
+
+    Ok(entries)
+}
+
+// ## interpolate_value(value: &mut Value, variables: &Variables, warnings: &mut Vec<String>) -> Result<(), String>
+// This function interpolates every string of a YAML value, keys are kept
+fn interpolate_value(
+    value: &mut Value,
+    variables: &Variables,
+    warnings: &mut Vec<String>,
+) -> Result<(), String> {
+    match value {
+        Value::String(string) => *string = interpolate(string, variables, warnings)?,
+        Value::Sequence(sequence) => {
+            for item in sequence {
+                interpolate_value(item, variables, warnings)?;
+            }
+        }
+        Value::Mapping(mapping) => {
+            for (_, item) in mapping.iter_mut() {
+                interpolate_value(item, variables, warnings)?;
+            }
+        }
+        Value::Tagged(tagged) => interpolate_value(&mut tagged.value, variables, warnings)?,
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+    Ok(())
+}
+
+// ## substitute(expression: &str, variables, warnings, text) -> Result<String, String>
+// This function returns the value of the expression between `${` and `}`
+fn substitute(
+    expression: &str,
+    variables: &Variables,
+    warnings: &mut Vec<String>,
+    text: &str,
+) -> Result<String, String> {
+    let length = variable_name_length(expression);
+    let (name, operator) = expression.split_at(length);
+    if name.is_empty() {
+        return Err(format!("Invalid interpolation format: {}", text));
+    }
+
+    let value = variables.get(name);
+    let (colon, operator) = match operator.strip_prefix(':') {
+        Some(operator) => (true, operator),
+        None => (false, operator),
+    };
+    // with `:` an empty variable counts as unset
+    let set = value.is_some_and(|value| !colon || !value.is_empty());
+
+    match operator.chars().next() {
+        None if !colon => Ok(lookup(name, variables, warnings)),
+        Some('-') if set => Ok(value.cloned().unwrap_or_default()),
+        Some('-') => interpolate(&operator[1..], variables, warnings),
+        Some('?') if set => Ok(value.cloned().unwrap_or_default()),
+        Some('?') => {
+            let message = interpolate(&operator[1..], variables, warnings)?;
+            Err(match message.is_empty() {
+                true => format!("required variable {} is missing a value", name),
+                false => format!("required variable {} is missing a value: {}", name, message),
+            })
+        }
+        Some('+') if set => interpolate(&operator[1..], variables, warnings),
+        Some('+') => Ok(String::new()),
+        _ => Err(format!("Invalid interpolation format: {}", text)),
+    }
+}
+
+// ## lookup(name: &str, variables: &Variables, warnings: &mut Vec<String>) -> String
+// This function returns the value of a variable, blank with a warning if
+// it isn't set
+fn lookup(name: &str, variables: &Variables, warnings: &mut Vec<String>) -> String {
+    match variables.get(name) {
+        Some(value) => value.clone(),
+        None => {
+            let warning = format!(
+                "The \"{}\" variable is not set. Defaulting to a blank string.",
+                name
+            );
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+            String::new()
+        }
+    }
+}
+
+// ## variable_name_length(text: &str) -> usize
+// This function returns the length of the variable name at the start of a
+// string, 0 if it doesn't start with one
+fn variable_name_length(text: &str) -> usize {
+    if text.starts_with(|c: char| c.is_ascii_digit()) {
+        return 0;
+    }
+    text.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(text.len())
+}
+
+// ## matching_brace(text: &str) -> Option<usize>
+// This function returns the index of the `}` closing a `${`, nested `${`
+// in defaults included
+fn matching_brace(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut previous = ' ';
+
+    for (index, c) in text.char_indices() {
+        match c {
+            '{' if previous == '$' => depth += 1,
+            '}' if depth == 0 => return Some(index),
+            '}' => depth -= 1,
+            _ => {}
+        }
+        previous = c;
+    }
+
+    None
+}
+
+// ## closing_quote(text: &str, quote: char) -> Option<usize>
+// This function returns the index of the quote closing a value, escaped
+// double quotes don't close it
+fn closing_quote(text: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+
+    for (index, c) in text.char_indices() {
+        match c {
+            '\\' if quote == '"' && !escaped => {
+                escaped = true;
+                continue;
+            }
+            c if c == quote && !escaped => return Some(index),
+            _ => {}
+        }
+        escaped = false;
+    }
+
+    None
+}
+
+// ## unescape(text: &str) -> String
+// This function replaces the escapes of a double quoted value
+fn unescape(text: &str) -> String {
+    let mut result = String::new();
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            // keep `\$` escaped for the interpolation
+            Some('$') => result.push_str("$$"),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+
+    result
+}
+
+#[test]
+fn test_docker_compose_interpolate() {
+    let variables = Variables::from([
+        ("TAG".to_string(), "1.2.0".to_string()),
+        ("EMPTY".to_string(), String::new()),
+    ]);
+    let mut warnings = Vec::new();
+    let mut run = |text: &str| interpolate(text, &variables, &mut warnings);
+
+    assert_eq!(run("api:${TAG}"), Ok("api:1.2.0".to_string()));
+    assert_eq!(run("api:$TAG-slim"), Ok("api:1.2.0-slim".to_string()));
+    assert_eq!(run("${EMPTY:-latest}"), Ok("latest".to_string()));
+    assert_eq!(run("${EMPTY-latest}"), Ok(String::new()));
+    assert_eq!(run("${UNSET:-${TAG}}"), Ok("1.2.0".to_string()));
+    assert_eq!(run("${TAG:+set}${UNSET+set}"), Ok("set".to_string()));
+    assert_eq!(run("cost $$5 ${TAG?}"), Ok("cost $5 1.2.0".to_string()));
+    assert_eq!(
+        run("${UNSET:?the token is needed}"),
+        Err("required variable UNSET is missing a value: the token is needed".to_string())
+    );
+    assert!(run("${TAG").is_err());
+    assert_eq!(run("$UNSET"), Ok(String::new()));
+    assert_eq!(
+        warnings,
+        vec!["The \"UNSET\" variable is not set. Defaulting to a blank string.".to_string()]
+    );
+
+    let env_file = "# settings\nexport HOST=db\nURL=\"postgres://${HOST}/shop\\n\"\nRAW='${HOST}'\nNAME=shop # inline\nTAG\n";
+    assert_eq!(
+        parse_env_file(env_file, &variables),
+        Ok(vec![
+            ("HOST".to_string(), "db".to_string()),
+            ("URL".to_string(), "postgres://db/shop\n".to_string()),
+            ("RAW".to_string(), "${HOST}".to_string()),
+            ("NAME".to_string(), "shop".to_string()),
+            ("TAG".to_string(), "1.2.0".to_string()),
+        ])
+    );
+
+    let mut docker_compose = DockerCompose::from_yaml(
+        "services:\n  api:\n    image: api\n  debugger:\n    image: debug\n    profiles: [debug]\n",
+    )
+    .unwrap();
+    apply_profiles(&mut docker_compose, &[]);
+    assert_eq!(docker_compose.services.keys().collect::<Vec<_>>(), ["api"]);
+}
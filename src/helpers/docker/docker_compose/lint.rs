@@ -0,0 +1,385 @@
+use crate::helpers::graph::ServiceGraph;
+use crate::helpers::yaml_edit::locate;
+use crate::models::docker_compose::service::{Build, Port, ServiceVolume};
+use crate::models::docker_compose::DockerCompose;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// How bad a problem found by `tools compose lint` is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Error,   // compose will refuse the file or the project won't start
+    Warning, // the file works but probably not as intended
+}
+
+/// A problem of the compose files
+#[derive(Debug, PartialEq)]
+pub struct LintIssue {
+    pub severity: Severity,
+    pub file: Option<String>, // the file the problem is in, None if it isn't in any of the texts
+    pub line: Option<usize>,  // the 1-based line of the problem in the file
+    pub message: String,
+}
+
+/// ## lint(docker_compose: &DockerCompose, files: &[(String, String)], project_path: &Path) -> Vec<LintIssue>
+/// This function checks a compose file for references to undefined
+/// networks, volumes, secrets, configs and services, dependency cycles,
+/// host ports published twice, missing build contexts, services without
+/// an image, and keys compose doesn't know
+///
+/// ### Arguments
+/// - docker_compose: &DockerCompose - The parsed compose file, merged from the files
+/// - files: &[(String, String)] - The name and text of every compose file, to find the line of every problem
+/// - project_path: &Path - The directory the build contexts are relative to
+///
+/// ### Returns
+/// - Vec<LintIssue> - The problems sorted by file and line, empty if the file is fine
+pub fn lint(
+    docker_compose: &DockerCompose,
+    files: &[(String, String)],
+    project_path: &Path,
+) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    let mut issue = |severity: Severity, path: &[&str], message: String| {
+        // the file that comes closest to the node, a later file on a tie as it overrides the earlier ones
+        let location = files
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (name, text))| {
+                locate(text, path).map(|(line, depth)| (depth, index, name, line))
+            })
+            .max();
+        issues.push(LintIssue {
+            severity,
+            file: location.map(|(_, _, name, _)| name.clone()),
+            line: location.map(|(_, _, _, line)| line),
+            message,
+        })
+    };
+
+    for key in docker_compose.extensions.keys() {
+        if !key.starts_with("x-") {
+            issue(
+                Severity::Warning,
+                &[key],
+                format!("unknown top level key `{}`", key),
+            );
+        }
+    }
+
+    for (name, service) in &docker_compose.services {
+        for key in service.extensions.keys() {
+            if !key.starts_with("x-") {
+                issue(
+                    Severity::Warning,
+                    &service_path(name, &[key]),
+                    format!(
+                        "unknown key `{}` in service {}, is it indented correctly?",
+                        key, name
+                    ),
+                );
+            }
+        }
+
+        if service.image.is_none() && service.build.is_none() && service.extends.is_none() {
+            issue(
+                Severity::Error,
+                &service_path(name, &[]),
+                format!("service {} has neither an image nor a build", name),
+            );
+        }
+
+        for network in service
+            .networks
+            .iter()
+            .flat_map(|networks| networks.names())
+        {
+            if network != "default" && !docker_compose.networks.contains_key(&network) {
+                issue(
+                    Severity::Error,
+                    &service_path(name, &["networks", &network]),
+                    format!("service {} uses the undefined network {}", name, network),
+                );
+            }
+        }
+
+        for volume in service.volumes.iter().flatten() {
+            let Some(source) = volume.source().filter(|_| volume.is_named()) else {
+                continue;
+            };
+            if !docker_compose.volumes.contains_key(&source) {
+                let item = match volume {
+                    ServiceVolume::Short(item) => item.as_str(),
+                    ServiceVolume::Long(_) => "",
+                };
+                issue(
+                    Severity::Error,
+                    &service_path(name, &["volumes", item]),
+                    format!("service {} uses the undefined volume {}", name, source),
+                );
+            }
+        }
+
+        for secret in service.secrets.iter().flatten() {
+            if !docker_compose.secrets.contains_key(secret.source()) {
+                issue(
+                    Severity::Error,
+                    &service_path(name, &["secrets", secret.source()]),
+                    format!(
+                        "service {} uses the undefined secret {}",
+                        name,
+                        secret.source()
+                    ),
+                );
+            }
+        }
+
+        for config in service.configs.iter().flatten() {
+            if !docker_compose.configs.contains_key(config.source()) {
+                issue(
+                    Severity::Error,
+                    &service_path(name, &["configs", config.source()]),
+                    format!(
+                        "service {} uses the undefined config {}",
+                        name,
+                        config.source()
+                    ),
+                );
+            }
+        }
+
+        for dependency in service
+            .depends_on
+            .iter()
+            .flat_map(|depends_on| depends_on.services())
+        {
+            if !docker_compose.services.contains_key(&dependency) {
+                issue(
+                    Severity::Error,
+                    &service_path(name, &["depends_on", &dependency]),
+                    format!(
+                        "service {} depends on the undefined service {}",
+                        name, dependency
+                    ),
+                );
+            }
+        }
+
+        let context = match &service.build {
+            Some(Build::Context(context)) => Some(context.clone()),
+            Some(Build::Config(build)) => Some(build.context.clone().unwrap_or(".".to_string())),
+            None => None,
+        };
+        if let Some(context) = context.filter(|context| is_local_path(context)) {
+            if !project_path.join(&context).is_dir() {
+                issue(
+                    Severity::Error,
+                    &service_path(name, &["build", "context"]),
+                    format!(
+                        "the build context {} of service {} doesn't exist",
+                        context, name
+                    ),
+                );
+            }
+        }
+    }
+
+    for cycle in ServiceGraph::from_compose(docker_compose).cycles() {
+        issue(
+            Severity::Error,
+            &["services", &cycle[0], "depends_on", &cycle[1]],
+            format!("dependency cycle: {}", cycle.join(" -> ")),
+        );
+    }
+
+    // the services publishing every host port, by protocol and port
+    let mut published: HashMap<(String, u16), Vec<(String, String)>> = HashMap::new();
+    for (name, service) in &docker_compose.services {
+        for port in service.ports.iter().flatten() {
+            let Some((host_ip, ports, protocol, item)) = host_ports(port) else {
+                continue;
+            };
+            for number in ports {
+                let users = published.entry((protocol.clone(), number)).or_default();
+                if let Some((other, _)) = users.iter().find(|(_, other_ip)| {
+                    host_ip.is_empty() || other_ip.is_empty() || *other_ip == host_ip
+                }) {
+                    issue(
+                        Severity::Error,
+                        &["services", name, "ports", &item],
+                        format!(
+                            "host port {}/{} of service {} is already published by {}",
+                            number, protocol, name, other
+                        ),
+                    );
+                    break;
+                }
+                users.push((name.clone(), host_ip.clone()));
+            }
+        }
+    }
+
+    let position = |issue: &LintIssue| {
+        let file = issue.file.as_ref();
+        let index = files.iter().position(|(name, _)| Some(name) == file);
+        (
+            index.unwrap_or(files.len()),
+            issue.line.unwrap_or(usize::MAX),
+        )
+    };
+    issues.sort_by_key(position);
+    issues
+}
+
+// ## service_path(name: &str, path: &[&str]) -> Vec<&str>
+// This function returns the path of a node of a service in the file
+fn service_path<'a>(name: &'a str, path: &[&'a str]) -> Vec<&'a str> {
+    [&["services", name][..], path].concat()
+}
+
+/// ## host_ports(port: &Port) -> Option<(String, Vec<u16>, String, String)>
+/// This function returns the host ip, the host ports, the protocol and the
+/// text of a published port, None if no fixed host port is published
+///
+/// ### Arguments
+/// - port: &Port - A port of a service
+///
+/// ### Returns
+/// - Option<(String, Vec<u16>, String, String)> - The host ip, empty for every address, the ports, the protocol and the short syntax
+pub fn host_ports(port: &Port) -> Option<(String, Vec<u16>, String, String)> {
+    let (host_ip, published, protocol, item) = match port {
+        Port::Short(short) => {
+            let item = short.to_string();
+            let (ports, protocol) = match item.split_once('/') {
+                Some((ports, protocol)) => (ports.to_string(), protocol.to_string()),
+                None => (item.clone(), "tcp".to_string()),
+            };
+            match ports.rsplitn(3, ':').collect::<Vec<&str>>()[..] {
+                [_, published] => (String::new(), published.to_string(), protocol, item),
+                [_, published, host_ip] => {
+                    (host_ip.to_string(), published.to_string(), protocol, item)
+                }
+                _ => return None,
+            }
+        }
+        Port::Long(long) => (
+            long.host_ip.clone().unwrap_or_default(),
+            long.published.as_ref()?.to_string(),
+            long.protocol.clone().unwrap_or("tcp".to_string()),
+            String::new(),
+        ),
+    };
+
+    let host_ip = match host_ip.as_str() {
+        "0.0.0.0" | "::" | "[::]" => String::new(),
+        _ => host_ip,
+    };
+    let ports = match published.split_once('-') {
+        Some((first, last)) => (first.parse::<u16>().ok()?..=last.parse::<u16>().ok()?).collect(),
+        None => vec![published.parse::<u16>().ok()?],
+    };
+    Some((host_ip, ports, protocol, item))
+}
+
+// ## is_local_path(context: &str) -> bool
+// This function checks if a build context is a directory on disk, not a
+// git repository, a URL or a path built from variables
+fn is_local_path(context: &str) -> bool {
+    !(context.contains("://")
+        || context.starts_with("git@")
+        || context.starts_with("github.com/")
+        || context.contains('$'))
+}
+
+#[test]
+fn test_docker_compose_lint() {
+    let text = r#"services:
+  api:
+    build: ./missing
+    depends_on:
+      - db
+      - cache
+    ports:
+      - "8080:80"
+    networks:
+      - backend
+  db:
+    image: postgres
+    depends_on: [api]
+    ports:
+      - "127.0.0.1:8080:5432"
+    volumes:
+      - data:/var/lib/postgresql/data
+  worker:
+    build:
+    context: ./worker
+"#;
+    let docker_compose = DockerCompose::from_yaml(text).unwrap();
+    let files = [("docker-compose.yaml".to_string(), text.to_string())];
+    let issues = lint(&docker_compose, &files, Path::new("/nonexistent"))
+        .into_iter()
+        .map(|issue| (issue.line, issue.message))
+        .collect::<Vec<(Option<usize>, String)>>();
+
+    assert_eq!(
+        issues,
+        vec![
+            (
+                Some(3),
+                "the build context ./missing of service api doesn't exist".to_string()
+            ),
+            (Some(5), "dependency cycle: api -> db -> api".to_string()),
+            (
+                Some(6),
+                "service api depends on the undefined service cache".to_string()
+            ),
+            (
+                Some(10),
+                "service api uses the undefined network backend".to_string()
+            ),
+            (
+                Some(15),
+                "host port 8080/tcp of service db is already published by api".to_string()
+            ),
+            (
+                Some(17),
+                "service db uses the undefined volume data".to_string()
+            ),
+            (
+                Some(18),
+                "service worker has neither an image nor a build".to_string()
+            ),
+            (
+                Some(20),
+                "unknown key `context` in service worker, is it indented correctly?".to_string()
+            ),
+        ]
+    );
+
+    // a problem brought in by an override file is reported in that file
+    let files = [
+        (
+            "docker-compose.yaml".to_string(),
+            "services:\n  db:\n    image: postgres\n".to_string(),
+        ),
+        (
+            "docker-compose.override.yaml".to_string(),
+            "services:\n  db:\n    networks:\n      - debug\n".to_string(),
+        ),
+    ];
+    let texts = files
+        .iter()
+        .map(|(_, text)| text.clone())
+        .collect::<Vec<String>>();
+    let docker_compose =
+        crate::helpers::docker::docker_compose::merge::merge_compose_texts(&texts).unwrap();
+    assert_eq!(
+        lint(&docker_compose, &files, Path::new("/nonexistent")),
+        vec![LintIssue {
+            severity: Severity::Error,
+            file: Some("docker-compose.override.yaml".to_string()),
+            line: Some(4),
+            message: "service db uses the undefined network debug".to_string(),
+        }]
+    );
+}
@@ -0,0 +1,370 @@
+use crate::models::docker_compose::DockerCompose;
+use serde_yaml::{Mapping, Value};
+
+/// How a field of an override file is merged into the field of the base file
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MergeRule {
+    Replace,   // the override wins, e.g. `command`
+    KeyValue,  // `KEY=VALUE` lists and maps are merged by key, e.g. `environment`
+    Mapping,   // lists of names and maps are merged by name, e.g. `depends_on`
+    ByTarget,  // items are merged by the path they are mounted at, e.g. `volumes`
+    BySource,  // items are merged by the name of the config or secret
+    Recursive, // maps are merged, sequences appended and scalars replaced
+}
+
+/// ## merge_compose_files(files: Vec<Value>) -> Value
+/// This function merges compose files the way compose does, every file
+/// overrides the files before it. Maps are merged, sequences like `ports`
+/// are appended, `environment` and `labels` are merged by key, `volumes`
+/// by their target, and `command` and `entrypoint` replaced. A value
+/// tagged `!reset` removes the field and `!override` replaces it instead
+/// of merging
+///
+/// ### Arguments
+/// - files: Vec<Value> - The parsed compose files, the base file first
+///
+/// ### Returns
+/// - Value - The effective compose file
+pub fn merge_compose_files(files: Vec<Value>) -> Value {
+    let mut merged = Value::Mapping(Mapping::new());
+
+    // an empty file overrides nothing
+    for file in files.into_iter().filter(|file| !file.is_null()) {
+        merge_value(&mut merged, file, &[]);
+    }
+
+    strip_tags(merged).unwrap_or(Value::Null)
+}
+
+/// ## merge_compose_texts(texts: &[String]) -> Result<DockerCompose, String>
+/// This function parses compose files, applies their `<<` merge keys and
+/// merges them with `merge_compose_files`
+///
+/// ### Arguments
+/// - texts: &[String] - The texts of the compose files, the base file first
+///
+/// ### Returns
+/// - Result<DockerCompose, String> - The effective compose file, or the first parse error
+pub fn merge_compose_texts(texts: &[String]) -> Result<DockerCompose, String> {
+    let mut files = Vec::new();
+    for text in texts {
+        let mut file: Value = serde_yaml::from_str(text).map_err(|error| error.to_string())?;
+        file.apply_merge().map_err(|error| error.to_string())?;
+        files.push(file);
+    }
+
+    serde_yaml::from_value(merge_compose_files(files)).map_err(|error| error.to_string())
+}
+
+// ## merge_value(base: &mut Value, overlay: Value, path: &[String])
+// This function merges a value of an override file into the base value at
+// the same path
+fn merge_value(base: &mut Value, overlay: Value, path: &[String]) {
+    let overlay = match overlay {
+        Value::Tagged(tagged) if tagged.tag == "override" => {
+            *base = strip_tags(tagged.value).unwrap_or(Value::Null);
+            return;
+        }
+        Value::Tagged(tagged) if tagged.tag != "reset" => tagged.value,
+        overlay => overlay,
+    };
+
+    match (merge_rule(path), base, overlay) {
+        (MergeRule::Replace, base, overlay) => *base = strip_tags(overlay).unwrap_or(Value::Null),
+        (MergeRule::KeyValue, base, overlay) => {
+            let separators: &[char] = match path.last().map(|key| key.as_str()) {
+                Some("extra_hosts") => &['=', ':'],
+                _ => &['='],
+            };
+            let mut merged = key_value_mapping(base, separators);
+            merged.extend(key_value_mapping(&overlay, separators));
+            *base = Value::Mapping(merged);
+        }
+        (MergeRule::Mapping, base @ Value::Sequence(_), Value::Sequence(overlay)) => {
+            append_unique(base, overlay)
+        }
+        (MergeRule::Mapping, base, overlay) => {
+            let mut merged = names_mapping(base, path);
+            merge_mapping(&mut merged, names_mapping(&overlay, path), path);
+            *base = Value::Mapping(merged);
+        }
+        (
+            rule @ (MergeRule::ByTarget | MergeRule::BySource),
+            Value::Sequence(base),
+            Value::Sequence(overlay),
+        ) => {
+            let key = match rule {
+                MergeRule::ByTarget => mount_target,
+                _ => reference_source,
+            };
+            for item in overlay.into_iter().filter_map(strip_tags) {
+                match base
+                    .iter_mut()
+                    .find(|existing| key(existing).is_some() && key(existing) == key(&item))
+                {
+                    Some(existing) => *existing = item,
+                    None => base.push(item),
+                }
+            }
+        }
+        (_, Value::Mapping(base), Value::Mapping(overlay)) => merge_mapping(base, overlay, path),
+        (_, base @ Value::Sequence(_), Value::Sequence(overlay)) => append_unique(base, overlay),
+        (_, base, overlay) => *base = strip_tags(overlay).unwrap_or(Value::Null),
+    }
+}
+
+// ## merge_mapping(base: &mut Mapping, overlay: Mapping, path: &[String])
+// This function merges the entries of a map, `!reset` entries are removed
+fn merge_mapping(base: &mut Mapping, overlay: Mapping, path: &[String]) {
+    for (key, value) in overlay {
+        if matches!(&value, Value::Tagged(tagged) if tagged.tag == "reset") {
+            base.remove(&key);
+            continue;
+        }
+
+        let child_path = [path, &[key_name(&key)]].concat();
+        match base.get_mut(&key) {
+            Some(existing) => merge_value(existing, value, &child_path),
+            None => {
+                if let Some(value) = strip_tags(value) {
+                    base.insert(key, value);
+                }
+            }
+        }
+    }
+}
+
+// ## merge_rule(path: &[String]) -> MergeRule
+// This function returns how the field at a path is merged
+fn merge_rule(path: &[String]) -> MergeRule {
+    let path = path.iter().map(|key| key.as_str()).collect::<Vec<&str>>();
+
+    match path[..] {
+        ["services", _, "command" | "entrypoint"] | ["services", _, "healthcheck", "test"] => {
+            MergeRule::Replace
+        }
+        ["services", _, "environment" | "labels" | "annotations" | "extra_hosts" | "sysctls"]
+        | ["services", _, "build", "args" | "labels" | "extra_hosts"] => MergeRule::KeyValue,
+        ["services", _, "depends_on" | "networks"] => MergeRule::Mapping,
+        ["services", _, "volumes" | "devices"] => MergeRule::ByTarget,
+        ["services", _, "secrets" | "configs"] => MergeRule::BySource,
+        _ => MergeRule::Recursive,
+    }
+}
+
+// ## strip_tags(value: Value) -> Option<Value>
+// This function removes the merge tags of a value that has nothing to be
+// merged into, None for a `!reset` value
+fn strip_tags(value: Value) -> Option<Value> {
+    match value {
+        Value::Tagged(tagged) if tagged.tag == "reset" => None,
+        Value::Tagged(tagged) if tagged.tag == "override" => strip_tags(tagged.value),
+        Value::Mapping(mapping) => Some(Value::Mapping(
+            mapping
+                .into_iter()
+                .filter_map(|(key, value)| strip_tags(value).map(|value| (key, value)))
+                .collect(),
+        )),
+        Value::Sequence(sequence) => Some(Value::Sequence(
+            sequence.into_iter().filter_map(strip_tags).collect(),
+        )),
+        value => Some(value),
+    }
+}
+
+// ## append_unique(base: &mut Value, overlay: Vec<Value>)
+// This function appends the items of a sequence that the base doesn't have
+fn append_unique(base: &mut Value, overlay: Vec<Value>) {
+    if let Value::Sequence(base) = base {
+        for item in overlay.into_iter().filter_map(strip_tags) {
+            if !base.contains(&item) {
+                base.push(item);
+            }
+        }
+    }
+}
+
+// ## key_value_mapping(value: &Value, separators: &[char]) -> Mapping
+// This function returns a `KEY=VALUE` list or a map as a map
+fn key_value_mapping(value: &Value, separators: &[char]) -> Mapping {
+    match value {
+        Value::Mapping(mapping) => strip_tags(Value::Mapping(mapping.clone()))
+            .and_then(|value| value.as_mapping().cloned())
+            .unwrap_or_default(),
+        Value::Sequence(entries) => entries
+            .iter()
+            .filter_map(|entry| entry.as_str())
+            .map(|entry| match entry.split_once(separators) {
+                Some((key, value)) => (Value::from(key), Value::from(value)),
+                None => (Value::from(entry), Value::Null),
+            })
+            .collect(),
+        _ => Mapping::new(),
+    }
+}
+
+// ## names_mapping(value: &Value, path: &[String]) -> Mapping
+// This function returns a list of names or a map as a map, names of
+// `depends_on` wait for the service to start as in compose, other names
+// have no options
+fn names_mapping(value: &Value, path: &[String]) -> Mapping {
+    let options = match path.last().map(|key| key.as_str()) {
+        Some("depends_on") => Value::Mapping(Mapping::from_iter([(
+            Value::from("condition"),
+            Value::from("service_started"),
+        )])),
+        _ => Value::Null,
+    };
+
+    match value {
+        Value::Mapping(mapping) => mapping.clone(),
+        Value::Sequence(names) => names
+            .iter()
+            .map(|name| (name.clone(), options.clone()))
+            .collect(),
+        _ => Mapping::new(),
+    }
+}
+
+// ## mount_target(item: &Value) -> Option<String>
+// This function returns the path a volume or device is mounted at
+fn mount_target(item: &Value) -> Option<String> {
+    match item {
+        Value::String(item) => match item.split(':').collect::<Vec<&str>>()[..] {
+            [target] | [_, target] | [_, target, _] => Some(target.to_string()),
+            _ => None,
+        },
+        Value::Mapping(mapping) => mapping
+            .get("target")
+            .and_then(|target| target.as_str())
+            .map(String::from),
+        _ => None,
+    }
+}
+
+// ## reference_source(item: &Value) -> Option<String>
+// This function returns the name of a config or secret granted to a service
+fn reference_source(item: &Value) -> Option<String> {
+    match item {
+        Value::String(item) => Some(item.clone()),
+        Value::Mapping(mapping) => mapping
+            .get("source")
+            .and_then(|source| source.as_str())
+            .map(String::from),
+        _ => None,
+    }
+}
+
+// ## key_name(key: &Value) -> String
+// This function returns a map key as a path segment
+fn key_name(key: &Value) -> String {
+    match key {
+        Value::String(key) => key.clone(),
+        key => serde_yaml::to_string(key)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+#[test]
+fn test_docker_compose_merge_compose_files() {
+    let base = serde_yaml::from_str(
+        r#"
+services:
+  api:
+    image: api:1.0
+    command: ["serve", "--port", "80"]
+    environment:
+      - DEBUG=0
+      - REGION=eu
+    ports: ["8080:80"]
+    volumes:
+      - data:/data
+      - ./config:/config:ro
+    depends_on: [db]
+    healthcheck:
+      test: ["CMD", "true"]
+      interval: 10s
+  db:
+    image: postgres
+    ports: ["5432:5432"]
+volumes:
+  data: {}
+"#,
+    )
+    .unwrap();
+    let overlay = serde_yaml::from_str(
+        r#"
+services:
+  api:
+    image: api:dev
+    command: ["serve", "--reload"]
+    environment:
+      DEBUG: "1"
+    ports: ["9229:9229", "8080:80"]
+    volumes:
+      - ./src:/data
+    depends_on:
+      cache:
+        condition: service_started
+    healthcheck:
+      interval: 5s
+  db:
+    ports: !reset []
+  cache:
+    image: redis
+volumes: !override
+  cache: {}
+"#,
+    )
+    .unwrap();
+
+    let expected: Value = serde_yaml::from_str(
+        r#"
+services:
+  api:
+    image: api:dev
+    command: ["serve", "--reload"]
+    environment:
+      DEBUG: "1"
+      REGION: eu
+    ports: ["8080:80", "9229:9229"]
+    volumes:
+      - ./src:/data
+      - ./config:/config:ro
+    depends_on:
+      db:
+        condition: service_started
+      cache:
+        condition: service_started
+    healthcheck:
+      test: ["CMD", "true"]
+      interval: 5s
+  db:
+    image: postgres
+  cache:
+    image: redis
+volumes:
+  cache: {}
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(merge_compose_files(vec![base, overlay]), expected);
+
+    // the merged file is a valid compose file
+    let merged = merge_compose_texts(&[
+        "services:\n  api:\n    image: api\n    depends_on: [db]\n".to_string(),
+        "services:\n  api:\n    depends_on:\n      cache:\n        condition: service_healthy\n"
+            .to_string(),
+    ])
+    .unwrap();
+    assert_eq!(
+        merged.services["api"]
+            .depends_on
+            .as_ref()
+            .map(|depends_on| depends_on.services()),
+        Some(vec!["db".to_string(), "cache".to_string()])
+    );
+}
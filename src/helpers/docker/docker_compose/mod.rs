@@ -1,5 +1,9 @@
-mod file;
-//mod add_network;
-//mod add_secret;
-//mod add_service;
-//mod add_volume;
+pub mod add_network;
+pub mod add_secret;
+pub mod add_service;
+pub mod add_volume;
+pub mod file;
+pub mod generate;
+pub mod interpolate;
+pub mod lint;
+pub mod merge;
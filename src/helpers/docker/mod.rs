@@ -1 +1 @@
-mod docker_compose;
+pub mod docker_compose;
@@ -0,0 +1,237 @@
+use self::templates::{DockerfileTemplate, TEMPLATES};
+use crate::helpers::container_engine::compose_project_name;
+use crate::helpers::docker::docker_compose::generate::default_port;
+use crate::helpers::templates::render_template;
+use crate::models::project::Project;
+use std::collections::HashMap;
+use std::path::Path;
+
+pub mod templates;
+
+/// The directory of the project with the templates that replace the
+/// built-in ones, `<language or framework>/Dockerfile` and an optional
+/// `<language or framework>/.dockerignore`
+pub const LOCAL_TEMPLATES_DIR: &str = "templates/dockerfile";
+
+/// The Dockerfile and .dockerignore rendered for a service
+#[derive(Debug, PartialEq)]
+pub struct GeneratedDockerfile {
+    pub template: String,   // the built-in or local template it was rendered from
+    pub dockerfile: String, // the rendered Dockerfile
+    pub dockerignore: Option<String>, // the rendered .dockerignore, if any
+}
+
+/// ## generate_dockerfile(service: &Project, service_dir: &Path, project_path: &Path, variables: &HashMap<String, String>) -> Result<GeneratedDockerfile, String>
+/// This function renders the Dockerfile and .dockerignore of a service from
+/// the template of its frameworks or languages. A local template of the
+/// project for any of them replaces the built-in templates, then the
+/// frameworks are tried before the languages
+///
+/// ### Arguments
+/// - service: &Project - The service of project.yaml
+/// - service_dir: &Path - The directory of the service, its manifests name the binary
+/// - project_path: &Path - The root of the project, with the local templates
+/// - variables: &HashMap<String, String> - The project variables available to the templates
+///
+/// ### Returns
+/// - Result<GeneratedDockerfile, String> - The rendered files, or an error if no template matches the service
+pub fn generate_dockerfile(
+    service: &Project,
+    service_dir: &Path,
+    project_path: &Path,
+    variables: &HashMap<String, String>,
+) -> Result<GeneratedDockerfile, String> {
+    let frameworks = normalized_names(&service.frameworks);
+    let languages = normalized_names(&service.languages);
+    let builtin = [&frameworks, &languages].into_iter().find_map(|names| {
+        TEMPLATES.iter().find(|template| {
+            template
+                .keys
+                .iter()
+                .any(|key| names.iter().any(|name| name == key))
+        })
+    });
+
+    let local = frameworks
+        .iter()
+        .chain(languages.iter())
+        .map(|name| format!("{}/{}", LOCAL_TEMPLATES_DIR, name))
+        .find(|local| project_path.join(local).join("Dockerfile").is_file());
+
+    let (template, dockerfile, dockerignore) = match (local, builtin) {
+        (Some(local), _) => {
+            let dir = project_path.join(&local);
+            let read = |file: &str| {
+                std::fs::read_to_string(dir.join(file)).map_err(|error| {
+                    format!("Failed to read {}: {}", dir.join(file).display(), error)
+                })
+            };
+            let dockerignore = match dir.join(".dockerignore").is_file() {
+                true => Some(read(".dockerignore")?),
+                false => builtin.map(|template| template.dockerignore.to_string()),
+            };
+            (local, read("Dockerfile")?, dockerignore)
+        }
+        (None, Some(template)) => (
+            template.name.to_string(),
+            template.dockerfile.to_string(),
+            Some(template.dockerignore.to_string()),
+        ),
+        (None, None) if frameworks.is_empty() && languages.is_empty() => {
+            return Err(format!(
+                "Service {} has no languages or frameworks in project.yaml",
+                service.name
+            ))
+        }
+        (None, None) => {
+            return Err(format!(
+                "No Dockerfile template for {}, add one as {}/<language or framework>/Dockerfile",
+                [frameworks, languages].concat().join(", "),
+                LOCAL_TEMPLATES_DIR
+            ))
+        }
+    };
+
+    let mut variables = variables.clone();
+    variables.extend(service_variables(service, service_dir, builtin));
+
+    Ok(GeneratedDockerfile {
+        template,
+        dockerfile: render_template(&dockerfile, &variables),
+        dockerignore: dockerignore.map(|dockerignore| render_template(&dockerignore, &variables)),
+    })
+}
+
+// ## normalized_names(names: &Option<Vec<String>>) -> Vec<String>
+// This function returns languages or frameworks in lowercase without
+// punctuation, `Next.js` becomes `nextjs`
+fn normalized_names(names: &Option<Vec<String>>) -> Vec<String> {
+    names
+        .iter()
+        .flatten()
+        .map(|name| {
+            name.to_lowercase()
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric())
+                .collect::<String>()
+        })
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+// ## service_variables(service: &Project, service_dir: &Path, template: Option<&DockerfileTemplate>) -> HashMap<String, String>
+// This function returns the variables of a service available to the
+// templates: its name, path, port, binary, command and build output
+fn service_variables(
+    service: &Project,
+    service_dir: &Path,
+    template: Option<&DockerfileTemplate>,
+) -> HashMap<String, String> {
+    let name = compose_project_name(&service.name);
+    let port = default_port(service)
+        .or(template.map(|template| template.port))
+        .unwrap_or(8080);
+    let frameworks = normalized_names(&service.frameworks);
+    let has_framework = |framework: &str| frameworks.iter().any(|name| name == framework);
+
+    let command = if has_framework("fastapi") {
+        format!(
+            r#"["uvicorn", "main:app", "--host", "0.0.0.0", "--port", "{}"]"#,
+            port
+        )
+    } else if has_framework("flask") {
+        format!(r#"["gunicorn", "--bind", "0.0.0.0:{}", "app:app"]"#, port)
+    } else if has_framework("django") {
+        format!(
+            r#"["gunicorn", "--bind", "0.0.0.0:{}", "{}.wsgi"]"#,
+            port,
+            name.replace('-', "_")
+        )
+    } else {
+        r#"["python", "main.py"]"#.to_string()
+    };
+
+    // create-react-app builds to build, vite to dist
+    let build_output = match has_framework("react") {
+        true => "build",
+        false => "dist",
+    };
+
+    HashMap::from([
+        ("service_name".to_string(), name.clone()),
+        ("service_path".to_string(), service.service_path()),
+        ("port".to_string(), port.to_string()),
+        (
+            "binary".to_string(),
+            cargo_package_name(service_dir).unwrap_or(name),
+        ),
+        ("command".to_string(), command),
+        ("build_output".to_string(), build_output.to_string()),
+    ])
+}
+
+// ## cargo_package_name(service_dir: &Path) -> Option<String>
+// This function returns the package name of the Cargo.toml of a service,
+// the name of the binary cargo builds
+fn cargo_package_name(service_dir: &Path) -> Option<String> {
+    let manifest = std::fs::read_to_string(service_dir.join("Cargo.toml")).ok()?;
+
+    let mut in_package = false;
+    for line in manifest.lines().map(|line| line.trim()) {
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+        } else if let (true, Some(("name", value))) = (
+            in_package,
+            line.split_once('=').map(|(key, value)| (key.trim(), value)),
+        ) {
+            return Some(value.trim().trim_matches('"').to_string());
+        }
+    }
+
+    None
+}
+
+#[test]
+fn test_dockerfile_generate_dockerfile() {
+    let service = |frameworks: &str, languages: &str| {
+        Project::from_yaml(&format!(
+            "name: Web App\ndomain: shop.test\nversion: 1.0.0\ndescription: The web app\nframeworks: {}\nlanguages: {}\npath: services/web\n",
+            frameworks, languages
+        ))
+        .unwrap()
+    };
+    let variables = HashMap::from([("project_name".to_string(), "shop".to_string())]);
+    let generate = |service: &Project| {
+        generate_dockerfile(
+            service,
+            Path::new("/nonexistent"),
+            Path::new("/nonexistent"),
+            &variables,
+        )
+    };
+
+    // the frameworks are tried before the languages, nextjs before react
+    let nextjs = generate(&service("[React, Next.js]", "[JavaScript]")).unwrap();
+    assert_eq!(nextjs.template, "nextjs");
+    assert!(nextjs
+        .dockerfile
+        .contains("# Generated by `tools dockerfile generate web-app` for shop"));
+    assert!(nextjs.dockerfile.contains("EXPOSE 3000"));
+    assert!(nextjs.dockerignore.unwrap().contains("node_modules"));
+
+    // without a known framework the language picks the template
+    let rust = generate(&service("[]", "[Rust]")).unwrap();
+    assert_eq!(rust.template, "rust");
+    assert!(rust
+        .dockerfile
+        .contains("COPY --from=build /app/target/release/web-app /usr/local/bin/web-app"));
+    assert!(rust.dockerfile.contains("EXPOSE 8080"));
+
+    let flask = generate(&service("[Flask]", "[Python]")).unwrap();
+    assert!(flask
+        .dockerfile
+        .contains(r#"CMD ["gunicorn", "--bind", "0.0.0.0:5000", "app:app"]"#));
+
+    assert!(generate(&service("[]", "[]")).is_err());
+    assert!(generate(&service("[]", "[COBOL]")).is_err());
+}
@@ -0,0 +1,261 @@
+/// A built-in template of a Dockerfile and its .dockerignore
+#[derive(Debug, PartialEq)]
+pub struct DockerfileTemplate {
+    pub name: &'static str,            // the name of the template
+    pub keys: &'static [&'static str], // the frameworks and languages it is used for
+    pub port: u16,                     // the port without a framework default
+    pub dockerfile: &'static str,      // the Dockerfile, with `{{ name }}` placeholders
+    pub dockerignore: &'static str,    // the .dockerignore
+}
+
+/// The built-in templates, the first one matching a framework of the
+/// service is used, then the first one matching a language
+pub const TEMPLATES: [DockerfileTemplate; 6] = [
+    DockerfileTemplate {
+        name: "nextjs",
+        keys: &["nextjs", "next"],
+        port: 3000,
+        dockerfile: NEXTJS_DOCKERFILE,
+        dockerignore: NODE_DOCKERIGNORE,
+    },
+    DockerfileTemplate {
+        name: "spa",
+        keys: &["react", "vue", "svelte"],
+        port: 80,
+        dockerfile: SPA_DOCKERFILE,
+        dockerignore: NODE_DOCKERIGNORE,
+    },
+    DockerfileTemplate {
+        name: "python",
+        keys: &["django", "fastapi", "flask", "python"],
+        port: 8000,
+        dockerfile: PYTHON_DOCKERFILE,
+        dockerignore: PYTHON_DOCKERIGNORE,
+    },
+    DockerfileTemplate {
+        name: "node",
+        keys: &[
+            "express",
+            "nestjs",
+            "javascript",
+            "typescript",
+            "node",
+            "nodejs",
+        ],
+        port: 3000,
+        dockerfile: NODE_DOCKERFILE,
+        dockerignore: NODE_DOCKERIGNORE,
+    },
+    DockerfileTemplate {
+        name: "rust",
+        keys: &["actix", "axum", "rocket", "rust"],
+        port: 8080,
+        dockerfile: RUST_DOCKERFILE,
+        dockerignore: RUST_DOCKERIGNORE,
+    },
+    DockerfileTemplate {
+        name: "go",
+        keys: &["gin", "go", "golang"],
+        port: 8080,
+        dockerfile: GO_DOCKERFILE,
+        dockerignore: GO_DOCKERIGNORE,
+    },
+];
+
+//multiline variable for the Dockerfile of rust services
+const RUST_DOCKERFILE: &str = r#"# syntax=docker/dockerfile:1
+# Generated by `tools dockerfile generate {{ service_name }}` for {{ project_name }}
+
+FROM rust:1-slim AS build
+WORKDIR /app
+
+# build the dependencies alone first, they stay cached until Cargo.toml or Cargo.lock change
+COPY Cargo.toml Cargo.lock* ./
+RUN mkdir src && echo "fn main() {}" > src/main.rs \
+    && cargo build --release \
+    && rm -rf src
+
+COPY . .
+RUN touch src/main.rs && cargo build --release
+
+FROM debian:bookworm-slim
+RUN apt-get update \
+    && apt-get install -y --no-install-recommends ca-certificates \
+    && rm -rf /var/lib/apt/lists/*
+COPY --from=build /app/target/release/{{ binary }} /usr/local/bin/{{ binary }}
+
+USER nobody
+EXPOSE {{ port }}
+CMD ["{{ binary }}"]
+"#;
+
+//multiline variable for the .dockerignore of rust services
+const RUST_DOCKERIGNORE: &str = r#".git
+.gitignore
+.dockerignore
+Dockerfile
+target
+*.md
+.env
+.env.*
+"#;
+
+//multiline variable for the Dockerfile of node services
+const NODE_DOCKERFILE: &str = r#"# syntax=docker/dockerfile:1
+# Generated by `tools dockerfile generate {{ service_name }}` for {{ project_name }}
+
+FROM node:20-alpine AS deps
+WORKDIR /app
+
+# install the dependencies alone first, they stay cached until the manifests change
+COPY package.json package-lock.json* ./
+RUN if [ -f package-lock.json ]; then npm ci --omit=dev; else npm install --omit=dev; fi
+
+FROM node:20-alpine
+WORKDIR /app
+ENV NODE_ENV=production PORT={{ port }}
+COPY --from=deps /app/node_modules ./node_modules
+COPY . .
+
+USER node
+EXPOSE {{ port }}
+CMD ["npm", "start"]
+"#;
+
+//multiline variable for the .dockerignore of javascript services
+const NODE_DOCKERIGNORE: &str = r#".git
+.gitignore
+.dockerignore
+Dockerfile
+node_modules
+npm-debug.log*
+.next
+build
+dist
+coverage
+*.md
+.env
+.env.*
+"#;
+
+//multiline variable for the Dockerfile of nextjs services
+const NEXTJS_DOCKERFILE: &str = r#"# syntax=docker/dockerfile:1
+# Generated by `tools dockerfile generate {{ service_name }}` for {{ project_name }}
+# needs `output: "standalone"` in next.config.js
+
+FROM node:20-alpine AS deps
+WORKDIR /app
+
+# install the dependencies alone first, they stay cached until the manifests change
+COPY package.json package-lock.json* ./
+RUN if [ -f package-lock.json ]; then npm ci; else npm install; fi
+
+FROM node:20-alpine AS build
+WORKDIR /app
+ENV NEXT_TELEMETRY_DISABLED=1
+COPY --from=deps /app/node_modules ./node_modules
+COPY . .
+RUN npm run build
+
+FROM node:20-alpine
+WORKDIR /app
+ENV NODE_ENV=production NEXT_TELEMETRY_DISABLED=1 PORT={{ port }} HOSTNAME=0.0.0.0
+COPY --from=build --chown=node:node /app/.next/standalone ./
+COPY --from=build --chown=node:node /app/.next/static ./.next/static
+
+USER node
+EXPOSE {{ port }}
+CMD ["node", "server.js"]
+"#;
+
+//multiline variable for the Dockerfile of single page applications
+const SPA_DOCKERFILE: &str = r#"# syntax=docker/dockerfile:1
+# Generated by `tools dockerfile generate {{ service_name }}` for {{ project_name }}
+
+FROM node:20-alpine AS build
+WORKDIR /app
+
+# install the dependencies alone first, they stay cached until the manifests change
+COPY package.json package-lock.json* ./
+RUN if [ -f package-lock.json ]; then npm ci; else npm install; fi
+
+COPY . .
+RUN npm run build
+
+FROM nginx:alpine
+RUN sed -i 's/listen\s*80;/listen {{ port }};/' /etc/nginx/conf.d/default.conf
+COPY --from=build /app/{{ build_output }} /usr/share/nginx/html
+
+EXPOSE {{ port }}
+"#;
+
+//multiline variable for the Dockerfile of python services
+const PYTHON_DOCKERFILE: &str = r#"# syntax=docker/dockerfile:1
+# Generated by `tools dockerfile generate {{ service_name }}` for {{ project_name }}
+
+FROM python:3.12-slim AS build
+ENV PIP_DISABLE_PIP_VERSION_CHECK=1 PIP_NO_CACHE_DIR=1
+
+# install the dependencies alone first, they stay cached until requirements.txt changes
+COPY requirements.txt ./
+RUN python -m venv /venv && /venv/bin/pip install -r requirements.txt
+
+FROM python:3.12-slim
+WORKDIR /app
+ENV PATH="/venv/bin:$PATH" PYTHONUNBUFFERED=1 PYTHONDONTWRITEBYTECODE=1
+COPY --from=build /venv /venv
+COPY . .
+
+USER nobody
+EXPOSE {{ port }}
+CMD {{ command }}
+"#;
+
+//multiline variable for the .dockerignore of python services
+const PYTHON_DOCKERIGNORE: &str = r#".git
+.gitignore
+.dockerignore
+Dockerfile
+__pycache__
+*.pyc
+.venv
+venv
+.pytest_cache
+.mypy_cache
+*.md
+.env
+.env.*
+"#;
+
+//multiline variable for the Dockerfile of go services
+const GO_DOCKERFILE: &str = r#"# syntax=docker/dockerfile:1
+# Generated by `tools dockerfile generate {{ service_name }}` for {{ project_name }}
+
+FROM golang:1.22 AS build
+WORKDIR /src
+
+# download the modules alone first, they stay cached until go.mod or go.sum change
+COPY go.mod go.sum* ./
+RUN go mod download
+
+COPY . .
+RUN CGO_ENABLED=0 go build -o /out/{{ binary }} .
+
+FROM gcr.io/distroless/static-debian12
+COPY --from=build /out/{{ binary }} /usr/local/bin/{{ binary }}
+
+USER nonroot
+EXPOSE {{ port }}
+ENTRYPOINT ["/usr/local/bin/{{ binary }}"]
+"#;
+
+//multiline variable for the .dockerignore of go services
+const GO_DOCKERIGNORE: &str = r#".git
+.gitignore
+.dockerignore
+Dockerfile
+bin
+*.md
+.env
+.env.*
+"#;
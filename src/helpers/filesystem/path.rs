@@ -11,15 +11,15 @@ pub fn get_path(path: &str) -> std::path::PathBuf {
     std::fs::canonicalize(path).unwrap()
 }
 
-/// ## append_path(base_path: &PathBuf, file: &str)
+/// ## append_path(base_path: &Path, file: &str)
 /// This function appends a file or directory to a path
 ///
 /// ## Arguments
-/// - base_path: &PathBuf - The base path to append the file or directory to
+/// - base_path: &Path - The base path to append the file or directory to
 /// - file: &str - The file or directory to append to the base path
 ///
 /// ## Returns
 /// - PathBuf - The path to the file or directory
-pub fn append_path(base_path: &std::path::PathBuf, file: &str) -> std::path::PathBuf {
+pub fn append_path(base_path: &std::path::Path, file: &str) -> std::path::PathBuf {
     base_path.join(file)
 }
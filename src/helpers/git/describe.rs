@@ -0,0 +1,66 @@
+/// The parsed output of `git describe --tags --long --dirty --always`
+#[derive(Debug, PartialEq)]
+pub struct Describe {
+    pub tag: Option<String>, // the last tag, None if no tag is reachable
+    pub distance: u64,       // the number of commits since the tag
+    pub hash: String,        // the abbreviated hash of HEAD
+    pub dirty: bool,         // true if the working tree has uncommitted changes
+}
+
+impl Describe {
+    /// This function parses `<tag>-<distance>-g<hash>[-dirty]`, or only
+    /// `<hash>[-dirty]` when no tag is reachable. The distance is 0 without
+    /// a tag and has to be counted separately.
+    pub fn from_describe(output: &str) -> Result<Self, String> {
+        let output = output.trim();
+        let (description, dirty) = match output.strip_suffix("-dirty") {
+            Some(description) => (description, true),
+            None => (output, false),
+        };
+
+        // tags can contain `-`, so the fields are split from the right
+        let mut parts = description.rsplitn(3, '-');
+        let describe = match (parts.next(), parts.next(), parts.next()) {
+            (Some(hash), Some(distance), Some(tag)) if hash.starts_with('g') => Describe {
+                tag: Some(tag.to_string()),
+                distance: distance
+                    .parse()
+                    .map_err(|_| format!("unexpected description: {}", output))?,
+                hash: hash[1..].to_string(),
+                dirty,
+            },
+            (Some(hash), None, None) if !hash.is_empty() => Describe {
+                tag: None,
+                distance: 0,
+                hash: hash.to_string(),
+                dirty,
+            },
+            _ => return Err(format!("unexpected description: {}", output)),
+        };
+
+        Ok(describe)
+    }
+}
+
+#[test]
+fn test_describe_from_describe() {
+    assert_eq!(
+        Describe::from_describe("v1.3.0-rc.1-12-g3f2a1b4-dirty\n"),
+        Ok(Describe {
+            tag: Some("v1.3.0-rc.1".to_string()),
+            distance: 12,
+            hash: "3f2a1b4".to_string(),
+            dirty: true,
+        })
+    );
+    assert_eq!(
+        Describe::from_describe("3f2a1b4"),
+        Ok(Describe {
+            tag: None,
+            distance: 0,
+            hash: "3f2a1b4".to_string(),
+            dirty: false,
+        })
+    );
+    assert!(Describe::from_describe("v1.3.0-x-g3f2a1b4").is_err());
+}
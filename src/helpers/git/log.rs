@@ -0,0 +1,57 @@
+/// The `--format` of `git log` understood by `Commit::from_log`, fields
+/// are separated by the unit separator and commits by the record separator
+pub const LOG_FORMAT: &str = "%H%x1f%h%x1f%an%x1f%ae%x1f%aI%x1f%s%x1f%b%x1e";
+
+/// A commit as returned by `git log`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Commit {
+    pub hash: String,
+    pub short_hash: String,
+    pub author_name: String,
+    pub author_email: String,
+    pub date: String, // the author date in strict ISO 8601 format
+    pub subject: String,
+    pub body: String,
+}
+
+impl Commit {
+    /// This function parses the output of `git log --format=LOG_FORMAT`.
+    pub fn from_log(output: &str) -> Vec<Self> {
+        output
+            .split('\x1e')
+            .map(|record| record.trim_start_matches('\n'))
+            .filter(|record| !record.is_empty())
+            .filter_map(|record| {
+                let fields = record.splitn(7, '\x1f').collect::<Vec<&str>>();
+                if fields.len() != 7 {
+                    return None;
+                }
+                Some(Commit {
+                    hash: fields[0].to_string(),
+                    short_hash: fields[1].to_string(),
+                    author_name: fields[2].to_string(),
+                    author_email: fields[3].to_string(),
+                    date: fields[4].to_string(),
+                    subject: fields[5].to_string(),
+                    body: fields[6].trim().to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_commit_from_log() {
+    let output = "3f2a1b4c\x1f3f2a1b4\x1fDiana\x1fdiana@example.com\x1f2023-11-03T10:00:00+00:00\x1ffeat: add foreach\x1fLonger description\n\nBREAKING CHANGE: none\n\x1e\n9e8d7c6b\x1f9e8d7c6\x1fDiana\x1fdiana@example.com\x1f2023-11-02T10:00:00+00:00\x1ffix: typo\x1f\x1e\n";
+
+    let commits = Commit::from_log(output);
+
+    assert_eq!(commits.len(), 2);
+    assert_eq!(commits[0].subject, "feat: add foreach");
+    assert_eq!(
+        commits[0].body,
+        "Longer description\n\nBREAKING CHANGE: none"
+    );
+    assert_eq!(commits[1].short_hash, "9e8d7c6");
+    assert_eq!(commits[1].body, "");
+}
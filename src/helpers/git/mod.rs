@@ -0,0 +1,307 @@
+pub mod describe;
+pub mod log;
+pub mod refs;
+pub mod status;
+pub mod submodule;
+
+use crate::CONFIG;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// An error returned by a git command, carrying the command that failed
+/// and what git printed on stderr
+#[derive(Debug)]
+pub enum GitError {
+    /// git could not be started, e.g. because it is not installed
+    Spawn {
+        args: Vec<String>,
+        error: std::io::Error,
+    },
+    /// git exited with a failing code
+    Failed {
+        args: Vec<String>,
+        code: Option<i32>,
+        stderr: String,
+    },
+    /// git succeeded but its output could not be understood
+    Parse { args: Vec<String>, message: String },
+}
+
+impl fmt::Display for GitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GitError::Spawn { args, error } => {
+                write!(f, "failed to run `git {}`: {}", args.join(" "), error)
+            }
+            GitError::Failed { args, code, stderr } => write!(
+                f,
+                "`git {}` failed with exit code {}: {}",
+                args.join(" "),
+                code.map(|code| code.to_string())
+                    .unwrap_or("unknown".to_string()),
+                stderr
+            ),
+            GitError::Parse { args, message } => {
+                write!(
+                    f,
+                    "couldn't parse the output of `git {}`: {}",
+                    args.join(" "),
+                    message
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for GitError {}
+
+/// A git repository on disk, every git command runs in its directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct Git {
+    pub dir: PathBuf,
+}
+
+impl Git {
+    /// This function returns a git repository for a directory.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Git { dir: dir.into() }
+    }
+
+    /// This function returns the git repository of the project.
+    pub fn project() -> Self {
+        Git::new(&CONFIG.project_path)
+    }
+
+    /// This function runs a git command in the repository and returns its
+    /// stdout, or a `GitError` with the stderr of git if it failed.
+    pub fn run(&self, args: &[&str]) -> Result<String, GitError> {
+        let owned_args = args
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<String>>();
+
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(&self.dir)
+            .output()
+            .map_err(|error| GitError::Spawn {
+                args: owned_args.clone(),
+                error,
+            })?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        } else {
+            Err(GitError::Failed {
+                args: owned_args,
+                code: output.status.code(),
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            })
+        }
+    }
+
+    /// This function returns the status of the working tree.
+    pub fn status(&self) -> Result<status::Status, GitError> {
+        let args = ["status", "--porcelain=v2", "--branch"];
+        let output = self.run(&args)?;
+        status::Status::from_porcelain_v2(&output).map_err(|message| GitError::Parse {
+            args: args.map(String::from).to_vec(),
+            message,
+        })
+    }
+
+    /// This function resolves a revision to its full commit hash.
+    pub fn rev_parse(&self, rev: &str) -> Result<String, GitError> {
+        Ok(self
+            .run(&["rev-parse", "--verify", rev])?
+            .trim()
+            .to_string())
+    }
+
+    /// This function resolves a revision to its abbreviated commit hash.
+    pub fn short_hash(&self, rev: &str) -> Result<String, GitError> {
+        Ok(self
+            .run(&["rev-parse", "--short", "--verify", rev])?
+            .trim()
+            .to_string())
+    }
+
+    /// This function resolves a path inside the git directory, e.g. `hooks`,
+    /// following `.git` files of submodules and `core.hooksPath`.
+    pub fn git_path(&self, path: &str) -> Result<PathBuf, GitError> {
+        let git_path = PathBuf::from(self.run(&["rev-parse", "--git-path", path])?.trim());
+        Ok(if git_path.is_absolute() {
+            git_path
+        } else {
+            self.dir.join(git_path)
+        })
+    }
+
+    /// This function describes HEAD relative to the last release tag,
+    /// `v<version>`, and marks a dirty working tree.
+    pub fn describe(&self) -> Result<describe::Describe, GitError> {
+        let args = [
+            "describe",
+            "--tags",
+            "--long",
+            "--dirty",
+            "--always",
+            "--abbrev=7",
+            "--match",
+            "v[0-9]*",
+        ];
+        let output = self.run(&args)?;
+        describe::Describe::from_describe(&output).map_err(|message| GitError::Parse {
+            args: args.map(String::from).to_vec(),
+            message,
+        })
+    }
+
+    /// This function counts the commits reachable from a revision.
+    pub fn commit_count(&self, rev: &str) -> Result<u64, GitError> {
+        let args = ["rev-list", "--count", rev];
+        let output = self.run(&args)?;
+        output.trim().parse().map_err(|_| GitError::Parse {
+            args: args.map(String::from).to_vec(),
+            message: format!("unexpected count: {}", output.trim()),
+        })
+    }
+
+    /// This function returns the commits in a revision range, newest first.
+    pub fn log(&self, range: &str) -> Result<Vec<log::Commit>, GitError> {
+        let format = format!("--format={}", log::LOG_FORMAT);
+        let output = self.run(&["log", &format, range, "--"])?;
+        Ok(log::Commit::from_log(&output))
+    }
+
+    /// This function returns the local branches of the repository.
+    pub fn branches(&self) -> Result<Vec<refs::Branch>, GitError> {
+        let output = self.run(&["for-each-ref", refs::BRANCH_FORMAT, "refs/heads"])?;
+        Ok(refs::Branch::from_for_each_ref(&output))
+    }
+
+    /// This function creates a branch at a revision, or at HEAD.
+    pub fn create_branch(&self, name: &str, start: Option<&str>) -> Result<(), GitError> {
+        match start {
+            Some(start) => self.run(&["branch", name, start])?,
+            None => self.run(&["branch", name])?,
+        };
+        Ok(())
+    }
+
+    /// This function deletes a branch, refusing unmerged branches unless
+    /// force is set.
+    pub fn delete_branch(&self, name: &str, force: bool) -> Result<(), GitError> {
+        self.run(&["branch", if force { "-D" } else { "-d" }, name])?;
+        Ok(())
+    }
+
+    /// This function returns the remotes of the repository.
+    #[allow(dead_code)]
+    pub fn remotes(&self) -> Result<Vec<refs::Remote>, GitError> {
+        let output = self.run(&["remote", "-v"])?;
+        Ok(refs::Remote::from_remote_verbose(&output))
+    }
+
+    /// This function fetches from a remote, or from the default remote.
+    pub fn fetch(&self, remote: Option<&str>) -> Result<(), GitError> {
+        match remote {
+            Some(remote) => self.run(&["fetch", "--quiet", remote])?,
+            None => self.run(&["fetch", "--quiet"])?,
+        };
+        Ok(())
+    }
+
+    /// This function checks out a branch, or any revision as a detached HEAD.
+    pub fn checkout(&self, rev: &str, detach: bool) -> Result<(), GitError> {
+        if detach {
+            self.run(&["checkout", "--quiet", "--detach", rev])?;
+        } else {
+            self.run(&["checkout", "--quiet", rev])?;
+        }
+        Ok(())
+    }
+
+    /// This function returns the tags of the repository, newest first.
+    pub fn tags(&self) -> Result<Vec<String>, GitError> {
+        Ok(self
+            .run(&["tag", "--list", "--sort=-creatordate"])?
+            .lines()
+            .map(|tag| tag.to_string())
+            .collect())
+    }
+
+    /// This function creates an annotated tag at a revision, or at HEAD.
+    pub fn create_tag(
+        &self,
+        name: &str,
+        message: &str,
+        target: Option<&str>,
+    ) -> Result<(), GitError> {
+        match target {
+            Some(target) => self.run(&["tag", "--annotate", name, "--message", message, target])?,
+            None => self.run(&["tag", "--annotate", name, "--message", message])?,
+        };
+        Ok(())
+    }
+
+    /// This function stages files for the next commit.
+    pub fn add(&self, paths: &[&str]) -> Result<(), GitError> {
+        let mut args = vec!["add", "--"];
+        args.extend(paths);
+        self.run(&args)?;
+        Ok(())
+    }
+
+    /// This function commits the staged changes.
+    pub fn commit(&self, message: &str) -> Result<(), GitError> {
+        self.run(&["commit", "--quiet", "--message", message])?;
+        Ok(())
+    }
+
+    /// This function returns the status of every submodule.
+    #[allow(dead_code)]
+    pub fn submodule_status(&self) -> Result<Vec<submodule::SubmoduleStatus>, GitError> {
+        let output = self.run(&["submodule", "status", "--recursive"])?;
+        Ok(submodule::SubmoduleStatus::from_submodule_status(&output))
+    }
+
+    /// This function returns the submodule pointers recorded in the tree of
+    /// a revision of the superproject.
+    pub fn gitlinks(&self, rev: &str) -> Result<Vec<submodule::Gitlink>, GitError> {
+        let output = self.run(&["ls-tree", "-r", "--full-tree", rev])?;
+        Ok(submodule::Gitlink::from_ls_tree(&output))
+    }
+
+    /// This function initializes and updates every submodule recursively.
+    pub fn submodule_update(&self) -> Result<(), GitError> {
+        self.run(&["submodule", "update", "--init", "--recursive"])?;
+        Ok(())
+    }
+
+    /// This function adds a repository as a submodule at a path.
+    pub fn submodule_add(&self, url: &str, path: &str) -> Result<(), GitError> {
+        self.run(&["submodule", "add", url, path])?;
+        Ok(())
+    }
+}
+
+/// ## clone(url: &str, path: &Path) -> Result<Git, GitError>
+/// This function clones a repository into a path and returns it
+///
+/// ### Arguments
+/// - url: &str - The URL of the repository to clone
+/// - path: &Path - The directory to clone into, it must not exist yet
+///
+/// ### Returns
+/// - Result<Git, GitError> - The cloned repository
+pub fn clone(url: &str, path: &Path) -> Result<Git, GitError> {
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let target = path.to_string_lossy().to_string();
+    Git::new(parent).run(&["clone", "--quiet", url, &target])?;
+    Ok(Git::new(path))
+}
@@ -0,0 +1,109 @@
+/// The `--format` of `git for-each-ref` understood by `Branch::from_for_each_ref`
+pub const BRANCH_FORMAT: &str =
+    "--format=%(refname:short)%09%(objectname)%09%(upstream:short)%09%(HEAD)";
+
+/// A local branch as returned by `git for-each-ref refs/heads`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Branch {
+    pub name: String,
+    pub commit: String,
+    pub upstream: Option<String>,
+    pub is_head: bool, // true for the branch that is checked out
+}
+
+/// A remote as returned by `git remote -v`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Remote {
+    pub name: String,
+    pub fetch_url: String,
+    pub push_url: String,
+}
+
+impl Branch {
+    /// This function parses the output of `git for-each-ref BRANCH_FORMAT`.
+    pub fn from_for_each_ref(output: &str) -> Vec<Self> {
+        output
+            .lines()
+            .filter_map(|line| {
+                let fields = line.split('\t').collect::<Vec<&str>>();
+                if fields.len() != 4 {
+                    return None;
+                }
+                Some(Branch {
+                    name: fields[0].to_string(),
+                    commit: fields[1].to_string(),
+                    upstream: Some(fields[2].to_string()).filter(|upstream| !upstream.is_empty()),
+                    is_head: fields[3] == "*",
+                })
+            })
+            .collect()
+    }
+}
+
+impl Remote {
+    /// This function parses the output of `git remote -v`, which lists
+    /// every remote once for fetching and once for pushing.
+    pub fn from_remote_verbose(output: &str) -> Vec<Self> {
+        let mut remotes: Vec<Remote> = Vec::new();
+
+        for line in output.lines() {
+            let mut fields = line.split_whitespace();
+            let (Some(name), Some(url), Some(kind)) = (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+
+            let index = match remotes.iter().position(|remote| remote.name == name) {
+                Some(index) => index,
+                None => {
+                    remotes.push(Remote {
+                        name: name.to_string(),
+                        fetch_url: String::new(),
+                        push_url: String::new(),
+                    });
+                    remotes.len() - 1
+                }
+            };
+
+            if kind == "(push)" {
+                remotes[index].push_url = url.to_string();
+            } else {
+                remotes[index].fetch_url = url.to_string();
+            }
+        }
+
+        remotes
+    }
+}
+
+#[test]
+fn test_refs_parsing() {
+    let branches =
+        Branch::from_for_each_ref("main\t3f2a1b4c\torigin/main\t*\nfeature/login\t9e8d7c6b\t\t \n");
+
+    assert_eq!(
+        branches,
+        vec![
+            Branch {
+                name: "main".to_string(),
+                commit: "3f2a1b4c".to_string(),
+                upstream: Some("origin/main".to_string()),
+                is_head: true,
+            },
+            Branch {
+                name: "feature/login".to_string(),
+                commit: "9e8d7c6b".to_string(),
+                upstream: None,
+                is_head: false,
+            },
+        ]
+    );
+
+    let remotes = Remote::from_remote_verbose(
+        "origin\tgit@github.com:xN4P4LM-org/tools.git (fetch)\norigin\tgit@github.com:xN4P4LM-org/tools.git (push)\n",
+    );
+
+    assert_eq!(remotes.len(), 1);
+    assert_eq!(remotes[0].fetch_url, "git@github.com:xN4P4LM-org/tools.git");
+    assert_eq!(remotes[0].push_url, remotes[0].fetch_url);
+}
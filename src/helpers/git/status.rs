@@ -0,0 +1,199 @@
+/// The branch information of `git status --porcelain=v2 --branch`
+#[derive(Debug, Default, PartialEq)]
+pub struct BranchStatus {
+    pub oid: Option<String>,  // the commit of HEAD, None before the first commit
+    pub head: Option<String>, // the current branch, None when HEAD is detached
+    pub upstream: Option<String>, // the upstream branch if one is set
+    pub ahead: u32,           // commits on the branch that are not on the upstream
+    pub behind: u32,          // commits on the upstream that are not on the branch
+}
+
+/// The kind of a `git status --porcelain=v2` entry
+#[derive(Debug, PartialEq)]
+pub enum EntryKind {
+    Changed,
+    Renamed,
+    Unmerged,
+    Untracked,
+    Ignored,
+}
+
+/// A single file in `git status --porcelain=v2`
+#[derive(Debug, PartialEq)]
+pub struct StatusEntry {
+    pub kind: EntryKind,
+    pub index: char,    // the status of the file in the index, `.` if unchanged
+    pub worktree: char, // the status of the file in the worktree, `.` if unchanged
+    pub submodule: bool,
+    pub path: String,
+    pub original_path: Option<String>, // the path before a rename or copy
+}
+
+/// The parsed output of `git status --porcelain=v2 --branch`
+#[derive(Debug, Default, PartialEq)]
+pub struct Status {
+    pub branch: BranchStatus,
+    pub entries: Vec<StatusEntry>,
+}
+
+impl Status {
+    /// This function parses the output of `git status --porcelain=v2 --branch`.
+    pub fn from_porcelain_v2(output: &str) -> Result<Self, String> {
+        let mut status = Status::default();
+
+        for line in output.lines().filter(|line| !line.is_empty()) {
+            if let Some(header) = line.strip_prefix("# ") {
+                status.parse_header(header)?;
+                continue;
+            }
+
+            let (kind, rest) = line
+                .split_once(' ')
+                .ok_or(format!("unexpected line: {}", line))?;
+
+            let entry = match kind {
+                "1" => parse_entry(EntryKind::Changed, rest, 7)?,
+                "2" => parse_entry(EntryKind::Renamed, rest, 8)?,
+                "u" => parse_entry(EntryKind::Unmerged, rest, 9)?,
+                "?" | "!" => StatusEntry {
+                    kind: if kind == "?" {
+                        EntryKind::Untracked
+                    } else {
+                        EntryKind::Ignored
+                    },
+                    index: '?',
+                    worktree: '?',
+                    submodule: false,
+                    path: rest.to_string(),
+                    original_path: None,
+                },
+                _ => return Err(format!("unexpected line: {}", line)),
+            };
+            status.entries.push(entry);
+        }
+
+        Ok(status)
+    }
+
+    /// This function returns true if there are no changed, unmerged or
+    /// untracked files.
+    pub fn is_clean(&self) -> bool {
+        self.entries
+            .iter()
+            .all(|entry| entry.kind == EntryKind::Ignored)
+    }
+
+    /// This function returns true if a tracked file is changed or unmerged,
+    /// untracked files like a bootstrapped `.env` don't count.
+    pub fn has_tracked_changes(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| !matches!(entry.kind, EntryKind::Untracked | EntryKind::Ignored))
+    }
+
+    /// This function parses a `# branch.*` header line.
+    fn parse_header(&mut self, header: &str) -> Result<(), String> {
+        let (key, value) = header
+            .split_once(' ')
+            .ok_or(format!("unexpected header: {}", header))?;
+
+        match key {
+            "branch.oid" if value != "(initial)" => self.branch.oid = Some(value.to_string()),
+            "branch.head" if value != "(detached)" => self.branch.head = Some(value.to_string()),
+            "branch.upstream" => self.branch.upstream = Some(value.to_string()),
+            "branch.ab" => {
+                let (ahead, behind) = value
+                    .split_once(' ')
+                    .ok_or(format!("unexpected header: {}", header))?;
+                self.branch.ahead = ahead
+                    .trim_start_matches('+')
+                    .parse()
+                    .map_err(|_| format!("unexpected header: {}", header))?;
+                self.branch.behind = behind
+                    .trim_start_matches('-')
+                    .parse()
+                    .map_err(|_| format!("unexpected header: {}", header))?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+/// This function parses a changed, renamed or unmerged entry, where the
+/// path follows a fixed number of space separated fields.
+fn parse_entry(kind: EntryKind, rest: &str, fields: usize) -> Result<StatusEntry, String> {
+    let parts = rest.splitn(fields + 1, ' ').collect::<Vec<&str>>();
+    if parts.len() != fields + 1 {
+        return Err(format!("unexpected entry: {}", rest));
+    }
+
+    let mut xy = parts[0].chars();
+    let (path, original_path) = match kind {
+        // renamed entries end with `<path>\t<original path>`
+        EntryKind::Renamed => match parts[fields].split_once('\t') {
+            Some((path, original_path)) => (path.to_string(), Some(original_path.to_string())),
+            None => return Err(format!("unexpected entry: {}", rest)),
+        },
+        _ => (parts[fields].to_string(), None),
+    };
+
+    Ok(StatusEntry {
+        kind,
+        index: xy.next().unwrap_or('.'),
+        worktree: xy.next().unwrap_or('.'),
+        submodule: parts[1].starts_with('S'),
+        path,
+        original_path,
+    })
+}
+
+#[test]
+fn test_status_from_porcelain_v2() {
+    let output = "# branch.oid 3f2a1b4c5d6e7f8091a2b3c4d5e6f708192a3b4c
+# branch.head main
+# branch.upstream origin/main
+# branch.ab +2 -1
+1 .M N... 100644 100644 100644 3f2a1b4 3f2a1b4 src/main.rs
+1 .M SC.. 160000 160000 160000 9e8d7c6 9e8d7c6 backend
+2 R. N... 100644 100644 100644 3f2a1b4 3f2a1b4 R100 docs/new name.md\tdocs/old.md
+u UU N... 100644 100644 100644 100644 3f2a1b4 9e8d7c6 1a2b3c4 Cargo.toml
+? notes.txt
+";
+
+    let status = Status::from_porcelain_v2(output).unwrap();
+
+    assert_eq!(
+        status.branch,
+        BranchStatus {
+            oid: Some("3f2a1b4c5d6e7f8091a2b3c4d5e6f708192a3b4c".to_string()),
+            head: Some("main".to_string()),
+            upstream: Some("origin/main".to_string()),
+            ahead: 2,
+            behind: 1,
+        }
+    );
+    assert_eq!(status.entries.len(), 5);
+    assert_eq!(status.entries[0].worktree, 'M');
+    assert!(status.entries[1].submodule);
+    assert_eq!(status.entries[2].path, "docs/new name.md");
+    assert_eq!(
+        status.entries[2].original_path,
+        Some("docs/old.md".to_string())
+    );
+    assert_eq!(status.entries[3].kind, EntryKind::Unmerged);
+    assert_eq!(status.entries[4].kind, EntryKind::Untracked);
+    assert!(!status.is_clean());
+
+    let detached =
+        Status::from_porcelain_v2("# branch.oid (initial)\n# branch.head (detached)\n").unwrap();
+    assert_eq!(detached.branch, BranchStatus::default());
+    assert!(detached.is_clean());
+
+    // untracked files make the worktree dirty, but don't change tracked files
+    let untracked = Status::from_porcelain_v2("# branch.head main\n? .env\n").unwrap();
+    assert!(!untracked.is_clean());
+    assert!(!untracked.has_tracked_changes());
+    assert!(status.has_tracked_changes());
+}
@@ -0,0 +1,115 @@
+/// The state of a submodule as reported by `git submodule status`
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubmoduleState {
+    UpToDate,       // the checked out commit matches the superproject
+    Uninitialized,  // `-` the submodule has not been initialized
+    Modified,       // `+` the checked out commit differs from the superproject
+    MergeConflicts, // `U` the submodule has merge conflicts
+}
+
+/// A submodule as returned by `git submodule status`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubmoduleStatus {
+    pub state: SubmoduleState,
+    pub commit: String,
+    pub path: String,
+    pub describe: Option<String>, // the `git describe` of the commit if available
+}
+
+/// A submodule pointer (gitlink) recorded in a tree of the superproject
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gitlink {
+    pub path: String,
+    pub commit: String,
+}
+
+impl Gitlink {
+    /// This function parses the output of `git ls-tree -r <rev>` and keeps
+    /// the submodule entries.
+    pub fn from_ls_tree(output: &str) -> Vec<Self> {
+        output
+            .lines()
+            .filter_map(|line| {
+                let (info, path) = line.split_once('\t')?;
+                let mut fields = info.split_whitespace();
+                let (mode, kind, commit) = (fields.next()?, fields.next()?, fields.next()?);
+                if mode != "160000" || kind != "commit" {
+                    return None;
+                }
+                Some(Gitlink {
+                    path: path.to_string(),
+                    commit: commit.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl SubmoduleStatus {
+    /// This function parses the output of `git submodule status`.
+    pub fn from_submodule_status(output: &str) -> Vec<Self> {
+        output
+            .lines()
+            .filter(|line| line.len() > 1)
+            .filter_map(|line| {
+                let (state, rest) = line.split_at(1);
+                let state = match state {
+                    "-" => SubmoduleState::Uninitialized,
+                    "+" => SubmoduleState::Modified,
+                    "U" => SubmoduleState::MergeConflicts,
+                    _ => SubmoduleState::UpToDate,
+                };
+
+                let mut fields = rest.splitn(3, ' ');
+                let commit = fields.next()?.to_string();
+                let path = fields.next()?.to_string();
+                let describe = fields
+                    .next()
+                    .map(|describe| describe.trim_matches(|c| c == '(' || c == ')').to_string());
+
+                Some(SubmoduleStatus {
+                    state,
+                    commit,
+                    path,
+                    describe,
+                })
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_gitlink_from_ls_tree() {
+    let gitlinks = Gitlink::from_ls_tree(
+        "100644 blob 1a2b3c4d\t.gitmodules\n160000 commit 3f2a1b4c\tbackend\n160000 commit 9e8d7c6b\tservices/api\n",
+    );
+
+    assert_eq!(
+        gitlinks,
+        vec![
+            Gitlink {
+                path: "backend".to_string(),
+                commit: "3f2a1b4c".to_string(),
+            },
+            Gitlink {
+                path: "services/api".to_string(),
+                commit: "9e8d7c6b".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_submodule_status() {
+    let submodules = SubmoduleStatus::from_submodule_status(
+        " 3f2a1b4c backend (v0.1.0)\n-9e8d7c6b frontend\n+1a2b3c4d services/api (heads/main)\n",
+    );
+
+    assert_eq!(submodules.len(), 3);
+    assert_eq!(submodules[0].state, SubmoduleState::UpToDate);
+    assert_eq!(submodules[0].describe, Some("v0.1.0".to_string()));
+    assert_eq!(submodules[1].state, SubmoduleState::Uninitialized);
+    assert_eq!(submodules[1].path, "frontend");
+    assert_eq!(submodules[2].state, SubmoduleState::Modified);
+    assert_eq!(submodules[2].path, "services/api");
+}
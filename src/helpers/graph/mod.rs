@@ -0,0 +1,365 @@
+use crate::models::docker_compose::service::{DependencyCondition, DependsOn};
+use crate::models::docker_compose::DockerCompose;
+use crate::models::project::Project;
+use std::collections::HashMap;
+
+/// How two services of the graph are related
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EdgeKind {
+    DependsOn(Option<DependencyCondition>), // `from` needs `to` to be started, or healthy or completed
+    Contains,                               // `from` is the parent of `to` in project.yaml
+}
+
+/// An edge of the graph, `from` depends on or contains `to`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    pub kind: EdgeKind,
+}
+
+/// The services of the project and how they depend on each other
+#[derive(Debug, Default, PartialEq)]
+pub struct ServiceGraph {
+    pub nodes: Vec<String>, // in the order of the files
+    pub edges: Vec<Edge>,
+}
+
+impl ServiceGraph {
+    /// This function returns the graph of the `depends_on` of the services of
+    /// a compose file, dependencies on undefined services are left out.
+    pub fn from_compose(docker_compose: &DockerCompose) -> Self {
+        let mut graph = ServiceGraph::default();
+
+        for (name, service) in &docker_compose.services {
+            graph.add_node(name);
+            let dependencies = match &service.depends_on {
+                Some(DependsOn::List(services)) => {
+                    services.iter().map(|service| (service, None)).collect()
+                }
+                Some(DependsOn::Map(services)) => services
+                    .iter()
+                    .map(|(service, dependency)| (service, dependency.condition))
+                    .collect(),
+                None => Vec::new(),
+            };
+            for (dependency, condition) in dependencies {
+                if docker_compose.services.contains_key(dependency) {
+                    graph.edges.push(Edge {
+                        from: name.clone(),
+                        to: dependency.clone(),
+                        kind: EdgeKind::DependsOn(condition),
+                    });
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// This function adds the hierarchy of project.yaml, every project
+    /// contains its services.
+    pub fn add_project(&mut self, project: &Project) {
+        self.add_node(&project.name);
+        for service in project.services.iter().flatten() {
+            self.add_node(&service.name);
+            self.edges.push(Edge {
+                from: project.name.clone(),
+                to: service.name.clone(),
+                kind: EdgeKind::Contains,
+            });
+            self.add_project(service);
+        }
+    }
+
+    /// This function returns the services a service directly depends on.
+    pub fn dependencies(&self, node: &str) -> Vec<&str> {
+        self.depends_on()
+            .filter(|edge| edge.from == node)
+            .map(|edge| edge.to.as_str())
+            .collect()
+    }
+
+    /// This function returns the services that directly depend on a service.
+    pub fn dependents(&self, node: &str) -> Vec<&str> {
+        self.depends_on()
+            .filter(|edge| edge.to == node)
+            .map(|edge| edge.from.as_str())
+            .collect()
+    }
+
+    /// This function returns every service a service needs, directly or
+    /// through other services, the closest first.
+    pub fn transitive_dependencies(&self, node: &str) -> Vec<String> {
+        self.reachable(node, |node| self.dependencies(node))
+    }
+
+    /// This function returns every service that needs a service, directly
+    /// or through other services, the closest first.
+    pub fn transitive_dependents(&self, node: &str) -> Vec<String> {
+        self.reachable(node, |node| self.dependents(node))
+    }
+
+    /// This function returns the order the services start in, every service
+    /// after its dependencies and otherwise in the order of the file, or
+    /// the first dependency cycle.
+    pub fn start_order(&self) -> Result<Vec<String>, Vec<String>> {
+        let mut order: Vec<String> = Vec::new();
+
+        while order.len() < self.nodes.len() {
+            let next = self.nodes.iter().find(|node| {
+                !order.contains(node)
+                    && self
+                        .dependencies(node)
+                        .iter()
+                        .all(|dependency| order.iter().any(|started| started == dependency))
+            });
+            match next {
+                Some(node) => order.push(node.clone()),
+                None => return Err(self.cycles().into_iter().next().unwrap_or_default()),
+            }
+        }
+
+        Ok(order)
+    }
+
+    /// This function returns the order the services stop in, every service
+    /// before its dependencies.
+    pub fn stop_order(&self) -> Result<Vec<String>, Vec<String>> {
+        let mut order = self.start_order()?;
+        order.reverse();
+        Ok(order)
+    }
+
+    /// This function finds the cycles of `depends_on`, every cycle once with
+    /// its first service repeated at the end.
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        // 1 while the node is on the current path, 2 once it is done
+        let mut state: HashMap<&str, u8> = HashMap::new();
+        let mut cycles = Vec::new();
+
+        for node in &self.nodes {
+            if !state.contains_key(node.as_str()) {
+                self.find_cycles(node, &mut Vec::new(), &mut state, &mut cycles);
+            }
+        }
+
+        cycles
+    }
+
+    /// This function returns the graph in the DOT language of Graphviz.
+    pub fn to_dot(&self, name: &str) -> String {
+        let mut dot = format!("digraph {} {{\n    rankdir=LR;\n", quote(name));
+
+        for node in &self.nodes {
+            dot.push_str(&format!("    {};\n", quote(node)));
+        }
+        for edge in &self.edges {
+            let attributes = match edge.kind {
+                EdgeKind::DependsOn(Some(condition)) => {
+                    format!(" [label={}]", quote(condition_name(condition)))
+                }
+                EdgeKind::DependsOn(None) => String::new(),
+                EdgeKind::Contains => " [style=dashed, arrowhead=none]".to_string(),
+            };
+            dot.push_str(&format!(
+                "    {} -> {}{};\n",
+                quote(&edge.from),
+                quote(&edge.to),
+                attributes
+            ));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// This function returns the graph as a Mermaid flowchart.
+    pub fn to_mermaid(&self) -> String {
+        let mut mermaid = "flowchart LR\n".to_string();
+
+        for node in &self.nodes {
+            mermaid.push_str(&format!("    {}[\"{}\"]\n", mermaid_id(node), node));
+        }
+        for edge in &self.edges {
+            let arrow = match edge.kind {
+                EdgeKind::DependsOn(Some(condition)) => {
+                    format!("-->|{}|", condition_name(condition))
+                }
+                EdgeKind::DependsOn(None) => "-->".to_string(),
+                EdgeKind::Contains => "-.-".to_string(),
+            };
+            mermaid.push_str(&format!(
+                "    {} {} {}\n",
+                mermaid_id(&edge.from),
+                arrow,
+                mermaid_id(&edge.to)
+            ));
+        }
+
+        mermaid
+    }
+
+    // ## add_node(name: &str)
+    // This function adds a node if the graph doesn't have it yet
+    fn add_node(&mut self, name: &str) {
+        if !self.nodes.iter().any(|node| node == name) {
+            self.nodes.push(name.to_string());
+        }
+    }
+
+    // ## depends_on() -> impl Iterator<Item = &Edge>
+    // This function returns the `depends_on` edges of the graph
+    fn depends_on(&self) -> impl Iterator<Item = &Edge> {
+        self.edges
+            .iter()
+            .filter(|edge| matches!(edge.kind, EdgeKind::DependsOn(_)))
+    }
+
+    // ## reachable(node: &str, next: F) -> Vec<String>
+    // This function walks the graph breadth first from a node
+    fn reachable<'a, F>(&'a self, node: &str, next: F) -> Vec<String>
+    where
+        F: Fn(&str) -> Vec<&'a str>,
+    {
+        let mut found: Vec<String> = Vec::new();
+        let mut queue = vec![node.to_string()];
+
+        while !queue.is_empty() {
+            let current = queue.remove(0);
+            for neighbour in next(&current) {
+                if neighbour != node && !found.iter().any(|found| found == neighbour) {
+                    found.push(neighbour.to_string());
+                    queue.push(neighbour.to_string());
+                }
+            }
+        }
+
+        found
+    }
+
+    // ## find_cycles(node, path, state, cycles)
+    // This function walks the dependencies of a node depth first and records
+    // every edge back to a node of the current path as a cycle
+    fn find_cycles<'a>(
+        &'a self,
+        node: &'a str,
+        path: &mut Vec<&'a str>,
+        state: &mut HashMap<&'a str, u8>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        state.insert(node, 1);
+        path.push(node);
+
+        for dependency in self.dependencies(node) {
+            match state.get(dependency) {
+                None => self.find_cycles(dependency, path, state, cycles),
+                Some(1) => {
+                    let start = path.iter().position(|open| *open == dependency).unwrap();
+                    let mut cycle = path[start..]
+                        .iter()
+                        .map(|node| node.to_string())
+                        .collect::<Vec<String>>();
+                    cycle.push(dependency.to_string());
+                    cycles.push(cycle);
+                }
+                _ => {}
+            }
+        }
+
+        path.pop();
+        state.insert(node, 2);
+    }
+}
+
+// ## condition_name(condition: DependencyCondition) -> &'static str
+// This function returns a condition as it is written in the compose file
+fn condition_name(condition: DependencyCondition) -> &'static str {
+    match condition {
+        DependencyCondition::Started => "service_started",
+        DependencyCondition::Healthy => "service_healthy",
+        DependencyCondition::CompletedSuccessfully => "service_completed_successfully",
+    }
+}
+
+// ## quote(id: &str) -> String
+// This function quotes an identifier of the DOT language
+fn quote(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// ## mermaid_id(name: &str) -> String
+// This function returns a node id Mermaid accepts, the name is its label
+fn mermaid_id(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[test]
+fn test_graph_service_graph() {
+    let docker_compose = DockerCompose::from_yaml(
+        r#"services:
+  web:
+    image: web
+    depends_on: [api]
+  api:
+    image: api
+    depends_on:
+      db:
+        condition: service_healthy
+      migrations:
+        condition: service_completed_successfully
+  migrations:
+    image: api
+    depends_on: [db]
+  db:
+    image: postgres
+"#,
+    )
+    .unwrap();
+    let graph = ServiceGraph::from_compose(&docker_compose);
+
+    assert_eq!(
+        graph.start_order(),
+        Ok(vec![
+            "db".to_string(),
+            "migrations".to_string(),
+            "api".to_string(),
+            "web".to_string()
+        ])
+    );
+    assert_eq!(graph.stop_order().unwrap()[0], "web");
+    assert_eq!(
+        graph.transitive_dependencies("api"),
+        vec!["db".to_string(), "migrations".to_string()]
+    );
+    assert_eq!(
+        graph.transitive_dependents("db"),
+        vec![
+            "api".to_string(),
+            "migrations".to_string(),
+            "web".to_string()
+        ]
+    );
+    assert!(graph
+        .to_dot("shop")
+        .contains("    \"api\" -> \"db\" [label=\"service_healthy\"];\n"));
+    assert!(graph
+        .to_mermaid()
+        .contains("    api -->|service_healthy| db\n"));
+
+    let cyclic = DockerCompose::from_yaml(
+        "services:\n  a:\n    depends_on: [b]\n  b:\n    depends_on: [c]\n  c:\n    depends_on: [a]\n",
+    )
+    .unwrap();
+    assert_eq!(
+        ServiceGraph::from_compose(&cyclic).start_order(),
+        Err(vec![
+            "a".to_string(),
+            "b".to_string(),
+            "c".to_string(),
+            "a".to_string()
+        ])
+    );
+}
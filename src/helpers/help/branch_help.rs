@@ -0,0 +1,23 @@
+//multiline variable for the branch help file
+pub const BRANCH_HELP: &str = r#"
+Usage: 
+    tools branch <subcommand> [name] [OPTIONS]
+
+Options:
+    -h, --help              Prints help information
+    --only <services>       Only uses the given comma separated services
+    -f, --force             Deletes branches even if they are not merged
+
+Subcommands:
+    create <name>           Creates and checks out the branch in every service that doesn't have it
+    checkout <name>         Checks out the branch in every service that has it
+    delete <name>           Deletes the branch in every service that has it
+    list [name]             Prints which services have which branches
+                            (* checked out, + exists, - missing)
+
+create and checkout refuse to run if any service has uncommitted changes.
+"#;
+
+pub fn print_help() {
+    println!("{}", BRANCH_HELP);
+}
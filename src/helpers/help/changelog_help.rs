@@ -0,0 +1,22 @@
+//multiline variable for the changelog help file
+pub const CHANGELOG_HELP: &str = r#"
+Usage: 
+    tools changelog <from>..<to> [OPTIONS]
+
+Options:
+    -h, --help              Prints help information
+    -o, --output <file>     Writes the changelog to a file instead of printing it
+
+Renders a Markdown changelog of the project between two refs, e.g.
+`tools changelog v0.1.0..v0.2.0`. `<to>` defaults to HEAD.
+
+For every submodule whose pointer moved in the range, the commits of the
+service between the two pointers are grouped by their conventional commit
+type (feat, fix, perf, ...). Breaking changes are listed first and only
+there, commits that don't follow conventional commits are listed under
+Other Changes.
+"#;
+
+pub fn print_help() {
+    println!("{}", CHANGELOG_HELP);
+}
@@ -0,0 +1,87 @@
+//multiline variable for the compose help file
+pub const COMPOSE_HELP: &str = r#"
+Usage: 
+    tools compose add service <name> (--image <image> | --build <context>) [OPTIONS]
+    tools compose add network|volume <name> [--driver <driver>]
+    tools compose add secret|config <name> (--file <path> | --environment <variable>)
+    tools compose add network|secret|config <name> --service <service>...
+    tools compose add volume <source> --service <service>... --target <path> [--read-only]
+    tools compose add env <service> <KEY[=VALUE]>...
+    tools compose add port <service> <[[host_ip:]published:]target[/protocol]>...
+    tools compose remove service|network|volume|secret|config <name>...
+    tools compose remove network|volume|secret|config <name> --service <service>...
+    tools compose remove env <service> <KEY>...
+    tools compose remove port <service> <port>...
+    tools compose rename <service> <new-name>
+    tools compose lint
+    tools compose config [--env <name>] [--env-file <path>...]
+    tools compose generate [--dry-run]
+
+Options:
+    -h, --help              Prints help information
+    --image <image>         The image of the new service
+    --build <context>       The build context of the new service
+    --port <port>           Publishes a port of the new service, can be repeated
+    --env <KEY[=VALUE]>     Sets an environment variable of the new service, can be repeated
+    --network <network>     Attaches the new service to a network, can be repeated
+    --driver <driver>       The driver of a new network or volume
+    --file <path>           The file of a new secret or config
+    --environment <name>    The environment variable of a new secret or config
+    --service <service>     Adds the network, volume, secret or config to a service
+                            instead of the top level, or removes it from the service
+    --target <path>         Where the volume is mounted in the container
+    --read-only             Mounts the volume read only
+    --env <name>            Uses an environment of config.yaml instead of TOOLS_ENV
+    --env-file <path>       Reads the variables from this file instead of .env, can be repeated
+    --dry-run               Prints what generate would change without writing it
+
+Edits the compose file of config.yaml, the first one if docker_compose
+lists several. Networks, named volumes, secrets
+and configs must exist at the top level before a service uses them, and
+nothing that is still used by a service can be removed. Renaming a
+service updates the depends_on, links, extends and volumes_from of the
+other services. Only the changed entries are rewritten, comments and the
+order of the file are kept.
+
+`tools compose lint` prints the problems of the compose files with their
+file and line, and exits with 1 if any is an error:
+
+    error    a network, volume, secret, config or service that isn't defined
+    error    a cycle of depends_on
+    error    a host port published twice
+    error    a build context that doesn't exist
+    error    a service with neither an image nor a build
+    warning  a key compose doesn't know, usually indented at the wrong level
+
+`tools compose config` prints the compose file the way compose reads it.
+The variables are interpolated from the .env file next to the compose
+file, overridden by the shell and by the project version variables of
+`tools version env`, and the env_file of every service is merged into
+its environment. `$$` is a literal `$`, and a missing `${VAR?error}` or
+`${VAR:?error}` stops with the error. With an environment its env files
+replace .env, its variables win over the shell, and the services of
+profiles it doesn't enable are left out, like with COMPOSE_PROFILES.
+
+`tools compose generate` creates a service for every service of
+project.yaml without services of its own, built from its path and
+publishing the default port of its framework, e.g. 8080 for actix or
+3000 for nextjs, on the next free host port. Every service joins the
+network of the project that declares it and the networks of the services
+in its depends_on. Existing services are only completed: a build is only
+added without an image, a port only if none is published, and networks
+and depends_on are added to the ones already there.
+
+docker_compose in config.yaml is a file or a list of files merged in
+order the way compose merges them: maps are merged, sequences like ports
+are appended, environment and labels are merged by name and volumes by
+their target, command and entrypoint are replaced. In an override file
+`!reset` removes a value and `!override` replaces it instead of merging.
+A single file is followed by its override file, e.g.
+docker-compose.override.yaml, if it exists. Lint, config, graph, lock
+and the container engine all see the merged files, or the compose_files
+of the selected environment.
+"#;
+
+pub fn print_help() {
+    println!("{}", COMPOSE_HELP);
+}
@@ -0,0 +1,35 @@
+//multiline variable for the dockerfile help file
+pub const DOCKERFILE_HELP: &str = r#"
+Usage: 
+    tools dockerfile generate <service> [--force]
+
+Options:
+    -h, --help              Prints help information
+    -f, --force             Overwrites the Dockerfile and .dockerignore of the service if they exist
+
+Writes a multi-stage Dockerfile and a .dockerignore in the directory of
+a service of project.yaml, from the template of its frameworks, then of
+its languages. The dependencies are installed in their own layer, so
+they are only rebuilt when the manifests change. The compose service of
+the service is created or changed to build from the Dockerfile.
+
+Built-in templates:
+    nextjs                  nextjs
+    spa                     react, vue, svelte, served by nginx
+    python                  django, fastapi, flask, python
+    node                    express, nestjs, javascript, typescript
+    rust                    actix, axum, rocket, rust
+    go                      gin, go
+
+A `templates/dockerfile/<language or framework>/Dockerfile` in the
+project, with an optional .dockerignore next to it, replaces the
+built-in templates. Templates use the `{{ name }}` placeholders of
+`tools init`, and {{ service_name }}, {{ service_path }}, {{ port }},
+{{ binary }}, {{ command }} and {{ build_output }}.
+
+    tools dockerfile generate api
+"#;
+
+pub fn print_help() {
+    println!("{}", DOCKERFILE_HELP);
+}
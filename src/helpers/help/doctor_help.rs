@@ -0,0 +1,23 @@
+//multiline variable for the doctor help file
+pub const DOCTOR_HELP: &str = r#"
+Usage: 
+    tools doctor
+
+Options:
+    -h, --help              Prints help information
+
+Checks the environment the tools depend on and prints a fix for every
+problem:
+    - git, docker and docker compose are installed in a supported version,
+      gh is optional
+    - the docker daemon is running and the current user can access it
+    - config.yaml, project.yaml, the compose file and .gitmodules parse,
+      and every submodule is initialized
+
+Exits with 1 if any required check failed, warnings don't change the
+exit code.
+"#;
+
+pub fn print_help() {
+    println!("{}", DOCTOR_HELP);
+}
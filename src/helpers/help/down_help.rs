@@ -0,0 +1,17 @@
+//multiline variable for the down help file
+pub const DOWN_HELP: &str = r#"
+Usage: 
+    tools down [OPTIONS]
+
+Options:
+    -h, --help              Prints help information
+    --env <name>            Uses an environment of config.yaml instead of TOOLS_ENV
+    -v, --volumes           Also removes the volumes of the project
+
+Stops and removes the containers of the project with the container engine
+of config.yaml.
+"#;
+
+pub fn print_help() {
+    println!("{}", DOWN_HELP);
+}
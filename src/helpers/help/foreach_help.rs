@@ -0,0 +1,20 @@
+//multiline variable for the foreach help file
+pub const FOREACH_HELP: &str = r#"
+Usage: 
+    tools foreach [OPTIONS] -- <command> [arguments]
+
+Options:
+    -h, --help              Prints help information
+    -p, --parallel <N>      Runs the command in up to N services at once (default: 1)
+    --only <services>       Only runs the command in the given comma separated services
+    --fail-fast             Stops starting new services after the first failure
+    --keep-going            Runs the command in every service even if one fails (default)
+
+Examples:
+    tools foreach -- git pull
+    tools foreach --parallel 4 --only backend,frontend -- cargo test
+"#;
+
+pub fn print_help() {
+    println!("{}", FOREACH_HELP);
+}
@@ -0,0 +1,25 @@
+//multiline variable for the graph help file
+pub const GRAPH_HELP: &str = r#"
+Usage: 
+    tools graph [--format text|dot|mermaid] [--project]
+    tools graph order [--stop]
+    tools graph deps <service> [--reverse]
+
+Options:
+    -h, --help              Prints help information
+    --format <format>       Prints the graph as text (default), DOT for Graphviz or a Mermaid flowchart
+    --project               Adds the services of project.yaml, linked to the project that contains them, not for order and deps
+    --stop                  Prints the order the services stop in instead of the start order
+    --reverse               Prints the services that depend on the service instead of its dependencies
+
+Builds the graph of the depends_on of the services in the compose file.
+`order` prints every service after the services it depends on, and
+fails if the dependencies have a cycle. `deps` prints every service a
+service needs, directly or through other services.
+
+    tools graph --format dot | dot -Tsvg > services.svg
+"#;
+
+pub fn print_help() {
+    println!("{}", GRAPH_HELP);
+}
@@ -0,0 +1,28 @@
+//multiline variable for the hooks help file
+pub const HOOKS_HELP: &str = r#"
+Usage: 
+    tools hooks install [OPTIONS]
+    tools hooks status [OPTIONS]
+
+Options:
+    -h, --help              Prints help information
+    --only <services>       Only the services in the comma separated list, without the project
+    -f, --force             Replaces hooks that were not installed by tools
+
+Installs the configured git hooks in the project and every service
+repository, submodules get them in their git directory under modules/.
+`status` shows the hooks that are missing, outdated or in the way and
+exits with 1 if any hook drifted.
+
+The hooks are configured in config.yaml:
+
+    hooks:
+      commit_msg: true      # check that messages are conventional commits (default: true)
+      secret_scan: true     # refuse staged changes that look like secrets (default: true)
+      pre_commit:           # commands run in the repository before every commit
+        - cargo fmt --check
+"#;
+
+pub fn print_help() {
+    println!("{}", HOOKS_HELP);
+}
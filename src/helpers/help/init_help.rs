@@ -5,6 +5,12 @@ Usage:
 
 Options:
     -h, --help      Prints help information
+    --from-project [file] [--submodule]
+                    Clones the services declared in a project file (default: project.yaml)
+                    that are missing on disk, optionally registering them as submodules
+    --locked [version]
+                    Checks out the service commits pinned in tools.lock for the project
+                    version (default: the current project version)
 
 Subcommands:
     none            Initializes the project
@@ -0,0 +1,16 @@
+//multiline variable for the lock help file
+pub const LOCK_HELP: &str = r#"
+Usage: 
+    tools lock [OPTIONS]
+
+Options:
+    -h, --help      Prints help information
+    --images        Also records the digest of every image in the compose file
+
+Records the commit of every service repository, submodules and nested
+clones, for the current project version in tools.lock. Use `tools init --locked [version]` to check them out again.
+"#;
+
+pub fn print_help() {
+    println!("{}", LOCK_HELP);
+}
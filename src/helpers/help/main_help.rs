@@ -5,16 +5,27 @@ Usage:
     
 Arguments:
     help, -h, --help        Prints this help message
-    version, --version      Prints the version of the program
+    -v, --version           Prints the version of the program and the project
     -v(#)                   Sets the verbosity level of the program (0-5) (default: 3) - (Not implemented yet)
     -q                      Sets the verbosity level to 0 (same as -v0) - (Not implemented yet)
 
 Commands:
-    down      Stops the project - (Not implemented yet)
+    branch    Creates, checks out, deletes or lists a branch across every service
+    changelog Renders release notes from the conventional commits of every service
+    compose   Edits, lints, resolves or generates the compose file
+    dockerfile Generates the Dockerfile of a service from its languages and frameworks
+    doctor    Checks the required programs, the container engine and the project files
+    down      Stops and removes the containers of the project
+    foreach   Runs a command in every service of the project
+    graph     Prints the dependency graph of the services and their start order
+    hooks     Installs the configured git hooks in the project and every service
     init      Initializes the project
-    up        Starts the project - (Not implemented yet)
-    watch     Starts the project and watches for changes - (Not implemented yet)
-    status    Prints the status of the project - (Not implemented yet)
+    lock      Pins the commit of every service for the project version in tools.lock
+    release   Bumps the project version, writes the changelog and tags the release
+    up        Starts the project in the background
+    version   Prints, sets or bumps the project version
+    watch     Starts the project and syncs or rebuilds the services when their files change
+    status    Prints the state of every container of the project
 
 For more information on a command, run `tools help [command]
 
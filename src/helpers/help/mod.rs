@@ -1,10 +1,40 @@
+pub mod branch_help;
+pub mod changelog_help;
+pub mod compose_help;
+pub mod dockerfile_help;
+pub mod doctor_help;
+pub mod down_help;
+pub mod foreach_help;
+pub mod graph_help;
+pub mod hooks_help;
 pub mod init_help;
+pub mod lock_help;
 pub mod main_help;
+pub mod release_help;
+pub mod status_help;
+pub mod up_help;
+pub mod version_help;
+pub mod watch_help;
 
 pub fn router(args: &[String]) {
-    match args.get(0) {
+    match args.first() {
         Some(arg0) => match arg0.as_str() {
+            "branch" => branch_help::print_help(),
+            "changelog" => changelog_help::print_help(),
+            "compose" => compose_help::print_help(),
+            "dockerfile" => dockerfile_help::print_help(),
+            "doctor" => doctor_help::print_help(),
+            "down" => down_help::print_help(),
+            "foreach" => foreach_help::print_help(),
+            "graph" => graph_help::print_help(),
+            "hooks" => hooks_help::print_help(),
             "init" => init_help::print_help(),
+            "lock" => lock_help::print_help(),
+            "release" => release_help::print_help(),
+            "status" => status_help::print_help(),
+            "up" => up_help::print_help(),
+            "version" => version_help::print_help(),
+            "watch" => watch_help::print_help(),
             _ => main_help::print_help(),
         },
         None => {
@@ -0,0 +1,25 @@
+//multiline variable for the release help file
+pub const RELEASE_HELP: &str = r#"
+Usage: 
+    tools release [major|minor|patch|<version>] [OPTIONS]
+
+Options:
+    -h, --help              Prints help information
+    -n, --dry-run           Prints the new version, the changelog and the tags without changing anything
+    --pre <identifier>      Releases a prerelease, e.g. `--pre rc.1`
+    --from <ref>            The previous release, defaults to the tag of the current version
+    --tag-services          Also tags every service that changed as <project>/v<version>
+    --changelog <file>      The changelog to prepend the release to (default: CHANGELOG.md)
+
+Without a bump the version is detected from the conventional commits of the
+project and every service since the previous release: major for breaking
+changes, minor for features and patch otherwise.
+
+The new version is written to config.yaml and project.yaml, the changelog
+is prepended and the changes are committed and tagged v<version>. Nothing
+is pushed, run `git push --follow-tags` afterwards.
+"#;
+
+pub fn print_help() {
+    println!("{}", RELEASE_HELP);
+}
@@ -0,0 +1,26 @@
+//multiline variable for the status help file
+pub const STATUS_HELP: &str = r#"
+Usage: 
+    tools status [OPTIONS]
+
+Options:
+    -h, --help              Prints help information
+    --env <name>            Uses an environment of config.yaml instead of TOOLS_ENV
+
+Prints the state of every container of the project, the same for every
+container engine. The legacy docker-compose only reports whether a
+service is running.
+
+The container engine is configured in config.yaml:
+
+    container_engine: auto  # the first of the engines below that is installed (default)
+                            # docker:         docker compose (Compose v2)
+                            # docker-compose: docker-compose (Compose v1)
+                            # podman:         podman compose
+                            # podman-compose: podman-compose
+                            # nerdctl:        nerdctl compose
+"#;
+
+pub fn print_help() {
+    println!("{}", STATUS_HELP);
+}
@@ -0,0 +1,30 @@
+//multiline variable for the up help file
+pub const UP_HELP: &str = r#"
+Usage: 
+    tools up [OPTIONS] [services...]
+
+Options:
+    -h, --help              Prints help information
+    --env <name>            Uses an environment of config.yaml instead of TOOLS_ENV
+    --build                 Builds the images before starting the containers
+
+Starts the services of the compose file in the background, every service
+if none are given, with the container engine of config.yaml. The project
+version is passed to compose as TOOLS_PROJECT_VERSION_TAG and the other
+variables of `tools version env`.
+
+An environment selects the compose files, env files, profiles and
+variables the project runs with. It is chosen with --env or TOOLS_ENV,
+and declared in config.yaml:
+    environments:
+      dev:
+        compose_files: [docker-compose.yaml, docker-compose.dev.yaml]
+        env_files: [.env, .env.dev]   # later files win
+        profiles: [debug]
+        variables:                    # win over the env files and the shell
+          LOG_LEVEL: debug
+"#;
+
+pub fn print_help() {
+    println!("{}", UP_HELP);
+}
@@ -0,0 +1,32 @@
+//multiline variable for the version help file
+pub const VERSION_HELP: &str = r#"
+Usage: 
+    tools version
+    tools version env [--export]
+    tools version set <version> [OPTIONS]
+    tools version bump <major|minor|patch> [OPTIONS]
+
+Options:
+    -h, --help          Prints help information
+    -n, --dry-run       Prints the diff of every file without changing anything
+    --pre <identifier>  Bumps to a prerelease, e.g. `--pre rc.1`
+    --manifests         Also sets the version in the Cargo.toml, package.json and
+                        pyproject.toml at the root of every service
+    --images            Also sets the tag of every image the compose file builds
+    --export            Prefixes the environment variables with `export` for shells
+
+Without arguments the project version is printed. The new version is
+written to config.yaml and project.yaml, and a diff of every file touched
+is shown. Images that are only pulled, e.g. `postgres:15`, are left as they are.
+
+With `version_mode: git` in config.yaml the effective version is computed
+from git: the release commit tagged v<version> has the configured version,
+later commits are development builds like 1.4.1-dev.3+g3f2a1b4 and
+uncommitted changes add a -dirty marker. `tools version env` prints it as
+TOOLS_PROJECT_VERSION, TOOLS_PROJECT_VERSION_TAG, TOOLS_GIT_COMMIT and
+TOOLS_GIT_DIRTY, e.g. for `docker compose --env-file`.
+"#;
+
+pub fn print_help() {
+    println!("{}", VERSION_HELP);
+}
@@ -0,0 +1,33 @@
+//multiline variable for the watch help file
+pub const WATCH_HELP: &str = r#"
+Usage: 
+    tools watch [OPTIONS] [services...]
+
+Options:
+    -h, --help              Prints help information
+    --env <name>            Uses an environment of config.yaml instead of TOOLS_ENV
+
+Starts the services of the compose file, every service if none are
+given, and syncs or rebuilds them when their files change until it is
+stopped with Ctrl+C. What happens on a change is set by the
+`develop.watch` rules of every service:
+
+    services:
+      api:
+        build: ./api
+        develop:
+          watch:
+            - action: sync
+              path: ./api/src
+              target: /app/src
+            - action: rebuild
+              path: ./api/Cargo.toml
+
+Needs `compose watch`, Docker with the Compose plugin 2.22 or newer, or
+podman compose running it. docker-compose, podman-compose and nerdctl
+can't watch.
+"#;
+
+pub fn print_help() {
+    println!("{}", WATCH_HELP);
+}
@@ -1,7 +1,16 @@
 pub mod config;
+pub mod container_engine;
+pub mod conventional_commits;
+pub mod diff;
 pub mod docker;
+pub mod dockerfile;
 pub mod filesystem;
 pub mod git;
+pub mod graph;
 pub mod help;
 pub mod program_checker;
+pub mod services;
+pub mod templates;
 pub mod user_input;
+pub mod version;
+pub mod yaml_edit;
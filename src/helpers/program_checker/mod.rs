@@ -0,0 +1,253 @@
+use crate::helpers::container_engine::Engine;
+use semver::Version;
+use std::process::Command;
+
+/// A program the tools depend on and the oldest version that is supported
+#[derive(Debug, PartialEq)]
+pub struct Program {
+    pub name: &'static str,               // the name shown to the user
+    pub command: &'static [&'static str], // the command printing the version of the program
+    pub minimum: (u64, u64, u64),         // the oldest supported version
+    pub required: bool,                   // false for programs that only enable some features
+    pub install: &'static str,            // how to install or upgrade the program
+}
+
+/// The git client, every command working with the service repositories needs it
+pub const GIT: Program = Program {
+    name: "git",
+    command: &["git", "--version"],
+    minimum: (2, 20, 0),
+    required: true,
+    install: "install git 2.20 or newer from https://git-scm.com/downloads",
+};
+
+/// The GitHub CLI, only needed for the GitHub API when there is no token
+pub const GH: Program = Program {
+    name: "gh",
+    command: &["gh", "--version"],
+    minimum: (2, 0, 0),
+    required: false,
+    install:
+        "install the GitHub CLI from https://cli.github.com, or set github_api_token in config.yaml",
+};
+
+/// The programs checked by `tools doctor` besides the container engine
+pub const PROGRAMS: [Program; 2] = [GIT, GH];
+
+/// Something a command needs from the machine before it can run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Requirement {
+    /// git in a supported version
+    Git,
+    /// the configured container engine and its compose implementation
+    ContainerEngine,
+}
+
+/// The result of checking a program
+#[derive(Debug, PartialEq)]
+pub enum ProgramStatus {
+    Found(Version),  // installed in a supported version
+    TooOld(Version), // installed but older than the minimum
+    Unknown(String), // installed but its version couldn't be read, with the reason
+    Missing,         // not installed or not on the PATH
+}
+
+impl ProgramStatus {
+    /// This function returns what is wrong with the program, None if it is
+    /// installed in a supported version.
+    pub fn problem(&self, program: &Program) -> Option<String> {
+        let (major, minor, patch) = program.minimum;
+        match self {
+            ProgramStatus::Found(_) => None,
+            ProgramStatus::TooOld(version) => Some(format!(
+                "{} {} is older than the minimum {}",
+                program.name,
+                version,
+                Version::new(major, minor, patch)
+            )),
+            ProgramStatus::Unknown(reason) => Some(format!(
+                "couldn't read the version of {}: {}",
+                program.name, reason
+            )),
+            ProgramStatus::Missing => Some(format!(
+                "{} is not installed or not on the PATH",
+                program.name
+            )),
+        }
+    }
+}
+
+/// ## check_requirement(requirement: Requirement) -> Vec<(String, String)>
+/// This function checks a requirement of a command
+///
+/// ### Arguments
+/// - requirement: Requirement - The requirement to check
+///
+/// ### Returns
+/// - Vec<(String, String)> - What is missing and how to get it, empty if the requirement is met
+pub fn check_requirement(requirement: Requirement) -> Vec<(String, String)> {
+    let programs: &[Program] = match requirement {
+        Requirement::Git => &[GIT],
+        Requirement::ContainerEngine => match Engine::from_config() {
+            Ok(engine) => engine.programs(),
+            Err(error) => {
+                return vec![(
+                    error,
+                    "install Docker with the Compose plugin, or podman with podman-compose"
+                        .to_string(),
+                )]
+            }
+        },
+    };
+
+    programs
+        .iter()
+        .filter_map(|program| {
+            check_program(program)
+                .problem(program)
+                .map(|problem| (problem, program.install.to_string()))
+        })
+        .collect()
+}
+
+/// ## require(command: &str, requirements: &[Requirement])
+/// This function checks the requirements of a command before it has any
+/// side effects, and exits with everything that is missing if any fails
+///
+/// ### Arguments
+/// - command: &str - The command, for the error message
+/// - requirements: &[Requirement] - What the command needs
+///
+/// ### Returns
+/// - nothing, exits with 1 if a requirement isn't met
+pub fn require(command: &str, requirements: &[Requirement]) {
+    let missing = requirements
+        .iter()
+        .flat_map(|requirement| check_requirement(*requirement))
+        .collect::<Vec<(String, String)>>();
+
+    if missing.is_empty() {
+        return;
+    }
+
+    eprintln!("`tools {}` can't run:", command);
+    for (problem, install) in missing {
+        eprintln!("  - {}", problem);
+        eprintln!("    {}", install);
+    }
+    eprintln!("Run `tools doctor` to check the whole environment");
+    std::process::exit(1);
+}
+
+/// ## check_program(program: &Program) -> ProgramStatus
+/// This function runs the version command of a program and compares its
+/// version with the minimum
+///
+/// ### Arguments
+/// - program: &Program - The program to check
+///
+/// ### Returns
+/// - ProgramStatus - The status of the program
+pub fn check_program(program: &Program) -> ProgramStatus {
+    let output = match Command::new(program.command[0])
+        .args(&program.command[1..])
+        .output()
+    {
+        Ok(output) => output,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            return ProgramStatus::Missing
+        }
+        Err(error) => return ProgramStatus::Unknown(error.to_string()),
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // plugins like `docker compose` are missing when the host command doesn't know them
+        if stderr.contains("unknown command") || stderr.contains("is not a docker command") {
+            return ProgramStatus::Missing;
+        }
+        let reason = stderr.lines().next().unwrap_or_default().trim();
+        return ProgramStatus::Unknown(reason.to_string());
+    }
+
+    match parse_version(&stdout) {
+        Some(version) => {
+            let (major, minor, patch) = program.minimum;
+            if version < Version::new(major, minor, patch) {
+                ProgramStatus::TooOld(version)
+            } else {
+                ProgramStatus::Found(version)
+            }
+        }
+        None => ProgramStatus::Unknown(format!("unexpected version output: {}", stdout.trim())),
+    }
+}
+
+/// ## parse_version(output: &str) -> Option<Version>
+/// This function finds the first `major.minor[.patch]` version in the
+/// output of a version command, e.g. `Docker version 24.0.5, build ced0996`
+///
+/// ### Arguments
+/// - output: &str - The output of the version command
+///
+/// ### Returns
+/// - Option<Version> - The version, without prerelease or build metadata
+pub fn parse_version(output: &str) -> Option<Version> {
+    output
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .filter(|word| word.contains('.'))
+        .find_map(|word| {
+            let mut numbers = word
+                .trim_matches('.')
+                .split('.')
+                .map(|number| number.parse::<u64>());
+            let major = numbers.next()?.ok()?;
+            let minor = numbers.next()?.ok()?;
+            let patch = numbers.next().and_then(|patch| patch.ok()).unwrap_or(0);
+            Some(Version::new(major, minor, patch))
+        })
+}
+
+#[test]
+fn test_program_checker_parse_version() {
+    assert_eq!(
+        parse_version("git version 2.39.2 (Apple Git-143)\n"),
+        Some(Version::new(2, 39, 2))
+    );
+    assert_eq!(
+        parse_version("Docker version 24.0.5, build ced0996"),
+        Some(Version::new(24, 0, 5))
+    );
+    assert_eq!(
+        parse_version("Docker Compose version v2.20.2-desktop.1"),
+        Some(Version::new(2, 20, 2))
+    );
+    assert_eq!(
+        parse_version(
+            "gh version 2.32.1 (2023-07-18)\nhttps://github.com/cli/cli/releases/tag/v2.32.1"
+        ),
+        Some(Version::new(2, 32, 1))
+    );
+    assert_eq!(
+        parse_version("podman version 4.6"),
+        Some(Version::new(4, 6, 0))
+    );
+    assert_eq!(parse_version("unknown"), None);
+}
+
+#[test]
+fn test_program_checker_problem() {
+    assert_eq!(
+        ProgramStatus::Found(Version::new(2, 39, 2)).problem(&GIT),
+        None
+    );
+    assert_eq!(
+        ProgramStatus::TooOld(Version::new(2, 17, 1)).problem(&GIT),
+        Some("git 2.17.1 is older than the minimum 2.20.0".to_string())
+    );
+    assert_eq!(
+        ProgramStatus::Missing.problem(&GH),
+        Some("gh is not installed or not on the PATH".to_string())
+    );
+}
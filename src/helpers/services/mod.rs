@@ -0,0 +1,123 @@
+use crate::controller::init::submodules::get_submodule_paths;
+use crate::helpers::config::project::get_project;
+use crate::helpers::filesystem::dir::map_dirs;
+use crate::helpers::git::Git;
+use std::path::Path;
+
+/// ## get_service_paths() -> Vec<(String, String, bool)>
+/// This function returns every service of the project, whether it is a
+/// git submodule listed in `.gitmodules` or a directory of a service of
+/// `project.yaml`, its `path` or its name when it only declares a
+/// repository. Services listed in both are only returned once.
+///
+/// ### Returns
+/// - Vec<(String, String, bool)> - vector of tuples containing information about the services
+///   - (String, String, bool) - (service absolute path, service path, service exists)
+pub fn get_service_paths() -> Vec<(String, String, bool)> {
+    let mut service_paths = get_submodule_paths();
+
+    // add the services declared in project.yaml that are not submodules
+    let declared_paths = get_declared_service_paths()
+        .into_iter()
+        .filter(|declared| {
+            !service_paths
+                .iter()
+                .any(|service| normalize_path(&service.1) == *declared)
+        })
+        .collect::<Vec<String>>();
+
+    service_paths.append(&mut map_dirs(declared_paths));
+    service_paths
+}
+
+/// ## get_declared_service_paths() -> Vec<String>
+/// This function returns the normalized paths of the services of
+/// `project.yaml` that live in a directory of their own, their `path` or
+/// their name when they only declare a repository, or nothing if there is
+/// no project file
+///
+/// ### Returns
+/// - Vec<String> - the service paths relative to the project root
+pub fn get_declared_service_paths() -> Vec<String> {
+    let mut declared_paths: Vec<String> = Vec::new();
+
+    for (_, path) in get_project()
+        .map(|project| project.get_service_paths())
+        .unwrap_or_default()
+    {
+        let path = normalize_path(&path);
+        if !declared_paths.contains(&path) {
+            declared_paths.push(path);
+        }
+    }
+
+    declared_paths
+}
+
+/// ## get_service_repositories(only: &[String]) -> Vec<(String, Git)>
+/// This function returns every service that is its own git repository,
+/// submodules and nested clones, skipping plain directories of a monorepo
+///
+/// ### Arguments
+/// - only: &[String] - the service paths or names to keep, empty keeps every service
+///
+/// ### Returns
+/// - Vec<(String, Git)> - the service path and repository of every service
+pub fn get_service_repositories(only: &[String]) -> Vec<(String, Git)> {
+    filter_service_paths(get_service_paths(), only)
+        .into_iter()
+        .filter(|service| service.2 && Path::new(&service.0).join(".git").exists())
+        .map(|service| (service.1, Git::new(service.0)))
+        .collect()
+}
+
+/// ## filter_service_paths(service_paths, only: &[String]) -> Vec<(String, String, bool)>
+/// This function keeps the services matching one of the `--only` filters,
+/// or every service if there are no filters
+///
+/// ### Arguments
+/// - service_paths: Vec<(String, String, bool)> - the services as returned by `get_service_paths()`
+/// - only: &[String] - the service paths or names to keep
+///
+/// ### Returns
+/// - Vec<(String, String, bool)> - the matching services
+pub fn filter_service_paths(
+    service_paths: Vec<(String, String, bool)>,
+    only: &[String],
+) -> Vec<(String, String, bool)> {
+    service_paths
+        .into_iter()
+        .filter(|service| {
+            only.is_empty() || only.iter().any(|only| matches_service(only, &service.1))
+        })
+        .collect()
+}
+
+/// ## split_service_list(services: &str) -> Vec<String>
+/// This function splits the comma separated value of an `--only` option
+pub fn split_service_list(services: &str) -> Vec<String> {
+    services
+        .split(',')
+        .filter(|service| !service.is_empty())
+        .map(|service| service.to_string())
+        .collect()
+}
+
+/// ## matches_service(filter: &str, service_path: &str) -> bool
+/// This function checks if an `--only` filter matches a service by
+/// its path or by the last component of its path
+fn matches_service(filter: &str, service_path: &str) -> bool {
+    let filter = normalize_path(filter);
+    let service_path = normalize_path(service_path);
+    service_path == filter || service_path.rsplit('/').next() == Some(filter.as_str())
+}
+
+/// ## normalize_path(path: &str) -> String
+/// This function normalizes a service path so `./backend/` and `backend`
+/// refer to the same service
+pub fn normalize_path(path: &str) -> String {
+    path.trim()
+        .trim_start_matches("./")
+        .trim_end_matches('/')
+        .to_string()
+}
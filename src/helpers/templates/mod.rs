@@ -0,0 +1,62 @@
+use crate::helpers::config::project::get_project;
+use crate::CONFIG;
+use std::collections::HashMap;
+
+/// ## get_project_variables() -> HashMap<String, String>
+/// This function collects the variables available to every template from
+/// the application config and the project definition file
+///
+/// ### Returns
+/// - HashMap<String, String> - The variables by name, like `project_name`
+pub fn get_project_variables() -> HashMap<String, String> {
+    let mut variables = HashMap::from([
+        ("project_name".to_string(), CONFIG.project_name.clone()),
+        (
+            "project_version".to_string(),
+            CONFIG.project_version.clone(),
+        ),
+        ("project_path".to_string(), CONFIG.project_path.clone()),
+        (
+            "docker_compose".to_string(),
+            CONFIG.docker_compose.base().to_string(),
+        ),
+    ]);
+
+    if let Some(project) = get_project() {
+        variables.insert("project_domain".to_string(), project.domain);
+        variables.insert("project_description".to_string(), project.description);
+    }
+
+    variables
+}
+
+/// ## render_template(template: &str, variables: &HashMap<String, String>) -> String
+/// This function replaces every known `{{ name }}` placeholder with its
+/// value and leaves unknown placeholders untouched
+///
+/// ### Arguments
+/// - template: &str - The text of the template
+/// - variables: &HashMap<String, String> - The values of the placeholders
+///
+/// ### Returns
+/// - String - The rendered text
+pub fn render_template(template: &str, variables: &HashMap<String, String>) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + end].trim();
+        rendered.push_str(&rest[..start]);
+        match variables.get(name) {
+            Some(value) => rendered.push_str(value),
+            None => rendered.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &rest[start + end + 2..];
+    }
+
+    rendered.push_str(rest);
+    rendered
+}
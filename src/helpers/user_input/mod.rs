@@ -134,24 +134,22 @@ pub fn normalize_prompt(prompt: &str) -> String {
 ///
 /// ### Returns
 /// - bool - true if the user answers yes, false if the user answers no
+#[allow(dead_code)]
 pub fn ask_user_yes_or_no(prompt: &str) -> bool {
-    // create a new string to store the user input
-    let mut input = String::new();
-
     // loop until the user answers yes or no
-    loop {
+    let input = loop {
         // get the user input
-        input = get_user_input(prompt, Some(true));
+        let input = get_user_input(prompt, Some(true));
 
         // check if the user input is yes or no
         if input == "yes" || input == "no" {
             // break out of the loop
-            break;
+            break input;
         } else {
             // print an error message
             println!("Please enter yes or no");
         }
-    }
+    };
 
     // check if the user input is yes
     if input == "yes" {
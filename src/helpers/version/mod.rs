@@ -0,0 +1,175 @@
+use crate::helpers::git::describe::Describe;
+use crate::helpers::git::Git;
+use crate::models::app_config::VersionMode;
+use crate::CONFIG;
+use semver::{BuildMetadata, Prerelease, Version};
+
+/// The configured and the effective project version
+#[derive(Debug, PartialEq)]
+pub struct VersionInfo {
+    pub configured: Version,        // the version in config.yaml
+    pub version: Version,           // the effective version, computed from git in git mode
+    pub describe: Option<Describe>, // HEAD of the project if it is a git repository
+}
+
+impl VersionInfo {
+    /// This function returns the version as environment variables for
+    /// tasks and compose files.
+    pub fn to_env(&self) -> Vec<(String, String)> {
+        let mut env = vec![
+            (
+                "TOOLS_PROJECT_NAME".to_string(),
+                CONFIG.project_name.clone(),
+            ),
+            (
+                "TOOLS_PROJECT_VERSION".to_string(),
+                self.version.to_string(),
+            ),
+            (
+                "TOOLS_PROJECT_VERSION_TAG".to_string(),
+                docker_tag(&self.version),
+            ),
+        ];
+
+        if let Some(describe) = &self.describe {
+            env.push(("TOOLS_GIT_COMMIT".to_string(), describe.hash.clone()));
+            env.push(("TOOLS_GIT_DIRTY".to_string(), describe.dirty.to_string()));
+        }
+
+        env
+    }
+}
+
+/// ## get_version_info() -> VersionInfo
+/// This function returns the configured project version and the effective
+/// version, which in the git version mode carries prerelease and build
+/// metadata computed from the commits since the last release tag
+///
+/// ### Returns
+/// - VersionInfo - The configured version is used if git can't describe the project
+pub fn get_version_info() -> VersionInfo {
+    let configured = CONFIG.get_version();
+    let project = Git::project();
+
+    // without a reachable tag every commit counts as unreleased
+    let describe = project.describe().and_then(|mut describe| {
+        if describe.tag.is_none() {
+            describe.distance = project.commit_count("HEAD")?;
+        }
+        Ok(describe)
+    });
+
+    let version = match (CONFIG.get_version_mode(), &describe) {
+        (VersionMode::Static, _) => Ok(configured.clone()),
+        (VersionMode::Git, Ok(describe)) => {
+            compute_version(&configured, describe).map_err(|error| error.to_string())
+        }
+        (VersionMode::Git, Err(error)) => Err(error.to_string()),
+    };
+
+    let version = version.unwrap_or_else(|error| {
+        eprintln!(
+            "Warning: couldn't compute the version from git, using {}: {}",
+            configured, error
+        );
+        configured.clone()
+    });
+
+    VersionInfo {
+        configured,
+        version,
+        describe: describe.ok(),
+    }
+}
+
+/// ## compute_version(configured: &Version, describe: &Describe) -> Result<Version, semver::Error>
+/// This function computes the effective version of a commit. The release
+/// commit tagged `v<configured>` has the configured version, commits after
+/// it lead up to the next patch, `1.3.1-dev.2+g3f2a1b4`, and commits of an
+/// unreleased version add to its prerelease, `1.4.0-dev.12+g3f2a1b4`. A
+/// dirty working tree is marked in the build metadata, `+g3f2a1b4-dirty`.
+///
+/// ### Arguments
+/// - configured: &Version - The version in config.yaml
+/// - describe: &Describe - HEAD relative to the last release tag
+///
+/// ### Returns
+/// - Result<Version, semver::Error> - The effective version
+pub fn compute_version(
+    configured: &Version,
+    describe: &Describe,
+) -> Result<Version, semver::Error> {
+    let released = describe.tag.as_deref() == Some(format!("v{}", configured).as_str());
+
+    if released && describe.distance == 0 && !describe.dirty {
+        return Ok(configured.clone());
+    }
+
+    let mut version = configured.clone();
+    if released && describe.distance > 0 {
+        version.patch += 1;
+        version.pre = Prerelease::new(&format!("dev.{}", describe.distance))?;
+    } else if !released && configured.pre.is_empty() {
+        version.pre = Prerelease::new(&format!("dev.{}", describe.distance))?;
+    } else if !released {
+        version.pre = Prerelease::new(&format!("{}.dev.{}", configured.pre, describe.distance))?;
+    }
+
+    let build = if describe.dirty {
+        format!("g{}-dirty", describe.hash)
+    } else {
+        format!("g{}", describe.hash)
+    };
+    version.build = BuildMetadata::new(&build)?;
+
+    Ok(version)
+}
+
+/// ## docker_tag(version: &Version) -> String
+/// This function returns a version as an image tag, tags can't contain the
+/// `+` of build metadata
+///
+/// ### Arguments
+/// - version: &Version - The version
+///
+/// ### Returns
+/// - String - The version with `+` replaced by `-`
+pub fn docker_tag(version: &Version) -> String {
+    version.to_string().replace('+', "-")
+}
+
+#[test]
+fn test_compute_version() {
+    let describe = |tag: Option<&str>, distance: u64, dirty: bool| Describe {
+        tag: tag.map(String::from),
+        distance,
+        hash: "3f2a1b4".to_string(),
+        dirty,
+    };
+    let version = |version: &str| Version::parse(version).unwrap();
+
+    assert_eq!(
+        compute_version(&version("1.3.0"), &describe(Some("v1.3.0"), 0, false)).unwrap(),
+        version("1.3.0")
+    );
+    assert_eq!(
+        compute_version(&version("1.3.0"), &describe(Some("v1.3.0"), 2, false)).unwrap(),
+        version("1.3.1-dev.2+g3f2a1b4")
+    );
+    assert_eq!(
+        compute_version(&version("1.4.0"), &describe(Some("v1.3.0"), 12, true)).unwrap(),
+        version("1.4.0-dev.12+g3f2a1b4-dirty")
+    );
+    assert_eq!(
+        compute_version(&version("2.0.0-rc.1"), &describe(None, 40, false)).unwrap(),
+        version("2.0.0-rc.1.dev.40+g3f2a1b4")
+    );
+    assert_eq!(
+        compute_version(&version("1.3.0"), &describe(Some("v1.3.0"), 0, true)).unwrap(),
+        version("1.3.0+g3f2a1b4-dirty")
+    );
+    assert_eq!(
+        docker_tag(&version("1.3.1-dev.2+g3f2a1b4")),
+        "1.3.1-dev.2-g3f2a1b4"
+    );
+}
@@ -0,0 +1,620 @@
+use serde_yaml::{Mapping, Value};
+
+/// The indentation of a YAML file, new nodes are written the same way
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Style {
+    unit: usize,            // the spaces of one level of indentation
+    indent_sequences: bool, // whether `- ` items are indented below their key
+}
+
+/// A `key: value` entry of a block mapping in the text
+#[derive(Debug, PartialEq)]
+struct Entry {
+    key: String,
+    line: usize,        // the line of the key
+    end: usize,         // the line after the value, trailing blank lines and comments excluded
+    inline_value: bool, // whether the value is on the line of the key
+}
+
+/// A replacement of the lines `start..end`
+#[derive(Debug)]
+struct Edit {
+    start: usize,
+    end: usize,
+    lines: Vec<String>,
+}
+
+/// ## patch_yaml(text: &str, old: &Value, new: &Value) -> String
+/// This function changes a YAML document from `old` to `new` by only
+/// rewriting the entries of its block mappings that changed, so comments,
+/// anchors, the order of keys and the formatting of everything else are
+/// kept. Documents it can't patch are serialized again
+///
+/// ### Arguments
+/// - text: &str - The YAML document
+/// - old: &Value - The value of the document, how the text parses
+/// - new: &Value - The value the document should have
+///
+/// ### Returns
+/// - String - The patched document
+pub fn patch_yaml(text: &str, old: &Value, new: &Value) -> String {
+    let serialized = || serde_yaml::to_string(new).unwrap_or_default();
+
+    let (Value::Mapping(old), Value::Mapping(new)) = (old, new) else {
+        return serialized();
+    };
+
+    let lines = text.lines().map(String::from).collect::<Vec<String>>();
+    let start = lines
+        .iter()
+        .position(|line| line.trim() == "---")
+        .map(|line| line + 1)
+        .unwrap_or(0);
+    let end = lines
+        .iter()
+        .skip(start)
+        .position(|line| line.trim() == "---" || line.trim() == "...")
+        .map(|line| line + start)
+        .unwrap_or(lines.len());
+    let style = detect_style(&lines);
+
+    let Some(edits) = patch_mapping(&lines, start, end, 0, old, new, style) else {
+        return serialized();
+    };
+
+    // apply the edits from the bottom so the line numbers stay valid, new
+    // keys at the same line in reverse so they keep the order of the value
+    let mut edits = edits
+        .into_iter()
+        .enumerate()
+        .collect::<Vec<(usize, Edit)>>();
+    edits.sort_by(|(a_index, a), (b_index, b)| {
+        b.start
+            .cmp(&a.start)
+            .then(b.end.cmp(&a.end))
+            .then(b_index.cmp(a_index))
+    });
+    let mut lines = lines;
+    for (_, edit) in edits {
+        lines.splice(edit.start..edit.end, edit.lines);
+    }
+
+    let mut patched = lines.join("\n");
+    if text.ends_with('\n') || text.is_empty() {
+        patched.push('\n');
+    }
+
+    // never write something that doesn't parse to the new value
+    match serde_yaml::from_str::<Value>(&patched) {
+        Ok(value) if value == Value::Mapping(new.clone()) => patched,
+        _ => serialized(),
+    }
+}
+
+/// ## locate(text: &str, path: &[&str]) -> Option<(usize, usize)>
+/// This function finds the line of a node of a YAML document in block
+/// style, a mapping key or a scalar sequence item, e.g.
+/// `["services", "api", "depends_on", "db"]`. Nodes it can't find, like
+/// the items of a flow sequence, are located at their closest ancestor
+///
+/// ### Arguments
+/// - text: &str - The YAML document
+/// - path: &[&str] - The keys and items from the root to the node
+///
+/// ### Returns
+/// - Option<(usize, usize)> - The 1-based line of the node and how many keys and items of the path were found, None if not even its first key is in the text
+pub fn locate(text: &str, path: &[&str]) -> Option<(usize, usize)> {
+    // the indentation, name and whether it is a sequence item of the open nodes
+    let mut stack: Vec<(usize, String, bool)> = Vec::new();
+    let mut best: Option<(usize, usize)> = None;
+
+    for (number, line) in text.lines().enumerate() {
+        if is_blank(line) || line.starts_with("---") || line.starts_with("...") {
+            continue;
+        }
+        let indent = indentation(line);
+        let content = line.trim();
+
+        match content.strip_prefix("- ") {
+            Some(item) => {
+                // items may be at the indentation of their key
+                while stack.last().is_some_and(|(open, _, is_item)| {
+                    *open > indent || (*open == indent && *is_item)
+                }) {
+                    stack.pop();
+                }
+                let item = item.trim_start();
+                match split_key(item) {
+                    Some((key, _)) => stack.push((indent + 2, key, false)),
+                    None => {
+                        let value = match trailing_comment(item) {
+                            Some(comment) => &item[..item.len() - comment.len()],
+                            None => item,
+                        };
+                        let value = value.trim().trim_matches(['"', '\'']);
+                        stack.push((indent, value.to_string(), true));
+                    }
+                }
+            }
+            None => {
+                let Some((key, _)) = split_key(content) else {
+                    continue;
+                };
+                while stack.last().is_some_and(|(open, _, _)| *open >= indent) {
+                    stack.pop();
+                }
+                stack.push((indent, key, false));
+            }
+        }
+
+        let depth = stack.len();
+        let matches = depth <= path.len()
+            && stack
+                .iter()
+                .zip(path)
+                .all(|((_, name, _), part)| name == part);
+        if matches && best.is_none_or(|(best_depth, _)| depth > best_depth) {
+            best = Some((depth, number + 1));
+        }
+    }
+
+    best.map(|(depth, line)| (line, depth))
+}
+
+// ## patch_mapping(...) -> Option<Vec<Edit>>
+// This function returns the edits that change the block mapping in the
+// lines `start..end` with entries at `indent` from `old` to `new`, None if
+// the text doesn't match `old`
+fn patch_mapping(
+    lines: &[String],
+    start: usize,
+    end: usize,
+    indent: usize,
+    old: &Mapping,
+    new: &Mapping,
+    style: Style,
+) -> Option<Vec<Edit>> {
+    let entries = mapping_entries(lines, start, end, indent)?;
+    let find = |key: &Value| {
+        let key = key_to_string(key)?;
+        entries.iter().find(|entry| entry.key == key)
+    };
+
+    let mut edits = Vec::new();
+
+    for (key, old_value) in old {
+        if !new.contains_key(key) {
+            let entry = find(key)?;
+            edits.push(Edit {
+                start: entry.line,
+                end: entry.end,
+                lines: Vec::new(),
+            });
+        } else if new.get(key) != Some(old_value) {
+            let entry = find(key)?;
+            let new_value = &new[key];
+
+            // recurse into block mappings, everything else is rewritten
+            let nested = match (old_value, new_value) {
+                (Value::Mapping(old_value), Value::Mapping(new_value)) if !entry.inline_value => {
+                    child_indent(lines, entry).and_then(|child_indent| {
+                        patch_mapping(
+                            lines,
+                            entry.line + 1,
+                            entry.end,
+                            child_indent,
+                            old_value,
+                            new_value,
+                            style,
+                        )
+                    })
+                }
+                _ => None,
+            };
+
+            match nested {
+                Some(mut nested) => edits.append(&mut nested),
+                None => {
+                    let mut rendered = render_entry(key, new_value, indent, style);
+                    // keep the comment at the end of a single line value
+                    if let (1, 1, Some(comment)) = (
+                        entry.end - entry.line,
+                        rendered.len(),
+                        trailing_comment(&lines[entry.line]),
+                    ) {
+                        rendered[0] = format!("{} {}", rendered[0], comment);
+                    }
+                    edits.push(Edit {
+                        start: entry.line,
+                        end: entry.end,
+                        lines: rendered,
+                    })
+                }
+            }
+        }
+    }
+
+    // new keys are added after the key before them, or before the first key,
+    // or at the end of an empty mapping
+    let mut position = entries.first().map_or(end, |entry| entry.line);
+    for (key, value) in new {
+        if old.contains_key(key) {
+            if let Some(entry) = find(key) {
+                position = entry.end;
+            }
+            continue;
+        }
+        edits.push(Edit {
+            start: position,
+            end: position,
+            lines: render_entry(key, value, indent, style),
+        });
+    }
+
+    Some(edits)
+}
+
+// ## mapping_entries(lines, start, end, indent) -> Option<Vec<Entry>>
+// This function returns the entries of the block mapping in the lines
+// `start..end`, None if the lines are not a block mapping at `indent`
+fn mapping_entries(
+    lines: &[String],
+    start: usize,
+    end: usize,
+    indent: usize,
+) -> Option<Vec<Entry>> {
+    let mut entries: Vec<Entry> = Vec::new();
+
+    for (index, line) in lines.iter().enumerate().take(end).skip(start) {
+        if is_blank(line) {
+            continue;
+        }
+        let line_indent = indentation(line);
+        if line_indent < indent {
+            return None;
+        }
+        // sequences that are not indented below their key belong to it
+        let content = &line[indent..];
+        if line_indent > indent || content.starts_with("- ") || content.trim_end() == "-" {
+            continue;
+        }
+
+        let (key, value) = split_key(content)?;
+        entries.push(Entry {
+            key,
+            line: index,
+            end,
+            inline_value: !is_blank(value) && !value.trim_start().starts_with('&'),
+        });
+    }
+
+    // every entry ends where the next one starts, without the blank lines
+    // and comments in front of the next entry
+    for index in 0..entries.len() {
+        let mut entry_end = entries.get(index + 1).map(|next| next.line).unwrap_or(end);
+        while entry_end > entries[index].line + 1 {
+            let line = &lines[entry_end - 1];
+            if line.trim().is_empty() || (is_blank(line) && indentation(line) <= indent) {
+                entry_end -= 1;
+            } else {
+                break;
+            }
+        }
+        entries[index].end = entry_end;
+    }
+
+    Some(entries)
+}
+
+// ## child_indent(lines: &[String], entry: &Entry) -> Option<usize>
+// This function returns the indentation of the block mapping below a key,
+// None if the value is a sequence
+fn child_indent(lines: &[String], entry: &Entry) -> Option<usize> {
+    let line = lines[entry.line + 1..entry.end]
+        .iter()
+        .find(|line| !is_blank(line))?;
+    let trimmed = line.trim_start();
+    (!trimmed.starts_with("- ") && trimmed != "-").then_some(indentation(line))
+}
+
+// ## split_key(line: &str) -> Option<(String, &str)>
+// This function splits a `key: value` line into the unquoted key and the
+// rest of the line
+fn split_key(line: &str) -> Option<(String, &str)> {
+    if line.starts_with("- ") || line == "-" {
+        return None;
+    }
+
+    let (key, rest) = match line.chars().next()? {
+        quote @ ('"' | '\'') => {
+            let close = line[1..].find(quote)? + 1;
+            (line[1..close].to_string(), &line[close + 1..])
+        }
+        _ => {
+            let colon = line
+                .match_indices(':')
+                .map(|(index, _)| index)
+                .find(|index| line[index + 1..].is_empty() || line[index + 1..].starts_with(' '))?;
+            (line[..colon].trim_end().to_string(), &line[colon..])
+        }
+    };
+
+    let value = rest.trim_start().strip_prefix(':')?;
+    Some((key, value))
+}
+
+// ## render_entry(key: &Value, value: &Value, indent: usize, style: Style) -> Vec<String>
+// This function writes a `key: value` entry in the block style of the file
+fn render_entry(key: &Value, value: &Value, indent: usize, style: Style) -> Vec<String> {
+    let prefix = format!("{}{}:", " ".repeat(indent), render_scalar(key));
+
+    match value {
+        Value::Mapping(mapping) if !mapping.is_empty() => {
+            let mut lines = vec![prefix];
+            for (key, value) in mapping {
+                lines.append(&mut render_entry(key, value, indent + style.unit, style));
+            }
+            lines
+        }
+        Value::Sequence(sequence) if !sequence.is_empty() => {
+            let item_indent = if style.indent_sequences {
+                indent + style.unit
+            } else {
+                indent
+            };
+            let mut lines = vec![prefix];
+            lines.append(&mut render_sequence(sequence, item_indent, style));
+            lines
+        }
+        // `name:` without a value, e.g. a volume with the default options
+        Value::Null => vec![prefix],
+        value => vec![format!("{} {}", prefix, render_scalar(value))],
+    }
+}
+
+// ## render_sequence(sequence: &[Value], indent: usize, style: Style) -> Vec<String>
+// This function writes the `- item` lines of a sequence
+fn render_sequence(sequence: &[Value], indent: usize, style: Style) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for item in sequence {
+        let mut item_lines = match item {
+            Value::Mapping(mapping) if !mapping.is_empty() => mapping
+                .iter()
+                .flat_map(|(key, value)| render_entry(key, value, indent + 2, style))
+                .collect(),
+            Value::Sequence(sequence) if !sequence.is_empty() => {
+                render_sequence(sequence, indent + 2, style)
+            }
+            item => vec![format!("{}{}", " ".repeat(indent + 2), render_scalar(item))],
+        };
+
+        // the first line of the item carries the dash
+        item_lines[0].replace_range(..indent + 2, &format!("{}- ", " ".repeat(indent)));
+        lines.append(&mut item_lines);
+    }
+
+    lines
+}
+
+// ## render_scalar(value: &Value) -> String
+// This function writes a value on a single line, multi-line strings are
+// double quoted and empty collections are written in the flow style
+fn render_scalar(value: &Value) -> String {
+    match value {
+        Value::String(string) if string.contains('\n') => {
+            serde_json::to_string(string).unwrap_or_default()
+        }
+        Value::Mapping(mapping) if mapping.is_empty() => "{}".to_string(),
+        Value::Sequence(sequence) if sequence.is_empty() => "[]".to_string(),
+        value => serde_yaml::to_string(value)
+            .unwrap_or_default()
+            .trim_end()
+            .to_string(),
+    }
+}
+
+// ## detect_style(lines: &[String]) -> Style
+// This function detects the indentation unit of a file, and whether it
+// indents sequences below their key
+fn detect_style(lines: &[String]) -> Style {
+    let mut unit = None;
+    let mut indent_sequences = None;
+
+    let content = lines
+        .iter()
+        .filter(|line| !is_blank(line))
+        .collect::<Vec<&String>>();
+    for pair in content.windows(2) {
+        let (parent, child) = (pair[0], pair[1]);
+        let parent_indent = indentation(parent);
+        let child_indent = indentation(child);
+        if !parent.trim_end().ends_with(':') {
+            continue;
+        }
+
+        if child.trim_start().starts_with("- ") {
+            indent_sequences.get_or_insert(child_indent > parent_indent);
+        } else if child_indent > parent_indent {
+            unit.get_or_insert(child_indent - parent_indent);
+        }
+    }
+
+    Style {
+        unit: unit.unwrap_or(2),
+        indent_sequences: indent_sequences.unwrap_or(true),
+    }
+}
+
+// ## trailing_comment(line: &str) -> Option<&str>
+// This function returns the `# comment` at the end of a line, outside of
+// quoted strings
+fn trailing_comment(line: &str) -> Option<&str> {
+    let mut quote = None;
+    let mut previous = ' ';
+
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), c) if c == open => quote = None,
+            (None, '#') if previous == ' ' && !line[..index].trim().is_empty() => {
+                return Some(&line[index..])
+            }
+            _ => {}
+        }
+        previous = c;
+    }
+
+    None
+}
+
+// ## key_to_string(key: &Value) -> Option<String>
+// This function returns a mapping key as it is written in the text
+fn key_to_string(key: &Value) -> Option<String> {
+    match key {
+        Value::String(key) => Some(key.clone()),
+        Value::Number(key) => Some(key.to_string()),
+        Value::Bool(key) => Some(key.to_string()),
+        _ => None,
+    }
+}
+
+// ## is_blank(line: &str) -> bool
+// This function checks if a line is empty or only a comment
+fn is_blank(line: &str) -> bool {
+    let line = line.trim();
+    line.is_empty() || line.starts_with('#')
+}
+
+// ## indentation(line: &str) -> usize
+// This function returns the number of leading spaces of a line
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+#[test]
+fn test_yaml_edit_locate() {
+    let text = "---\n# the shop\nservices:\n  api:\n    ports:\n      - \"8080:80\"  # web\n    depends_on:\n    - db\n    networks: [backend]\n  db:\n    image: postgres\n";
+
+    assert_eq!(
+        locate(text, &["services", "api", "ports", "8080:80"]),
+        Some((6, 4))
+    );
+    assert_eq!(
+        locate(text, &["services", "api", "depends_on", "db"]),
+        Some((8, 4))
+    );
+    assert_eq!(
+        locate(text, &["services", "api", "networks", "backend"]),
+        Some((9, 3))
+    );
+    assert_eq!(locate(text, &["services", "db", "image"]), Some((11, 3)));
+    assert_eq!(locate(text, &["volumes", "data"]), None);
+}
+
+#[test]
+fn test_yaml_edit_patch_yaml() {
+    let text = r#"---
+# the services of the shop
+x-logging: &logging
+  driver: json-file
+
+services:
+    # the public api
+    api:
+        image: shop/api:1.0.0 # bumped by tools
+        logging: *logging
+        ports:
+            - "8080:8080"
+    db:
+        image: postgres:16
+        restart: always
+"#;
+
+    let old = serde_yaml::from_str::<Value>(text).unwrap();
+    let mut new = old.clone();
+    new["services"]["api"]["image"] = Value::from("shop/api:1.1.0");
+    new["services"]["db"]
+        .as_mapping_mut()
+        .unwrap()
+        .remove("restart");
+    new["services"].as_mapping_mut().unwrap().insert(
+        Value::from("cache"),
+        serde_yaml::from_str("{image: redis:7, ports: [6379]}").unwrap(),
+    );
+
+    assert_eq!(
+        patch_yaml(text, &old, &new),
+        r#"---
+# the services of the shop
+x-logging: &logging
+  driver: json-file
+
+services:
+    # the public api
+    api:
+        image: shop/api:1.1.0 # bumped by tools
+        logging: *logging
+        ports:
+            - "8080:8080"
+    db:
+        image: postgres:16
+    cache:
+        image: redis:7
+        ports:
+            - 6379
+"#
+    );
+
+    // an unchanged document is written exactly as it was
+    assert_eq!(patch_yaml(text, &old, &old), text);
+
+    // new keys of an empty document keep their order
+    let new: Value =
+        serde_yaml::from_str("services:\n  api:\n    image: api\nnetworks:\n  shop:\n").unwrap();
+    assert_eq!(
+        patch_yaml("---\n", &Value::Mapping(Mapping::new()), &new),
+        "---\nservices:\n  api:\n    image: api\nnetworks:\n  shop:\n"
+    );
+}
+
+#[test]
+fn test_yaml_edit_patch_compose_file() {
+    use crate::helpers::diff::{diff_lines, DiffLine};
+    use crate::models::docker_compose::{service::Service, DockerCompose};
+
+    let text =
+        std::fs::read_to_string("src/test/models/docker_compose/docker-compose.full.yaml").unwrap();
+    let compose = DockerCompose::from_yaml(&text).unwrap();
+
+    let mut changed = compose.clone();
+    changed.services["api"].restart = None;
+    changed.services["db"].image = Some("postgres:17".to_string());
+    changed.services.insert(
+        "cache".to_string(),
+        Service {
+            image: Some("redis:7".to_string()),
+            ..Default::default()
+        },
+    );
+
+    let old = serde_yaml::to_value(&compose).unwrap();
+    let new = serde_yaml::to_value(&changed).unwrap();
+    let patched = patch_yaml(&text, &old, &new);
+
+    assert_eq!(DockerCompose::from_yaml(&patched).unwrap(), changed);
+    let changes = diff_lines(&text, &patched)
+        .into_iter()
+        .filter(|line| !matches!(line, DiffLine::Unchanged(_)))
+        .collect::<Vec<DiffLine>>();
+    assert_eq!(
+        changes,
+        vec![
+            DiffLine::Removed("    restart: unless-stopped"),
+            DiffLine::Removed("    image: postgres:16"),
+            DiffLine::Added("    image: postgres:17"),
+            DiffLine::Added("  cache:"),
+            DiffLine::Added("    image: redis:7"),
+        ]
+    );
+}
@@ -11,19 +11,18 @@ pub static CONFIG: Lazy<AppConfigFile> = Lazy::new(config::app_config::get_confi
 /// ## main()
 /// This function is the entry point for the program
 fn main() {
-
-    // // switch statement based on the first argument and pass the rest of the arguments to the controller
-    // match std::env::args().nth(1) {
-    //     Some(command) => {
-    //         let args: Vec<String> = std::env::args().collect();
-    //         if args.get(1).unwrap() == "help" {
-    //             helpers::help::router(&args[2..]);
-    //             return;
-    //         }
-    //         controller::run(command, &args[2..]);
-    //     }
-    //     None => {
-    //         helpers::help::main_help::print_help();
-    //     }
-    // }
+    // switch statement based on the first argument and pass the rest of the arguments to the controller
+    match std::env::args().nth(1) {
+        Some(command) => {
+            let args: Vec<String> = std::env::args().collect();
+            if args.get(1).unwrap() == "help" {
+                helpers::help::router(&args[2..]);
+                return;
+            }
+            controller::run(command, &args[2..]);
+        }
+        None => {
+            helpers::help::main_help::print_help();
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use indexmap::IndexMap;
 use semver::{BuildMetadata, Prerelease, Version};
 use serde::{Deserialize, Serialize};
 use std::error::Error;
@@ -5,10 +6,105 @@ use std::error::Error;
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct AppConfigFile {
     pub project_path: String,
-    pub docker_compose: String,
+    pub docker_compose: ComposeFiles,
     pub project_name: String,
     pub project_version: String,
     pub github_api_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_mode: Option<VersionMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<HooksConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container_engine: Option<ContainerEngine>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environments: Option<IndexMap<String, Environment>>,
+}
+
+/// The compose files of the project, one file or a base file followed by
+/// the files that override it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ComposeFiles {
+    File(String),
+    Files(Vec<String>),
+}
+
+impl ComposeFiles {
+    /// This function returns the compose files in the order they are merged.
+    pub fn files(&self) -> Vec<String> {
+        match self {
+            ComposeFiles::File(file) => vec![file.clone()],
+            ComposeFiles::Files(files) => files.clone(),
+        }
+    }
+
+    /// This function returns the base compose file, the one the tools edit.
+    pub fn base(&self) -> &str {
+        match self {
+            ComposeFiles::File(file) => file,
+            ComposeFiles::Files(files) => files.first().map_or("", |file| file.as_str()),
+        }
+    }
+}
+
+impl std::fmt::Display for ComposeFiles {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.files().join(", "))
+    }
+}
+
+/// A named environment like dev, test or ci, selected with `--env <name>`
+/// or `TOOLS_ENV`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Environment {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compose_files: Option<Vec<String>>, // used instead of docker_compose, merged in order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env_files: Option<Vec<String>>, // read instead of .env, later files win
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profiles: Option<Vec<String>>, // the compose profiles enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variables: Option<IndexMap<String, String>>, // win over the env files and the shell
+}
+
+/// The git hooks installed in the project and every service by
+/// `tools hooks install`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HooksConfig {
+    pub commit_msg: Option<bool>, // check that commit messages are conventional commits, default true
+    pub secret_scan: Option<bool>, // refuse commits that look like they add secrets, default true
+    pub pre_commit: Option<Vec<String>>, // commands run in the repository before every commit
+}
+
+/// How the effective project version is determined
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionMode {
+    /// the project version exactly as it is configured
+    #[default]
+    Static,
+    /// the configured version with prerelease and build metadata from git,
+    /// e.g. 1.4.0-dev.12+g3f2a1b4 twelve commits after the last release
+    Git,
+}
+
+/// The container engine and compose implementation that runs the project
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ContainerEngine {
+    /// the first engine found, in the order of the variants below
+    #[default]
+    Auto,
+    /// docker with the compose v2 plugin, `docker compose`
+    Docker,
+    /// docker with the legacy standalone compose v1, `docker-compose`
+    DockerCompose,
+    /// podman with its compose wrapper, `podman compose`
+    Podman,
+    /// podman with the standalone podman-compose, `podman-compose`
+    PodmanCompose,
+    /// containerd with nerdctl, `nerdctl compose`
+    Nerdctl,
 }
 
 impl AppConfigFile {
@@ -51,18 +147,22 @@ impl AppConfigFile {
         self.update_version(&version);
     }
 
-    /// This function updates the prerelease version of the project version.
-    pub fn update_prerelease_version(&mut self, prerelease: &str) {
+    /// This function updates the prerelease version of the project version,
+    /// an empty string removes it.
+    pub fn update_prerelease_version(&mut self, prerelease: &str) -> Result<(), semver::Error> {
         let mut version = self.get_version();
-        version.pre = Prerelease::new(prerelease).unwrap();
+        version.pre = Prerelease::new(prerelease)?;
         self.update_version(&version);
+        Ok(())
     }
 
-    /// This function updates the build metadata of the project version.
-    pub fn update_build_version(&mut self, build: &str) {
+    /// This function updates the build metadata of the project version, an
+    /// empty string removes it.
+    pub fn update_build_version(&mut self, build: &str) -> Result<(), semver::Error> {
         let mut version = self.get_version();
-        version.build = BuildMetadata::new(build).unwrap();
+        version.build = BuildMetadata::new(build)?;
         self.update_version(&version);
+        Ok(())
     }
 
     /// This function returns the project version as a semver::Version
@@ -70,13 +170,43 @@ impl AppConfigFile {
     pub fn get_version(&self) -> Version {
         Version::parse(&self.project_version).unwrap()
     }
+
+    /// This function returns the configured git hooks, or the default hooks.
+    pub fn get_hooks(&self) -> HooksConfig {
+        self.hooks.clone().unwrap_or_default()
+    }
+
+    /// This function returns how the effective project version is determined.
+    pub fn get_version_mode(&self) -> VersionMode {
+        self.version_mode.unwrap_or_default()
+    }
+
+    /// This function returns the configured container engine, or auto
+    /// detection.
+    pub fn get_container_engine(&self) -> ContainerEngine {
+        self.container_engine.unwrap_or_default()
+    }
+
+    /// This function returns an environment by name, None if it isn't
+    /// configured.
+    pub fn get_environment(&self, name: &str) -> Option<&Environment> {
+        self.environments.as_ref()?.get(name)
+    }
+
+    /// This function returns the names of the configured environments.
+    pub fn get_environment_names(&self) -> Vec<String> {
+        self.environments
+            .iter()
+            .flat_map(|environments| environments.keys().cloned())
+            .collect()
+    }
 }
 
 #[test]
 fn test_app_config() {
     let config = AppConfigFile {
         project_path: ".".to_string(),
-        docker_compose: "docker-compose.yaml".to_string(),
+        docker_compose: ComposeFiles::File("docker-compose.yaml".to_string()),
         project_name: "project".to_string(),
         project_version: Version {
             major: 0,
@@ -87,6 +217,10 @@ fn test_app_config() {
         }
         .to_string(),
         github_api_token: None,
+        version_mode: None,
+        hooks: None,
+        container_engine: None,
+        environments: None,
     };
 
     let config_yaml = r#"---
@@ -105,7 +239,7 @@ github_api_token: null
 fn test_app_config_update_version() {
     let mut config = AppConfigFile {
         project_path: ".".to_string(),
-        docker_compose: "docker-compose.yaml".to_string(),
+        docker_compose: ComposeFiles::File("docker-compose.yaml".to_string()),
         project_name: "project".to_string(),
         project_version: Version {
             major: 0,
@@ -116,6 +250,10 @@ fn test_app_config_update_version() {
         }
         .to_string(),
         github_api_token: None,
+        version_mode: None,
+        hooks: None,
+        container_engine: None,
+        environments: None,
     };
 
     let config_yaml = r#"---
@@ -161,6 +299,99 @@ github_token: gh_token # if you want to use github api instead of the GitHub CLI
     let from_file = AppConfigFile::from_yaml(&config_from_file);
     match from_file {
         Ok(from_file) => assert_eq!(config, from_file),
-        Err(e) => assert!(false, "{}", e.to_string()),
+        Err(e) => panic!("{}", e.to_string()),
     }
 }
+
+#[test]
+fn test_app_config_version_mode() {
+    let config_yaml = r#"---
+project_path: .
+docker_compose: docker-compose.yaml
+project_name: project
+project_version: 1.4.0
+github_api_token: null
+version_mode: git
+"#;
+
+    let config = AppConfigFile::from_yaml(config_yaml).unwrap();
+    assert_eq!(config.get_version_mode(), VersionMode::Git);
+    assert_eq!(config_yaml, config.to_yaml().unwrap());
+}
+
+#[test]
+fn test_app_config_container_engine() {
+    let config_yaml = r#"---
+project_path: .
+docker_compose: docker-compose.yaml
+project_name: project
+project_version: 1.4.0
+github_api_token: null
+container_engine: podman-compose
+"#;
+
+    let config = AppConfigFile::from_yaml(config_yaml).unwrap();
+    assert_eq!(
+        config.get_container_engine(),
+        ContainerEngine::PodmanCompose
+    );
+    assert_eq!(config_yaml, config.to_yaml().unwrap());
+}
+
+#[test]
+fn test_app_config_compose_files() {
+    let config_yaml = r#"---
+project_path: .
+docker_compose:
+- docker-compose.yaml
+- docker-compose.override.yaml
+- docker-compose.local.yaml
+project_name: project
+project_version: 1.4.0
+github_api_token: null
+"#;
+
+    let config = AppConfigFile::from_yaml(config_yaml).unwrap();
+    assert_eq!(config.docker_compose.base(), "docker-compose.yaml");
+    assert_eq!(config.docker_compose.files().len(), 3);
+    assert_eq!(config_yaml, config.to_yaml().unwrap());
+}
+
+#[test]
+fn test_app_config_environments() {
+    let config_yaml = r#"---
+project_path: .
+docker_compose: docker-compose.yaml
+project_name: project
+project_version: 1.4.0
+github_api_token: null
+environments:
+  dev:
+    compose_files:
+    - docker-compose.yaml
+    - docker-compose.dev.yaml
+    profiles:
+    - debug
+  ci:
+    env_files:
+    - .env.ci
+    variables:
+      LOG_LEVEL: warn
+"#;
+
+    let config = AppConfigFile::from_yaml(config_yaml).unwrap();
+    assert_eq!(config.get_environment_names(), vec!["dev", "ci"]);
+    assert_eq!(
+        config.get_environment("ci"),
+        Some(&Environment {
+            env_files: Some(vec![".env.ci".to_string()]),
+            variables: Some(IndexMap::from([(
+                "LOG_LEVEL".to_string(),
+                "warn".to_string()
+            )])),
+            ..Default::default()
+        })
+    );
+    assert_eq!(config.get_environment("prod"), None);
+    assert_eq!(config_yaml, config.to_yaml().unwrap());
+}
@@ -1,62 +1,170 @@
+pub mod service;
+pub mod types;
+
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use serde_yaml;
-use std::collections::HashMap;
+use serde_yaml::Value;
+use service::Service;
 use std::error::Error;
+use types::{External, ListOrDict, StringOrList, StringOrNumber};
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
-pub struct Build {
-    context: String,
-    dockerfile: String,
+/// A compose file, see https://github.com/compose-spec/compose-spec/blob/main/spec.md
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DockerCompose {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>, // obsolete, kept for older files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include: Option<Vec<Include>>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub services: IndexMap<String, Service>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub networks: IndexMap<String, Option<Network>>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub volumes: IndexMap<String, Option<Volume>>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub configs: IndexMap<String, Option<Config>>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub secrets: IndexMap<String, Option<Secret>>,
+    #[serde(flatten)]
+    pub extensions: IndexMap<String, Value>, // x-* extensions and fields the model doesn't cover
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
-pub struct Service {
-    build: Option<Build>,
-    image: Option<String>,
-    ports: Option<Vec<String>>,
-    networks: Option<Vec<String>>,
-    configs: Option<Vec<String>>,
-    secrets: Option<Vec<String>>,
-    volumes: Option<Vec<String>>,
-    depends_on: Option<Vec<String>>,
+/// Another compose file included in the project, its path or the long syntax
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Include {
+    Path(String),
+    Long {
+        path: StringOrList,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        project_directory: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        env_file: Option<StringOrList>,
+    },
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
-pub struct Volume {
-    driver: Option<String>,
-    driver_opts: Option<HashMap<String, String>>,
+/// A top level network
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Network {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver_opts: Option<IndexMap<String, StringOrNumber>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_ipv4: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_ipv6: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external: Option<External>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub internal: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipam: Option<Ipam>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<ListOrDict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub extensions: IndexMap<String, Value>,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
-pub struct Network {
-    driver: Option<String>,
+/// The IP address management of a network
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Ipam {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<Vec<IpamConfig>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<IndexMap<String, String>>,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
-pub struct Config {
-    external: bool,
+/// A subnet of a network
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IpamConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subnet: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_range: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gateway: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aux_addresses: Option<IndexMap<String, String>>,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
-pub struct Secret {
-    external: bool,
+/// A top level volume
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Volume {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver_opts: Option<IndexMap<String, StringOrNumber>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external: Option<External>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<ListOrDict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub extensions: IndexMap<String, Value>,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
-pub struct DockerCompose {
-    services: HashMap<String, Service>,
-    volumes: HashMap<String, Volume>,
-    networks: HashMap<String, Network>,
-    configs: HashMap<String, Config>,
-    secrets: HashMap<String, Secret>,
+/// A top level config, read from a file, an environment variable or inline
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external: Option<External>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<ListOrDict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template_driver: Option<String>,
+    #[serde(flatten)]
+    pub extensions: IndexMap<String, Value>,
+}
+
+/// A top level secret, read from a file or an environment variable
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Secret {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external: Option<External>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<ListOrDict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver_opts: Option<IndexMap<String, StringOrNumber>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template_driver: Option<String>,
+    #[serde(flatten)]
+    pub extensions: IndexMap<String, Value>,
 }
 
 impl DockerCompose {
+    /// This function returns the compose file from yaml formated text string.
     pub fn from_yaml(yaml: &str) -> Result<Self, Box<dyn Error>> {
         let docker_compose: DockerCompose = serde_yaml::from_str(yaml)?;
         Ok(docker_compose)
     }
 
+    /// This function returns the compose file as a yaml formated text string.
     pub fn to_yaml(&self) -> Result<String, Box<dyn Error>> {
         let mut yaml = serde_yaml::to_string(self)?;
         yaml.insert_str(0, "---\n");
@@ -70,8 +178,8 @@ fn test_docker_compose() {
 services:
     web:
         build:
-        context: ./web
-        dockerfile: Dockerfile
+            context: ./web
+            dockerfile: Dockerfile
         image: web:latest
         ports:
         - 8080:8080
@@ -87,8 +195,8 @@ services:
         - db
     db:
         build:
-        context: ./db
-        dockerfile: Dockerfile
+            context: ./db
+            dockerfile: Dockerfile
         image: db:latest
         ports:
         - 5432:5432
@@ -142,4 +250,58 @@ volumes:
     let yaml_from_file = DockerCompose::from_yaml(&yaml).unwrap();
 
     assert_eq!(docker_compose, yaml_from_file);
+
+    // every key is where compose expects it, nothing ends up in the extensions
+    let web = &yaml_from_file.services["web"];
+    assert!(
+        matches!(&web.build, Some(service::Build::Config(build)) if build.context.as_deref() == Some("./web"))
+    );
+    assert!(web.extensions.is_empty());
+}
+
+#[test]
+fn test_docker_compose_specification() {
+    use service::{Build, DependencyCondition, DependsOn, Port, ServiceVolume};
+
+    let yaml =
+        std::fs::read_to_string("src/test/models/docker_compose/docker-compose.full.yaml").unwrap();
+    let docker_compose = DockerCompose::from_yaml(&yaml).unwrap();
+
+    // services keep the order of the file
+    assert_eq!(
+        docker_compose.services.keys().collect::<Vec<&String>>(),
+        vec!["api", "migrations", "db"]
+    );
+
+    let api = &docker_compose.services["api"];
+    assert!(
+        matches!(&api.build, Some(Build::Config(build)) if build.target.as_deref() == Some("runtime"))
+    );
+    assert_eq!(
+        api.environment.as_ref().unwrap().to_map()["WORKERS"],
+        Some("4".to_string())
+    );
+    assert!(matches!(api.ports.as_ref().unwrap()[2], Port::Long(_)));
+    assert!(matches!(
+        api.volumes.as_ref().unwrap()[1],
+        ServiceVolume::Long(_)
+    ));
+    match api.depends_on.as_ref().unwrap() {
+        DependsOn::Map(dependencies) => assert_eq!(
+            dependencies["db"].condition,
+            Some(DependencyCondition::Healthy)
+        ),
+        DependsOn::List(_) => panic!("expected the long syntax of depends_on"),
+    }
+    assert_eq!(
+        api.depends_on.as_ref().unwrap().services(),
+        vec!["db".to_string(), "migrations".to_string()]
+    );
+    assert!(api.extensions.contains_key("x-tools-owner"));
+    assert!(docker_compose.extensions.contains_key("x-logging"));
+    assert_eq!(docker_compose.volumes["data"], None);
+
+    // nothing is lost writing the file
+    let written = DockerCompose::from_yaml(&docker_compose.to_yaml().unwrap()).unwrap();
+    assert_eq!(written, docker_compose);
 }
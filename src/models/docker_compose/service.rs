@@ -0,0 +1,606 @@
+use super::types::{FileReference, ListOrDict, StringOrList, StringOrNumber};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+/// A service of the compose file, see https://github.com/compose-spec/compose-spec/blob/main/05-services.md
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Service {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<ListOrDict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attach: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blkio_config: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub build: Option<Build>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cap_add: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cap_drop: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cgroup: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cgroup_parent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<StringOrList>, // a shell string or the exec form
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub configs: Option<Vec<FileReference>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_count: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_percent: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_period: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_quota: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_rt_period: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_rt_runtime: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_shares: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpus: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpuset: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credential_spec: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<DependsOn>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deploy: Option<Deploy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub develop: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_cgroup_rules: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub devices: Option<Vec<Device>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dns: Option<StringOrList>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dns_opt: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dns_search: Option<StringOrList>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domainname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entrypoint: Option<StringOrList>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env_file: Option<EnvFile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<ListOrDict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expose: Option<Vec<StringOrNumber>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extends: Option<Extends>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_links: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_hosts: Option<ListOrDict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gpus: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_add: Option<Vec<StringOrNumber>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub healthcheck: Option<Healthcheck>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub init: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipc: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub isolation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_file: Option<StringOrList>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<ListOrDict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logging: Option<Logging>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mac_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mem_limit: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mem_reservation: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mem_swappiness: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memswap_limit: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub networks: Option<ServiceNetworks>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oom_kill_disable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oom_score_adj: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pids_limit: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ports: Option<Vec<Port>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_start: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_stop: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub privileged: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profiles: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pull_policy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restart: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secrets: Option<Vec<FileReference>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security_opt: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shm_size: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stdin_open: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_grace_period: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_signal: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_opt: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sysctls: Option<ListOrDict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tmpfs: Option<StringOrList>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tty: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ulimits: Option<IndexMap<String, Ulimit>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub userns_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uts: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volumes: Option<Vec<ServiceVolume>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volumes_from: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+    #[serde(flatten)]
+    pub extensions: IndexMap<String, Value>, // x-* extensions and fields the model doesn't cover
+}
+
+/// How the image of a service is built, the context or the long syntax
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Build {
+    Context(String),
+    Config(Box<BuildConfig>),
+}
+
+/// The long syntax of `build`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BuildConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dockerfile: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dockerfile_inline: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub args: Option<ListOrDict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_contexts: Option<ListOrDict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_from: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_to: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entitlements: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_hosts: Option<ListOrDict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub isolation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<ListOrDict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_cache: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platforms: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub privileged: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pull: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secrets: Option<Vec<FileReference>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shm_size: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssh: Option<ListOrDict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ulimits: Option<IndexMap<String, Ulimit>>,
+    #[serde(flatten)]
+    pub extensions: IndexMap<String, Value>, // x-* extensions and fields the model doesn't cover
+}
+
+/// The services a service depends on, a list or a map with conditions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DependsOn {
+    List(Vec<String>),
+    Map(IndexMap<String, Dependency>),
+}
+
+/// The long syntax of a dependency
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Dependency {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<DependencyCondition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restart: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+}
+
+impl DependsOn {
+    /// This function returns the names of the services depended on, in the
+    /// order of the compose file.
+    pub fn services(&self) -> Vec<String> {
+        match self {
+            DependsOn::List(services) => services.clone(),
+            DependsOn::Map(services) => services.keys().cloned().collect(),
+        }
+    }
+}
+
+/// When a dependency is considered ready
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DependencyCondition {
+    #[serde(rename = "service_started")]
+    Started,
+    #[serde(rename = "service_healthy")]
+    Healthy,
+    #[serde(rename = "service_completed_successfully")]
+    CompletedSuccessfully,
+}
+
+/// The deployment of a service, see https://github.com/compose-spec/compose-spec/blob/main/deploy.md
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Deploy {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<ListOrDict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placement: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replicas: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resources: Option<Resources>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restart_policy: Option<RestartPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollback_config: Option<UpdateConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_config: Option<UpdateConfig>,
+    #[serde(flatten)]
+    pub extensions: IndexMap<String, Value>, // x-* extensions and fields the model doesn't cover
+}
+
+/// The resource limits and reservations of a deployment
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Resources {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limits: Option<ResourceLimits>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reservations: Option<ResourceLimits>,
+}
+
+/// Limits or reservations of cpus, memory, pids and devices
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpus: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pids: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub devices: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generic_resources: Option<Value>,
+}
+
+/// How the containers of a deployment are restarted
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RestartPolicy {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delay: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window: Option<String>,
+}
+
+/// How a deployment is updated or rolled back
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UpdateConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallelism: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delay: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_action: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monitor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_failure_ratio: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<String>,
+}
+
+/// A device mapped into the container, `host:container[:permissions]` or
+/// the long syntax
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Device {
+    Short(String),
+    Long {
+        source: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        target: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        permissions: Option<String>,
+    },
+}
+
+/// The env files of a service, a path or a list of paths and long entries
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EnvFile {
+    Path(String),
+    List(Vec<EnvFileEntry>),
+}
+
+/// An env file in the list syntax of `env_file`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EnvFileEntry {
+    Path(String),
+    Long {
+        path: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        required: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        format: Option<String>,
+    },
+}
+
+/// The service a service extends, its name in the same file or the long syntax
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Extends {
+    Service(String),
+    Long {
+        service: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file: Option<String>,
+    },
+}
+
+/// How the health of the containers of a service is checked
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Healthcheck {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub test: Option<StringOrList>, // `NONE`, or `CMD`/`CMD-SHELL` followed by the command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retries: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_period: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_interval: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable: Option<bool>,
+}
+
+/// The logging driver of a service
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Logging {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<IndexMap<String, Value>>,
+}
+
+/// The networks of a service, a list of names or a map with the options of
+/// every network
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ServiceNetworks {
+    List(Vec<String>),
+    Map(IndexMap<String, Option<ServiceNetwork>>),
+}
+
+impl ServiceNetworks {
+    /// This function returns the names of the networks, in the order of the
+    /// compose file.
+    pub fn names(&self) -> Vec<String> {
+        match self {
+            ServiceNetworks::List(networks) => networks.clone(),
+            ServiceNetworks::Map(networks) => networks.keys().cloned().collect(),
+        }
+    }
+}
+
+/// The options of a service in a network
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ServiceNetwork {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aliases: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver_opts: Option<IndexMap<String, StringOrNumber>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gw_priority: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipv4_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipv6_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_local_ips: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mac_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<StringOrNumber>,
+}
+
+/// A published port, `[host:]published:target[/protocol]` or the long syntax
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Port {
+    Short(StringOrNumber),
+    Long(PortConfig),
+}
+
+/// The long syntax of a port
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PortConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published: Option<StringOrNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host_ip: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_protocol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+}
+
+/// A limit of `ulimits`, a single value or a soft and a hard limit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Ulimit {
+    Single(StringOrNumber),
+    Limits {
+        soft: StringOrNumber,
+        hard: StringOrNumber,
+    },
+}
+
+/// A volume of a service, `[source:]target[:mode]` or the long syntax
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ServiceVolume {
+    Short(String),
+    Long(Box<ServiceVolumeConfig>),
+}
+
+impl ServiceVolume {
+    /// This function returns the source of the volume, a named volume or a
+    /// host path, None for an anonymous volume.
+    pub fn source(&self) -> Option<String> {
+        match self {
+            ServiceVolume::Short(volume) => match volume.split(':').collect::<Vec<&str>>()[..] {
+                [_] => None,
+                [source, ..] => Some(source.to_string()),
+                [] => None,
+            },
+            ServiceVolume::Long(volume) => volume.source.clone(),
+        }
+    }
+
+    /// This function returns the path the volume is mounted at in the
+    /// container.
+    pub fn target(&self) -> Option<String> {
+        match self {
+            ServiceVolume::Short(volume) => match volume.split(':').collect::<Vec<&str>>()[..] {
+                [target] | [_, target] | [_, target, _] => Some(target.to_string()),
+                _ => None,
+            },
+            ServiceVolume::Long(volume) => volume.target.clone(),
+        }
+    }
+
+    /// This function checks if the source of the volume is a named volume,
+    /// not a host path.
+    pub fn is_named(&self) -> bool {
+        match self {
+            ServiceVolume::Short(_) => self.source().is_some_and(|source| is_volume_name(&source)),
+            ServiceVolume::Long(volume) => {
+                volume.volume_type == "volume" && volume.source.is_some()
+            }
+        }
+    }
+}
+
+/// ## is_volume_name(source: &str) -> bool
+/// This function checks if the source of a short volume is the name of a
+/// volume, host paths start with `.`, `/` or `~`
+///
+/// ### Arguments
+/// - source: &str - The source of the volume
+///
+/// ### Returns
+/// - bool - true for a named volume
+pub fn is_volume_name(source: &str) -> bool {
+    !source.is_empty() && !source.starts_with(['.', '/', '~', '$']) && !source.contains('/')
+}
+
+/// The long syntax of a volume of a service
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ServiceVolumeConfig {
+    #[serde(rename = "type")]
+    pub volume_type: String, // volume, bind, tmpfs, image, npipe or cluster
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consistency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bind: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tmpfs: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<Value>,
+}
@@ -0,0 +1,128 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Number;
+
+/// A value that is a string or a number, compose files accept both for
+/// most numeric fields, e.g. `cpus: 0.5` and `cpus: "0.5"`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StringOrNumber {
+    Number(Number),
+    String(String),
+}
+
+/// A scalar value of an environment variable, label or option
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Scalar {
+    Bool(bool),
+    Number(Number),
+    String(String),
+}
+
+/// A field that is a single string or a list of strings, e.g. `command`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StringOrList {
+    String(String),
+    List(Vec<String>),
+}
+
+/// A field that is a list of `KEY=VALUE` strings or a map, e.g.
+/// `environment` and `labels`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ListOrDict {
+    List(Vec<String>),
+    Dict(IndexMap<String, Option<Scalar>>),
+}
+
+/// A top level resource that is created outside of compose, `true` or the
+/// legacy `{ name: ... }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum External {
+    Bool(bool),
+    Named { name: String },
+}
+
+/// A config or secret granted to a service, its name or the long syntax
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FileReference {
+    Short(String),
+    Long {
+        source: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        target: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        uid: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        gid: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        mode: Option<StringOrNumber>,
+    },
+}
+
+impl ListOrDict {
+    /// This function returns the entries as a map, `KEY` without a value in
+    /// the list syntax and `KEY:` in the map syntax have no value.
+    pub fn to_map(&self) -> IndexMap<String, Option<String>> {
+        match self {
+            ListOrDict::List(entries) => entries
+                .iter()
+                .map(|entry| match entry.split_once('=') {
+                    Some((key, value)) => (key.to_string(), Some(value.to_string())),
+                    None => (entry.to_string(), None),
+                })
+                .collect(),
+            ListOrDict::Dict(entries) => entries
+                .iter()
+                .map(|(key, value)| (key.clone(), value.as_ref().map(Scalar::to_string)))
+                .collect(),
+        }
+    }
+}
+
+impl FileReference {
+    /// This function returns the name of the config or secret.
+    pub fn source(&self) -> &str {
+        match self {
+            FileReference::Short(source) => source,
+            FileReference::Long { source, .. } => source,
+        }
+    }
+}
+
+impl std::fmt::Display for Scalar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Scalar::Bool(value) => write!(f, "{}", value),
+            Scalar::Number(value) => write!(f, "{}", value),
+            Scalar::String(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl std::fmt::Display for StringOrNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StringOrNumber::Number(value) => write!(f, "{}", value),
+            StringOrNumber::String(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+#[test]
+fn test_docker_compose_list_or_dict() {
+    let list: ListOrDict = serde_yaml::from_str("[DEBUG=1, TOKEN]").unwrap();
+    let dict: ListOrDict = serde_yaml::from_str("{DEBUG: 1, TOKEN: null}").unwrap();
+
+    assert!(matches!(list, ListOrDict::List(_)));
+    assert!(matches!(dict, ListOrDict::Dict(_)));
+    assert_eq!(list.to_map(), dict.to_map());
+    assert_eq!(list.to_map()["DEBUG"], Some("1".to_string()));
+
+    let command: StringOrList = serde_yaml::from_str("npm start").unwrap();
+    assert_eq!(command, StringOrList::String("npm start".to_string()));
+}
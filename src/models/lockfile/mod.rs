@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+/// This struct represents the `tools.lock` file, which pins the commit
+/// of every service (and optionally the image digests) for each project
+/// version.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LockFile {
+    pub versions: Vec<LockedVersion>, // required - The locked state of every project version, oldest first
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct LockedVersion {
+    pub project_version: String, // required - The project version this entry pins
+    pub services: Vec<LockedService>, // required - The commit of every service
+    pub images: Option<Vec<LockedImage>>, // optional - The digest of every image in the compose file
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct LockedService {
+    pub path: String, // required - The path of the service relative to the project root
+    pub commit: String, // required - The full commit hash the service is pinned to
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct LockedImage {
+    pub service: String, // required - The name of the service in the compose file
+    pub image: String,   // required - The image reference in the compose file
+    pub digest: String,  // required - The repository digest the image resolved to
+}
+
+impl LockFile {
+    /// This function returns the lock file from a yaml formated text string.
+    pub fn from_yaml(yaml: &str) -> Result<Self, Box<dyn Error>> {
+        let lock_file: LockFile = serde_yaml::from_str(yaml)?;
+        Ok(lock_file)
+    }
+
+    /// This function returns the lock file as a yaml formated text string.
+    pub fn to_yaml(&self) -> Result<String, Box<dyn Error>> {
+        let mut yaml = serde_yaml::to_string(self)?;
+        yaml.insert_str(0, "---\n");
+        Ok(yaml)
+    }
+
+    /// This function returns the locked state of a project version.
+    pub fn get_version(&self, project_version: &str) -> Option<&LockedVersion> {
+        self.versions
+            .iter()
+            .find(|locked| locked.project_version == project_version)
+    }
+
+    /// This function adds the locked state of a project version, replacing
+    /// the existing entry of the same version.
+    pub fn set_version(&mut self, locked_version: LockedVersion) {
+        match self
+            .versions
+            .iter_mut()
+            .find(|locked| locked.project_version == locked_version.project_version)
+        {
+            Some(existing) => *existing = locked_version,
+            None => self.versions.push(locked_version),
+        }
+    }
+}
+
+#[test]
+fn test_lock_file() {
+    let lock_yaml = r#"---
+versions:
+- project_version: 0.1.0
+  services:
+  - path: backend
+    commit: 3f2a1b4c5d6e7f8091a2b3c4d5e6f708192a3b4c
+  images: null
+"#;
+
+    let mut lock_file = LockFile::from_yaml(lock_yaml).unwrap();
+    assert_eq!(lock_yaml, lock_file.to_yaml().unwrap());
+
+    lock_file.set_version(LockedVersion {
+        project_version: "0.1.0".to_string(),
+        services: vec![LockedService {
+            path: "backend".to_string(),
+            commit: "0123456789abcdef0123456789abcdef01234567".to_string(),
+        }],
+        images: None,
+    });
+    lock_file.set_version(LockedVersion {
+        project_version: "0.2.0".to_string(),
+        services: Vec::new(),
+        images: None,
+    });
+
+    assert_eq!(lock_file.versions.len(), 2);
+    assert_eq!(
+        lock_file.get_version("0.1.0").unwrap().services[0].commit,
+        "0123456789abcdef0123456789abcdef01234567"
+    );
+    assert!(lock_file.get_version("0.3.0").is_none());
+}
+
+#[test]
+fn test_lock_file_read_from_file() {
+    let lock_from_file = std::fs::read_to_string("src/test/models/lockfile/tools.lock").unwrap();
+
+    match LockFile::from_yaml(&lock_from_file) {
+        Ok(lock_file) => assert!(lock_file.get_version("0.1.0").is_some()),
+        Err(e) => panic!("{}", e),
+    }
+}
@@ -1,4 +1,5 @@
 pub mod app_config;
 pub mod docker_compose;
+pub mod lockfile;
 pub mod project;
 pub mod repository;
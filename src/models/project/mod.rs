@@ -1,6 +1,6 @@
 use semver::{BuildMetadata, Prerelease, Version};
 use serde::{Deserialize, Serialize};
-use std::{error::Error, fs};
+use std::error::Error;
 
 /// This struct represents the structure of the project and
 /// is used to serialize and deserialize the project definition file.
@@ -13,10 +13,14 @@ pub struct Project {
     pub languages: Option<Vec<String>>, // optional - This is a list of programming languages used in the service
     pub frameworks: Option<Vec<String>>, // optional - This is the framework the service is developed
     pub services: Option<Vec<Project>>, // required for parent projects, or hierarchical projects - This is the list of services/children in the project
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>, // optional - This is the path of the service relative to the project root, used for services that are not git submodules
     pub repo: Option<Vec<ProjectRepository>>, // optional - This is the configuration for the repository
     pub parent: Option<Box<Project>>, // required for hierarchical projects - This is the parent project of the microservice
     pub from_template: Option<bool>, // optional - This indicates if the repository was created from a templates
     pub template: Option<String>, // optional - This is the name of the source template repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<Vec<String>>, // optional - This is the list of services the service needs to run, used by `tools compose generate`
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -37,6 +41,7 @@ impl Project {
         Ok(project)
     }
 
+    #[allow(dead_code)]
     pub fn to_yaml(&self) -> Result<String, Box<dyn Error>> {
         let mut yaml = serde_yaml::to_string(self)?;
         yaml.insert_str(0, "---\n");
@@ -69,18 +74,22 @@ impl Project {
         self.update_version(&version);
     }
 
-    /// This function updates the prerelease version of the project version.
-    pub fn update_prerelease_version(&mut self, prerelease: &str) {
+    /// This function updates the prerelease version of the project version,
+    /// an empty string removes it.
+    pub fn update_prerelease_version(&mut self, prerelease: &str) -> Result<(), semver::Error> {
         let mut version = self.get_version();
-        version.pre = Prerelease::new(prerelease).unwrap();
+        version.pre = Prerelease::new(prerelease)?;
         self.update_version(&version);
+        Ok(())
     }
 
-    /// This function updates the build metadata of the project version.
-    pub fn update_build_version(&mut self, build: &str) {
+    /// This function updates the build metadata of the project version, an
+    /// empty string removes it.
+    pub fn update_build_version(&mut self, build: &str) -> Result<(), semver::Error> {
         let mut version = self.get_version();
-        version.build = BuildMetadata::new(build).unwrap();
+        version.build = BuildMetadata::new(build)?;
         self.update_version(&version);
+        Ok(())
     }
 
     /// This function returns the project version as a semver::Version
@@ -88,6 +97,51 @@ impl Project {
     pub fn get_version(&self) -> Version {
         Version::parse(&self.version).unwrap()
     }
+
+    /// This function returns the path of the service relative to the
+    /// project root, the declared `path` or else the service name.
+    pub fn service_path(&self) -> String {
+        self.path.clone().unwrap_or(self.name.clone())
+    }
+
+    /// This function checks if the service lives in a directory of its
+    /// own, because it declares a `path` or a repository to clone.
+    pub fn has_directory(&self) -> bool {
+        self.path.is_some() || self.get_git_url().is_some()
+    }
+
+    /// This function returns the name and path of every service in the
+    /// service tree that lives in a directory of its own, in the order they
+    /// are declared.
+    pub fn get_service_paths(&self) -> Vec<(String, String)> {
+        self.get_all_services()
+            .into_iter()
+            .filter(|service| service.has_directory())
+            .map(|service| (service.name.clone(), service.service_path()))
+            .collect()
+    }
+
+    /// This function returns every service in the service tree, depth
+    /// first, in the order they are declared.
+    pub fn get_all_services(&self) -> Vec<&Project> {
+        let mut services = Vec::new();
+
+        for service in self.services.iter().flatten() {
+            services.push(service);
+            services.append(&mut service.get_all_services());
+        }
+
+        services
+    }
+
+    /// This function returns the git URL of the first repository of the
+    /// project that has one.
+    pub fn get_git_url(&self) -> Option<&str> {
+        self.repo
+            .iter()
+            .flatten()
+            .find_map(|repo| repo.git_url.as_deref())
+    }
 }
 
 #[test]
@@ -139,23 +193,70 @@ services:
     let project_from_yaml = Project::from_yaml(project);
 
     match project_from_yaml {
-        Ok(_project) => {
-            assert!(true)
-        }
+        Ok(_project) => {}
         Err(error) => {
-            assert!(false, "Error: {}", error)
+            panic!("Error: {}", error)
         }
     }
 }
 
+#[test]
+fn test_project_service_paths() {
+    let project = r#"---
+name: test-project
+domain: test-domain.test
+version: 0.1.0
+description: This is a test project
+services:
+    - name: test-backend
+      domain: test-domain.test
+      version: 0.1.0
+      description: This is a test backend service
+      path: services/backend
+      services:
+          - name: test-worker
+            domain: test-domain.test
+            version: 0.1.0
+            description: This is a test worker service
+            path: services/backend/worker
+    - name: test-frontend
+      domain: test-domain.test
+      version: 0.1.0
+      description: This is a test frontend service
+    - name: test-gateway
+      domain: test-domain.test
+      version: 0.1.0
+      description: This is a test gateway service
+      repo:
+          - name: test-gateway
+            description: The gateway
+            git_url: https://example.test/test-gateway.git
+"#;
+
+    let project = Project::from_yaml(project).unwrap();
+
+    assert_eq!(
+        project.get_service_paths(),
+        vec![
+            ("test-backend".to_string(), "services/backend".to_string()),
+            (
+                "test-worker".to_string(),
+                "services/backend/worker".to_string()
+            ),
+            ("test-gateway".to_string(), "test-gateway".to_string()),
+        ]
+    );
+    assert!(!project.to_yaml().unwrap().contains("path: null"));
+}
+
 #[test]
 fn test_project_read_from_file() {
-    let project_from_file = fs::read_to_string("src/test/models/project/project.yaml").unwrap();
+    let project_from_file = std::fs::read_to_string("src/test/models/project/project.yaml").unwrap();
 
     let from_file = Project::from_yaml(&project_from_file);
 
     match from_file {
-        Ok(_project) => assert!(true),
-        Err(e) => assert!(false, "{}", e.to_string()),
+        Ok(_project) => {}
+        Err(e) => panic!("{}", e.to_string()),
     }
 }